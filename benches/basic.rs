@@ -16,6 +16,11 @@ fn init(b: &mut Bencher) {
     b.iter(|| support::build_context());
 }
 
+#[bench]
+fn init_lazy(b: &mut Bencher) {
+    b.iter(|| support::build_lazy_context());
+}
+
 #[bench]
 fn clear(b: &mut Bencher) {
     let display = support::build_context();
@@ -27,6 +32,28 @@ fn clear(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn clear_checked_context(b: &mut Bencher) {
+    let (display, is_current_calls) = support::build_checked_context();
+
+    let mut frames = 0;
+    is_current_calls.set(0);
+
+    b.iter(|| {
+        // the context is current for the whole frame
+        unsafe { display.assume_current(); }
+
+        let mut target = glium::Frame::new(display.clone(), (800, 600));
+        target.clear_color(0.0, 0.0, 0.0, 1.0);
+        target.clear_color(1.0, 1.0, 1.0, 1.0);
+        target.finish().unwrap();
+        frames += 1;
+    });
+
+    // at most one call when swapping buffers, instead of one for each operation
+    assert!(is_current_calls.get() <= 2 * frames);
+}
+
 #[bench]
 fn create_program(b: &mut Bencher) {
     let display = support::build_context();
@@ -146,3 +173,31 @@ fn build_buffer(b: &mut Bencher) {
         ).unwrap()
     });
 }
+
+#[derive(Copy, Clone)]
+struct Particle {
+    position: [f32; 2],
+    color: [f32; 3],
+}
+
+implement_vertex!(Particle, position, color);
+
+#[bench]
+fn write_vertex_buffer_every_frame(b: &mut Bencher) {
+    let display = support::build_context();
+
+    let particles = vec![Particle { position: [0.0, 0.0], color: [1.0, 1.0, 1.0] }; 1024];
+    let buffer = glium::VertexBuffer::dynamic(&display, &particles).unwrap();
+
+    b.iter(|| buffer.write(&particles));
+}
+
+#[bench]
+fn write_streaming_vertex_buffer_every_frame(b: &mut Bencher) {
+    let display = support::build_context();
+
+    let particles = vec![Particle { position: [0.0, 0.0], color: [1.0, 1.0, 1.0] }; 1024];
+    let mut buffer = glium::vertex::StreamingVertexBuffer::new(&display, particles.len()).unwrap();
+
+    b.iter(|| buffer.next_region().write(&particles));
+}
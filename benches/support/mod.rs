@@ -1,8 +1,15 @@
 extern crate glium;
 extern crate libc;
 
+use glium::backend::Backend;
+
+use std::cell::Cell;
 use std::ptr;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+/// Size passed to the last call to `glBufferData`, returned when querying `GL_BUFFER_SIZE`.
+static LAST_BUFFER_SIZE: AtomicIsize = AtomicIsize::new(0);
 
 /// Builds a context with dummy OpenGL functions.
 pub fn build_context() -> Rc<glium::backend::Context> {
@@ -11,9 +18,29 @@ pub fn build_context() -> Rc<glium::backend::Context> {
     }
 }
 
+/// Builds a context with dummy OpenGL functions whose capabilities are loaded lazily.
+pub fn build_lazy_context() -> Rc<glium::backend::Context> {
+    unsafe {
+        glium::backend::Context::new_lazy::<_>(DummyBackend, false, Default::default()).unwrap()
+    }
+}
+
+/// Builds a context with dummy OpenGL functions that checks whether it is current, and
+/// returns the number of times the backend has been asked.
+pub fn build_checked_context() -> (Rc<glium::backend::Context>, Rc<Cell<usize>>) {
+    let calls = Rc::new(Cell::new(0));
+    let backend = CountingBackend { calls: calls.clone() };
+
+    let context = unsafe {
+        glium::backend::Context::new::<_>(backend, true, Default::default()).unwrap()
+    };
+
+    (context, calls)
+}
+
 struct DummyBackend;
 
-unsafe impl glium::backend::Backend for DummyBackend {
+unsafe impl Backend for DummyBackend {
     fn swap_buffers(&self) -> Result<(), glium::SwapBuffersError> {
         Ok(())
     }
@@ -32,10 +59,17 @@ unsafe impl glium::backend::Backend for DummyBackend {
             },
 
             "glBufferData" => {
-                extern "system" fn buffer_data(_: u32, _: isize, _: *const (), _: u32) {}
+                extern "system" fn buffer_data(_: u32, size: isize, _: *const (), _: u32) {
+                    LAST_BUFFER_SIZE.store(size, Ordering::Relaxed);
+                }
                 buffer_data as *const _
             },
 
+            "glBufferSubData" => {
+                extern "system" fn buffer_sub_data(_: u32, _: isize, _: isize, _: *const ()) {}
+                buffer_sub_data as *const _
+            },
+
             "glCompileShader" => {
                 extern "system" fn compile(_: u32) {}
                 compile as *const _
@@ -102,7 +136,9 @@ unsafe impl glium::backend::Backend for DummyBackend {
             "glGetBufferParameteriv" => {
                 extern "system" fn get_buf_paramiv(_: u32, param: u32, out: *mut i32) {
                     match param {
-                        0x8764 /* GL_BUFFER_SIZE */ => unsafe { *out = 60 },      // FIXME:
+                        0x8764 /* GL_BUFFER_SIZE */ => unsafe {
+                            *out = LAST_BUFFER_SIZE.load(Ordering::Relaxed) as i32
+                        },
                         _ => unsafe { *out = 0; }
                     }
                 }
@@ -185,7 +221,39 @@ unsafe impl glium::backend::Backend for DummyBackend {
         (800, 600)
     }
 
+    fn resize(&self, _: (u32, u32)) {
+    }
+
+    fn is_current(&self) -> bool {
+        true
+    }
+
+    unsafe fn make_current(&self) {
+    }
+}
+
+struct CountingBackend {
+    calls: Rc<Cell<usize>>,
+}
+
+unsafe impl Backend for CountingBackend {
+    fn swap_buffers(&self) -> Result<(), glium::SwapBuffersError> {
+        DummyBackend.swap_buffers()
+    }
+
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const libc::c_void {
+        DummyBackend.get_proc_address(symbol)
+    }
+
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        DummyBackend.get_framebuffer_dimensions()
+    }
+
+    fn resize(&self, _: (u32, u32)) {
+    }
+
     fn is_current(&self) -> bool {
+        self.calls.set(self.calls.get() + 1);
         true
     }
 
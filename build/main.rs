@@ -38,6 +38,7 @@ where
             "GL_ARB_depth_texture",
             "GL_ARB_direct_state_access",
             "GL_ARB_draw_buffers",
+            "GL_ARB_draw_buffers_blend",
             "GL_ARB_ES2_compatibility",
             "GL_ARB_ES3_compatibility",
             "GL_ARB_ES3_1_compatibility",
@@ -61,7 +62,10 @@ where
             "GL_ARB_texture_rg",
             "GL_ARB_texture_rgb10_a2ui",
             "GL_ARB_texture_storage",
+            "GL_ARB_texture_view",
+            "GL_ARB_transform_feedback2",
             "GL_ARB_transform_feedback3",
+            "GL_ARB_transform_feedback_instanced",
             "GL_ARB_vertex_buffer_object",
             "GL_ARB_vertex_shader",
             "GL_ATI_draw_buffers",
@@ -90,6 +94,8 @@ where
             "GL_KHR_robustness",
             "GL_NVX_gpu_memory_info",
             "GL_NV_conditional_render",
+            "GL_NV_conservative_raster",
+            "GL_NV_framebuffer_multisample_coverage",
             "GL_NV_vertex_attrib_integer_64bit",
         ],
     );
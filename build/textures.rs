@@ -312,9 +312,11 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             use crate::texture::get_format::{{InternalFormat, InternalFormatType, GetFormatError}};
             use crate::texture::pixel_buffer::PixelBuffer;
             use crate::texture::{{TextureCreationError, Texture1dDataSource, Texture2dDataSource}};
-            use crate::texture::{{Texture3dDataSource, Texture2dDataSink, MipmapsOption, CompressedMipmapsOption}};
+            use crate::texture::TextureViewError;
+            use crate::texture::{{Texture3dDataSource, Texture1dDataSink, Texture2dDataSink}};
+            use crate::texture::{{MipmapsOption, CompressedMipmapsOption}};
             use crate::texture::{{RawImage1d, RawImage2d, RawImage3d, CubeLayer}};
-            use crate::texture::pixel::PixelValue;
+            use crate::texture::pixel::{{PixelValue, Bgra}};
 
             use crate::image_format::{{ClientFormatAny, TextureFormatRequest}};
             use crate::image_format::{{UncompressedFloatFormat, UncompressedIntFormat}};
@@ -326,6 +328,7 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             use crate::framebuffer;
 
             use crate::Rect;
+            use crate::ReadError;
 
             use crate::GlObject;
             use crate::TextureExt;
@@ -584,6 +587,101 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                mipmap_default = mipmap_default, gen_doc = gen_doc)).unwrap();
     }
 
+    // writing the `from_images` function
+    if dimensions == TextureDimensions::Texture2dArray {
+        let gen_doc = if is_compressed {
+            "/// No mipmap level (except for the main level) will be allocator nor generated."
+        } else {
+            "/// This function will automatically generate all mipmaps of the texture."
+        };
+
+        (writeln!(dest, "
+                /// Builds a new texture array whose layers are the given images, in order.
+                ///
+                /// All the images must have the same dimensions and the same client format,
+                /// otherwise `TextureCreationError::InconsistentImages` is returned. Returns
+                /// `TextureCreationError::DimensionsNotSupported` if `images` is empty.
+                ///
+                {gen_doc}
+                pub fn from_images<F: ?Sized, P>(facade: &F, images: &[RawImage2d<'_, P>])
+                                                 -> Result<{name}, TextureCreationError>
+                                                 where P: PixelValue + Clone, F: Facade
+                {{
+                    let first = match images.first() {{
+                        Some(first) => first,
+                        None => return Err(TextureCreationError::DimensionsNotSupported),
+                    }};
+
+                    if images.iter().any(|i| i.width != first.width || i.height != first.height ||
+                                             i.format != first.format)
+                    {{
+                        return Err(TextureCreationError::InconsistentImages);
+                    }}
+
+                    let layers = images.iter().map(|i| RawImage2d {{
+                        data: Cow::Borrowed(&i.data[..]),
+                        width: i.width,
+                        height: i.height,
+                        format: i.format,
+                    }}).collect();
+
+                    {name}::new_impl(facade, layers, None, {mipmap_default})
+                }}
+            ", name = name, mipmap_default = mipmap_default, gen_doc = gen_doc)).unwrap();
+    }
+
+    // writing the `with_faces` function
+    if dimensions == TextureDimensions::CubemapArray &&
+            (ty == TextureType::Regular || ty == TextureType::Srgb ||
+             ty == TextureType::Integral || ty == TextureType::Unsigned)
+    {
+        (writeln!(dest, "
+                /// Builds a new cubemap array by uploading the faces of each layer.
+                ///
+                /// `data` contains one element per layer of the array. Each element contains the
+                /// six faces of the cubemap in the order `PositiveX`, `NegativeX`, `PositiveY`,
+                /// `NegativeY`, `PositiveZ`, `NegativeZ`.
+                ///
+                /// All the faces must be square and have the same dimensions and the same client
+                /// format, otherwise `TextureCreationError::InconsistentImages` is returned.
+                /// Returns `TextureCreationError::DimensionsNotSupported` if `data` is empty.
+                pub fn with_faces<'a, F: ?Sized, T>(facade: &F, data: Vec<[T; 6]>,
+                                                    mipmaps: MipmapsOption)
+                                                    -> Result<{name}, TextureCreationError>
+                                                    where T: Texture2dDataSource<'a>, F: Facade
+                {{
+                    let format = {default_format};
+
+                    let faces: Vec<_> = data.into_iter()
+                                            .flat_map(|layer| layer.into_iter().map(|face| face.into_raw()))
+                                            .collect();
+
+                    let first = match faces.first() {{
+                        Some(first) => first,
+                        None => return Err(TextureCreationError::DimensionsNotSupported),
+                    }};
+
+                    if first.width != first.height ||
+                       faces.iter().any(|f| f.width != first.width || f.height != first.height ||
+                                            f.format != first.format)
+                    {{
+                        return Err(TextureCreationError::InconsistentImages);
+                    }}
+
+                    let dimension = first.width;
+                    let array_size = faces.len() as u32 / 6;
+
+                    let RawImage3d {{ data, format: client_format, .. }} =
+                                                            RawImage3d::from_vec_raw2d(&faces);
+                    let client_format = ClientFormatAny::ClientFormat(client_format);
+
+                    Ok({name}(any::new_texture(facade, format, Some((client_format, data)),
+                                               mipmaps.into(),
+                                               Dimensions::CubemapArray {{ dimension, array_size }})?))
+                }}
+            ", name = name, default_format = default_format)).unwrap();
+    }
+
     // writing the `with_mipmaps` function
     if !dimensions.is_multisample() && !dimensions.is_cube() {
         let param = match dimensions {
@@ -785,6 +883,68 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         (writeln!(dest, "}}")).unwrap();
     }
 
+    // writing the `with_mipmap_data` function
+    if dimensions == TextureDimensions::Texture2d &&
+            (ty == TextureType::Regular || ty == TextureType::Srgb)
+    {
+        (write!(dest, r#"
+                /// Builds a texture with a specific format whose levels are initialized with the
+                /// given data, the main level first.
+                ///
+                /// Storage is allocated for exactly as many levels as provided, and each level is
+                /// uploaded as it is. This is useful to load mipmaps that have been generated by
+                /// an offline tool.
+                ///
+                /// Returns `TextureCreationError::InconsistentImages` if `levels` is empty, if the
+                /// dimensions of a level aren't half of the dimensions of the previous level
+                /// (rounded down, with a minimum of 1), or if there are more levels than the
+                /// dimensions of the main level allow.
+                pub fn with_mipmap_data<'a, F: ?Sized, I, T>(facade: &F, format: {format}, levels: I)
+                                                             -> Result<{name}, TextureCreationError>
+                                                             where I: IntoIterator<Item = T>,
+                                                                   T: {data_source_trait}<'a>,
+                                                                   T::Data: PixelValue, F: Facade
+                {{
+                    let levels: Vec<_> = levels.into_iter().map(|level| level.into_raw()).collect();
+                    let (width, height) = match levels.first() {{
+                        Some(level) => (level.width, level.height),
+                        None => return Err(TextureCreationError::InconsistentImages),
+                    }};
+
+                    // a chain can't have more than `log2(max(width, height)) + 1` levels
+                    let max_levels = 32 - ::std::cmp::max(width, height).leading_zeros();
+                    if levels.len() > max_levels as usize {{
+                        return Err(TextureCreationError::InconsistentImages);
+                    }}
+
+                    for (index, level) in levels.iter().enumerate() {{
+                        let expected = (::std::cmp::max(1, width >> index),
+                                        ::std::cmp::max(1, height >> index));
+                        if (level.width, level.height) != expected {{
+                            return Err(TextureCreationError::InconsistentImages);
+                        }}
+                    }}
+
+                    let mipmaps = MipmapsOption::EmptyMipmapsMax(levels.len() as u32 - 1);
+                    let texture = {name}::empty_with_format(facade, format, mipmaps, width, height)?;
+
+                    for (index, level) in levels.into_iter().enumerate() {{
+                        let rect = Rect {{
+                            left: 0,
+                            bottom: 0,
+                            width: level.width,
+                            height: level.height,
+                        }};
+
+                        texture.mipmap(index as u32).unwrap().write(rect, level);
+                    }}
+
+                    Ok(texture)
+                }}
+            "#, format = relevant_format, name = name,
+                data_source_trait = data_source_trait)).unwrap();
+    }
+
     // writing the `empty_with_mipmaps` function
     if !is_compressed {
         // opening function
@@ -825,6 +985,28 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 }}
         ", format = relevant_format, name = name)).unwrap();
 
+    // writing the 'view_of' function
+    (writeln!(dest, "
+                /// Builds a view of an existing texture that interprets its content with another
+                /// format, without copying anything.
+                ///
+                /// The texture must have the same kind of dimensions as this type. See
+                /// `TextureAny::create_view` for the meaning of the other parameters.
+                pub fn view_of(texture: &TextureAny, format: {format}, min_level: u32,
+                               num_levels: u32, min_layer: u32, num_layers: u32)
+                               -> Result<{name}, TextureViewError>
+                {{
+                    match texture.dimensions() {{
+                        Dimensions::{dimensions} {{ .. }} => (),
+                        _ => return Err(TextureViewError::IncompatibleDimensions),
+                    }};
+
+                    let format = format.to_texture_format();
+                    texture.create_view(format, min_level, num_levels, min_layer, num_layers)
+                           .map({name})
+                }}
+        ", format = relevant_format, name = name, dimensions = format!("{:?}", dimensions))).unwrap();
+
     // writing the 'new_from_fd' function
     (writeln!(dest, r#"
                 /// Builds a new texture reference from an existing texture, externally created by a foreign
@@ -915,6 +1097,42 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 }}
             "#)).unwrap();
 
+        (write!(dest, r#"
+                /// Reads the content of the texture to RAM in the BGRA format.
+                ///
+                /// This is often the native format of window systems and video encoders. Desktop
+                /// OpenGL always supports it, but OpenGL ES requires the `GL_EXT_read_format_bgra`
+                /// extension. Returns `ReadError::OutputFormatNotSupported` if it's not supported.
+                #[inline]
+                pub fn read_bgra<T>(&self) -> Result<T, ReadError> where T: Texture2dDataSink<Bgra> {{
+                    let rect = Rect {{ left: 0, bottom: 0, width: self.get_width(),
+                                       height: self.get_height().unwrap_or(1) }};
+                    self.0.main_level().first_layer().into_image(None).unwrap().try_raw_read(&rect)
+                }}
+            "#)).unwrap();
+
+        (write!(dest, r#"
+                /// Reads the content of the texture to RAM in the pixel format `P`.
+                ///
+                /// Contrary to `read`, only the channels of `P` are transferred. For example
+                /// reading to `u8` only fetches the red channel, which is four times less data
+                /// than `(u8, u8, u8, u8)`. Channels that don't exist in the texture are read as
+                /// `0`, except alpha which is read as `1`.
+                ///
+                /// Returns `ReadError::OutputFormatNotSupported` if the backend can't read to
+                /// this format. OpenGL ES only supports `(u8, u8, u8, u8)`. Returns
+                /// `ReadError::IncompatibleFormat` if the format can't hold the content of the
+                /// texture.
+                #[inline]
+                pub fn read_with_format<T, P>(&self) -> Result<T, ReadError>
+                                              where T: Texture2dDataSink<P>, P: PixelValue
+                {{
+                    let rect = Rect {{ left: 0, bottom: 0, width: self.get_width(),
+                                       height: self.get_height().unwrap_or(1) }};
+                    self.0.main_level().first_layer().into_image(None).unwrap().try_raw_read(&rect)
+                }}
+            "#)).unwrap();
+
         (write!(dest, r#"
                 /// Reads the content of the texture into a buffer in video memory. This method may
                 /// only read `U8U8U8U8` data, as it is the only format guaranteed to be supported
@@ -966,6 +1184,75 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             "#)).unwrap();
     }
 
+    // writing the `read` functions of 1D textures
+    if dimensions == TextureDimensions::Texture1d &&
+       (ty == TextureType::Regular || ty == TextureType::Srgb || ty == TextureType::Unsigned)
+    {
+        (write!(dest, r#"
+                /// Reads the content of the texture to RAM. This method may only read `U8U8U8U8`
+                /// data, as it is the only format guaranteed to be supported across all OpenGL
+                /// versions.
+                ///
+                /// You should avoid doing this at all cost during performance-critical
+                /// operations (for example, while you're drawing).
+                /// Use `read_to_pixel_buffer` instead.
+                #[inline]
+                pub fn read<T>(&self) -> T where T: Texture1dDataSink<(u8, u8, u8, u8)> {{
+                    unsafe {{ self.unchecked_read() }}
+                }}
+            "#)).unwrap();
+
+        (write!(dest, r#"
+                /// Reads the content of the texture into a buffer in video memory. This method may
+                /// only read `U8U8U8U8` data, as it is the only format guaranteed to be supported
+                /// across all OpenGL versions.
+                ///
+                /// This operation copies the texture's data into a buffer in video memory
+                /// (a pixel buffer). Contrary to the `read` function, this operation is
+                /// done asynchronously and doesn't need a synchronization.
+                #[inline]
+                pub fn read_to_pixel_buffer(&self) -> PixelBuffer<(u8, u8, u8, u8)> {{
+                    unsafe {{ self.unchecked_read_to_pixel_buffer() }}
+                }}
+            "#)).unwrap();
+
+        (write!(dest, r#"
+                /// Unsafely reads the content of the texture to RAM in the specified pixel format.
+                /// It is possible that the current OpenGL context does not support the given
+                /// format, in which case the returned data will be invalid.
+                ///
+                /// You should avoid doing this at all cost during performance-critical
+                /// operations (for example, while you're drawing).
+                /// Use `read_to_pixel_buffer` instead.
+                #[inline]
+                pub unsafe fn unchecked_read<T, P>(&self) -> T where T: Texture1dDataSink<P>, P: PixelValue {{
+                    let rect = Rect {{ left: 0, bottom: 0, width: self.get_width(), height: 1 }};
+                    let rows: Vec<Vec<P>> = self.0.main_level().first_layer().into_image(None)
+                                                  .unwrap().raw_read(&rect);
+                    let data: Vec<P> = rows.into_iter().flatten().collect();
+                    T::from_raw(Cow::Owned(data), rect.width)
+                }}
+            "#)).unwrap();
+
+        (write!(dest, r#"
+                /// Unsafely reads the content of the texture into a buffer in video memory. It is
+                /// possible that the current OpenGL context does not support the given format, in
+                /// which case the returned data will be invalid.
+                ///
+                /// This operation copies the texture's data into a buffer in video memory
+                /// (a pixel buffer). Contrary to the `read` function, this operation is
+                /// done asynchronously and doesn't need a synchronization.
+                #[inline]
+                pub unsafe fn unchecked_read_to_pixel_buffer<P>(&self) -> PixelBuffer<P> where P: PixelValue {{
+                    let rect = Rect {{ left: 0, bottom: 0, width: self.get_width(), height: 1 }};
+                    let pb = PixelBuffer::new_empty(self.0.get_context(), rect.width as usize);
+                    self.0.main_level().first_layer().into_image(None).unwrap()
+                          .raw_read_to_pixel_buffer(&rect, &pb);
+                    pb
+                }}
+            "#)).unwrap();
+    }
+
     // writing the `read_compressed_data` function
     if is_compressed && !dimensions.is_array() {
         (write!(dest, r#"
@@ -1020,6 +1307,27 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 compressed_restrictions = compressed_restrictions)).unwrap();
     }
 
+    // writing the `write` function of 1D textures
+    if dimensions == TextureDimensions::Texture1d &&
+            (ty == TextureType::Regular || ty == TextureType::Srgb)
+    {
+        (write!(dest, r#"
+                /// Uploads some data in the texture, starting at the pixel `offset`.
+                ///
+                /// Note that this may cause a synchronization if you use the texture right before
+                /// or right after this call. Prefer creating a whole new texture if you change a
+                /// huge part of it.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if `offset` plus the width of `data` is larger than the texture.
+                #[inline]
+                pub fn write<'a, T>(&self, offset: u32, data: T) where T: {data_source_trait}<'a> {{
+                    self.main_level().write(offset, data)
+                }}
+            "#, data_source_trait = data_source_trait)).unwrap();
+    }
+
     // writing the `write_compressed_data` function
     // TODO: implement for other types too
     if dimensions == TextureDimensions::Texture2d && is_compressed
@@ -1232,6 +1540,32 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                     compressed_restrictions = compressed_restrictions)).unwrap();
         }
 
+        // writing the `write` function for mipmaps of 1D textures.
+        if dimensions == TextureDimensions::Texture1d &&
+                (ty == TextureType::Regular || ty == TextureType::Srgb)
+        {
+            (write!(dest, r#"
+                    /// Uploads some data in the texture level, starting at the pixel `offset`.
+                    ///
+                    /// Note that this may cause a synchronization if you use the texture right before
+                    /// or right after this call.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if `offset` plus the width of `data` is larger than the level.
+                    pub fn write<'a, T>(&self, offset: u32, data: T) where T: {data_source_trait}<'a> {{
+                        let RawImage1d {{ data, width, format: client_format }} = data.into_raw();
+
+                        assert!(offset + width <= self.0.get_width());
+
+                        let client_format = ClientFormatAny::ClientFormat(client_format);
+
+                        self.0.upload_texture(offset, 0, 0, (client_format, data),
+                                              width, None, None, true).unwrap()
+                    }}
+                "#, data_source_trait = data_source_trait)).unwrap();
+        }
+
         // writing the `write_compressed_data` function for mipmaps.
         // TODO: implement for other types too
         if dimensions == TextureDimensions::Texture2d && is_compressed
@@ -0,0 +1,65 @@
+#[macro_use]
+extern crate glium;
+mod support;
+
+use glium::{Display};
+use glutin::surface::WindowSurface;
+use support::{ApplicationContext, State};
+
+struct Application { }
+
+impl ApplicationContext for Application {
+    const WINDOW_TITLE:&'static str = "Glium compute shader example";
+
+    fn new(display: &Display<WindowSurface>) -> Self {
+        if !glium::program::ComputeShader::is_supported(display) {
+            println!("Compute shaders are not supported by this backend");
+            return Self { };
+        }
+
+        // each invocation doubles one element of the shader storage buffer
+        let program = glium::program::ComputeShader::from_source(display, r#"\
+                #version 430
+                layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+                layout(std430) buffer MyBlock {
+                    float values[4096];
+                };
+
+                void main() {
+                    values[gl_GlobalInvocationID.x] *= 2.0;
+                }
+
+            "#).unwrap();
+
+        const NUM_VALUES: usize = 4096;
+
+        #[repr(C)]
+        #[derive(Clone, Copy)]
+        struct Data {
+            values: [f32; NUM_VALUES],
+        }
+        implement_uniform_block!(Data, values);
+
+        let mut values = [0.0; NUM_VALUES];
+        for (i, val) in values.iter_mut().enumerate() {
+            *val = i as f32;
+        }
+
+        let buffer = glium::uniforms::UniformBuffer::new(display, Data { values }).unwrap();
+
+        program.execute(uniform! { MyBlock: &buffer }, NUM_VALUES as u32 / 64, 1, 1);
+
+        let data = buffer.read().unwrap();
+        for val in data.values.iter().take(8) {
+            println!("{:?}", val);
+        }
+        println!("...");
+
+        Self { }
+    }
+}
+
+fn main() {
+    State::<Application>::run_once(false);
+}
@@ -0,0 +1,140 @@
+/*!
+
+This example demonstrates how to render without any window, for example on a server that
+doesn't run an X server.
+
+An EGL display is opened on top of the first available EGL device (which can be a GPU or a
+software renderer like llvmpipe), then a context is created and made current without any surface.
+The `Headless` object then provides a framebuffer to draw upon.
+
+The result is saved to `glium-example-headless.png`.
+
+*/
+#[cfg_attr(target_os = "linux", macro_use)]
+extern crate glium;
+
+#[cfg(target_os = "linux")]
+fn main() {
+    use glium::Surface;
+    use glium::index::PrimitiveType;
+    use glutin::api::egl::device::Device;
+    use glutin::api::egl::display::Display;
+    use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+    use glutin::context::{ContextApi, ContextAttributesBuilder, PossiblyCurrentContext};
+    use glutin::prelude::*;
+
+    // Opening an EGL display on the first device, which doesn't require any window system
+    let device = Device::query_devices().expect("failed to query the EGL devices")
+        .next().expect("no EGL device available");
+    let display = unsafe { Display::with_device(&device, None) }
+        .expect("failed to create the EGL display");
+
+    // We don't need any surface, since glium will provide the framebuffer
+    let template = ConfigTemplateBuilder::new()
+        .with_alpha_size(8)
+        .with_surface_type(ConfigSurfaceTypes::empty())
+        .build();
+    let config = unsafe { display.find_configs(template) }.unwrap()
+        .next().expect("no suitable EGL config");
+
+    // Trying desktop OpenGL first, then falling back to OpenGL ES
+    let context_attributes = ContextAttributesBuilder::new().build(None);
+    let fallback_context_attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::Gles(None))
+        .build(None);
+    let not_current_context = unsafe {
+        display.create_context(&config, &context_attributes).unwrap_or_else(|_| {
+            display.create_context(&config, &fallback_context_attributes)
+                .expect("failed to create context")
+        })
+    };
+    let context = not_current_context.make_current_surfaceless().unwrap();
+
+    let headless = glium::backend::glutin::Headless::new(PossiblyCurrentContext::Egl(context),
+                                                          (800, 600)).unwrap();
+    assert_eq!(headless.get_framebuffer_dimensions(), (800, 600));
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: [f32; 3],
+    }
+
+    implement_vertex!(Vertex, position, color);
+
+    let vertex_buffer = glium::VertexBuffer::new(&headless, &[
+        Vertex { position: [-0.5, -0.5], color: [0.0, 1.0, 0.0] },
+        Vertex { position: [ 0.0,  0.5], color: [0.0, 0.0, 1.0] },
+        Vertex { position: [ 0.5, -0.5], color: [1.0, 0.0, 0.0] },
+    ]).unwrap();
+    let index_buffer = glium::IndexBuffer::new(&headless, PrimitiveType::TrianglesList,
+                                               &[0u16, 1, 2]).unwrap();
+
+    let program = program!(&headless,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 position;
+                in vec3 color;
+                out vec3 vColor;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    vColor = color;
+                }
+            ",
+
+            fragment: "
+                #version 140
+                in vec3 vColor;
+                out vec4 f_color;
+
+                void main() {
+                    f_color = vec4(vColor, 1.0);
+                }
+            "
+        },
+
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+                attribute lowp vec3 color;
+                varying lowp vec3 vColor;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    vColor = color;
+                }
+            ",
+
+            fragment: "
+                #version 100
+                varying lowp vec3 vColor;
+
+                void main() {
+                    gl_FragColor = vec4(vColor, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    // Drawing on the framebuffer of the headless context
+    let mut target = headless.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+                &Default::default()).unwrap();
+
+    // Reading the result and saving it
+    let image: glium::texture::RawImage2d<'_, u8> = headless.read();
+    let image = image::ImageBuffer::from_raw(image.width, image.height, image.data.into_owned()).unwrap();
+    let image = image::DynamicImage::ImageRgba8(image).flipv();
+    image.save("glium-example-headless.png").unwrap();
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    println!("This example requires EGL devices, which are only available on Linux.");
+}
@@ -0,0 +1,232 @@
+/*!
+
+Headless rendering with glutin.
+
+A `Headless` wraps a glutin context that isn't associated to any window, for example an EGL
+context created with the surfaceless platform or on top of a GBM device. Since there is no
+default framebuffer to draw upon, the `Headless` owns a color texture and a depth buffer that
+are used as the backing framebuffer.
+
+See the `headless` example for how to create such a context on a server without an X server.
+
+*/
+use glutin::context::PossiblyCurrentContext;
+use glutin::display::GetGlDisplay;
+use glutin::prelude::*;
+
+use crate::backend::{self, Backend, Context};
+use crate::context;
+use crate::debug;
+use crate::framebuffer::{DepthRenderBuffer, RenderBufferCreationError, SimpleFrameBuffer};
+use crate::texture::{DepthFormat, Texture2d, Texture2dDataSink, TextureCreationError};
+use crate::{IncompatibleOpenGl, SwapBuffersError};
+
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+/// A glium context without any window, that draws into a framebuffer owned by glium.
+///
+/// The glutin context must be current when the `Headless` is built, and must stay current
+/// for the whole lifetime of the `Headless`.
+pub struct Headless {
+    // contains everything related to the current glium context and its state
+    context: Rc<context::Context>,
+    // the backing framebuffer
+    color: Texture2d,
+    depth: DepthRenderBuffer,
+    // dimensions of the backing framebuffer, shared with the backend
+    dimensions: Rc<Cell<(u32, u32)>>,
+}
+
+/// Error that can happen while creating a `Headless`.
+#[derive(Debug)]
+pub enum HeadlessCreationError {
+    /// The OpenGL implementation is too old.
+    IncompatibleOpenGl(IncompatibleOpenGl),
+    /// Failed to create the color texture of the backing framebuffer.
+    TextureCreationError(TextureCreationError),
+    /// Failed to create the depth buffer of the backing framebuffer.
+    RenderBufferCreationError(RenderBufferCreationError),
+}
+
+impl Headless {
+    /// Builds a new `Headless` from a glutin context whose backing framebuffer has the given
+    /// dimensions.
+    ///
+    /// Performs a compatibility check to make sure that all core elements of glium are supported
+    /// by the implementation.
+    pub fn new(context: PossiblyCurrentContext, dimensions: (u32, u32))
+               -> Result<Headless, HeadlessCreationError>
+    {
+        Headless::with_debug(context, dimensions, Default::default())
+    }
+
+    /// The same as the `new` constructor, but allows for specifying debug callback behaviour.
+    pub fn with_debug(context: PossiblyCurrentContext, dimensions: (u32, u32),
+                      debug: debug::DebugCallbackBehavior)
+                      -> Result<Headless, HeadlessCreationError>
+    {
+        let shared_dimensions = Rc::new(Cell::new(dimensions));
+        let backend = HeadlessBackend { context, dimensions: shared_dimensions.clone() };
+        let context = unsafe { context::Context::new(backend, false, debug) }?;
+
+        let (color, depth) = build_framebuffer(&context, dimensions)?;
+
+        Ok(Headless {
+            context,
+            color,
+            depth,
+            dimensions: shared_dimensions,
+        })
+    }
+
+    /// Replaces the backing framebuffer with a new one of the given dimensions.
+    ///
+    /// The content of the framebuffer is lost. On error, the previous framebuffer is kept.
+    pub fn resize(&mut self, dimensions: (u32, u32)) -> Result<(), HeadlessCreationError> {
+        let (color, depth) = build_framebuffer(&self.context, dimensions)?;
+        self.color = color;
+        self.depth = depth;
+        self.dimensions.set(dimensions);
+        Ok(())
+    }
+
+    /// Start drawing on the backing framebuffer.
+    #[inline]
+    pub fn draw(&self) -> SimpleFrameBuffer<'_> {
+        SimpleFrameBuffer::with_depth_buffer(&self.context, &self.color, &self.depth)
+            .expect("the backing framebuffer of the headless context is invalid")
+    }
+
+    /// Returns the texture used as the color attachment of the backing framebuffer.
+    #[inline]
+    pub fn get_texture(&self) -> &Texture2d {
+        &self.color
+    }
+
+    /// Reads the content of the backing framebuffer.
+    #[inline]
+    pub fn read<T>(&self) -> T where T: Texture2dDataSink<(u8, u8, u8, u8)> {
+        self.color.read()
+    }
+}
+
+/// Builds the attachments of the backing framebuffer.
+fn build_framebuffer(context: &Rc<Context>, dimensions: (u32, u32))
+                     -> Result<(Texture2d, DepthRenderBuffer), HeadlessCreationError>
+{
+    let color = Texture2d::empty(context, dimensions.0, dimensions.1)?;
+    let depth = DepthRenderBuffer::new(context, DepthFormat::I24, dimensions.0, dimensions.1)?;
+    Ok((color, depth))
+}
+
+impl fmt::Debug for Headless {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[glium::backend::glutin::headless::Headless]")
+    }
+}
+
+impl Deref for Headless {
+    type Target = Context;
+    #[inline]
+    fn deref(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl backend::Facade for Headless {
+    #[inline]
+    fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+}
+
+impl fmt::Display for HeadlessCreationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            HeadlessCreationError::IncompatibleOpenGl(err) => write!(fmt, "{}", err),
+            HeadlessCreationError::TextureCreationError(err) => write!(fmt, "{}", err),
+            HeadlessCreationError::RenderBufferCreationError(err) => write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl Error for HeadlessCreationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            HeadlessCreationError::IncompatibleOpenGl(ref err) => Some(err),
+            HeadlessCreationError::TextureCreationError(ref err) => Some(err),
+            HeadlessCreationError::RenderBufferCreationError(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<IncompatibleOpenGl> for HeadlessCreationError {
+    #[inline]
+    fn from(err: IncompatibleOpenGl) -> HeadlessCreationError {
+        HeadlessCreationError::IncompatibleOpenGl(err)
+    }
+}
+
+impl From<TextureCreationError> for HeadlessCreationError {
+    #[inline]
+    fn from(err: TextureCreationError) -> HeadlessCreationError {
+        HeadlessCreationError::TextureCreationError(err)
+    }
+}
+
+impl From<RenderBufferCreationError> for HeadlessCreationError {
+    #[inline]
+    fn from(err: RenderBufferCreationError) -> HeadlessCreationError {
+        HeadlessCreationError::RenderBufferCreationError(err)
+    }
+}
+
+/// An implementation of the `Backend` trait for a glutin context without any surface.
+struct HeadlessBackend {
+    context: PossiblyCurrentContext,
+    dimensions: Rc<Cell<(u32, u32)>>,
+}
+
+unsafe impl Backend for HeadlessBackend {
+    #[inline]
+    fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+        // there is no default framebuffer, so there is nothing to swap
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        match backend::glutin::symbol_to_cstring(symbol) {
+            Some(symbol) => self.context.display().get_proc_address(&symbol) as *const _,
+            None => ptr::null(),
+        }
+    }
+
+    #[inline]
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        self.dimensions.get()
+    }
+
+    #[inline]
+    fn resize(&self, _: (u32, u32)) {
+        // the backing framebuffer is owned by the `Headless`, see `Headless::resize`
+    }
+
+    #[inline]
+    fn is_current(&self) -> bool {
+        self.context.is_current()
+    }
+
+    #[inline]
+    unsafe fn make_current(&self) {
+        // the context is required to stay current, and the glium context is created with
+        // `check_current_context` set to false so there is nothing to do
+    }
+}
@@ -7,6 +7,17 @@ Backend implementation for the glutin library
 
 Only available if the 'glutin' feature is enabled.
 
+# Window management
+
+A `Display` only owns the OpenGL context and the surface, not the winit window. For this reason
+there is no `Display::set_fullscreen` or `Display::set_window_title`: call
+`Window::set_fullscreen` and `Window::set_title` on the window that you created alongside the
+display instead. The `borderless_fullscreen` and `exclusive_fullscreen` functions build the
+value to pass to `Window::set_fullscreen`, and `SimpleWindowBuilder::with_title` and
+`SimpleWindowBuilder::with_fullscreen` set the initial state of the window.
+
+Don't forget to call `Display::resize` when the window changes size.
+
 */
 pub use glutin;
 use glutin::surface::Surface;
@@ -22,26 +33,35 @@ use crate::glutin::prelude::*;
 use crate::glutin::context::PossiblyCurrentContext;
 use crate::glutin::display::GetGlDisplay;
 use crate::glutin::surface::{SurfaceTypeTrait, ResizeableSurface};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::error::Error;
 use std::ffi::CString;
 use std::fmt;
 use std::num::NonZeroU32;
 use std::ops::Deref;
 use std::os::raw::c_void;
+use std::ptr;
 use std::rc::Rc;
 use crate::{Frame, IncompatibleOpenGl};
 
+pub use self::headless::{Headless, HeadlessCreationError};
+
+pub mod headless;
+
 /// Wraps a glutin context together with the corresponding Surface.
 /// This is necessary so that we can swap buffers and determine the framebuffer size within glium.
 pub struct ContextSurfacePair<T: SurfaceTypeTrait + ResizeableSurface> {
-    context: PossiblyCurrentContext,
+    // shared between the pairs of the displays created with `Display::new_shared`
+    context: Rc<PossiblyCurrentContext>,
     surface: glutin::surface::Surface<T>,
+    // the error returned by glutin during the last failed buffer swap
+    swap_error: Cell<Option<glutin::error::Error>>,
 }
 
 impl<T: SurfaceTypeTrait + ResizeableSurface> ContextSurfacePair<T> {
-    fn new(context: PossiblyCurrentContext, surface: glutin::surface::Surface<T>) -> Self {
-        Self { context, surface }
+    fn new(context: Rc<PossiblyCurrentContext>, surface: glutin::surface::Surface<T>) -> Self {
+        Self { context, surface, swap_error: Cell::new(None) }
     }
 
     #[inline]
@@ -64,6 +84,14 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> ContextSurfacePair<T> {
         let height = NonZeroU32::new(new_size.1).unwrap_or(NonZeroU32::new(1).unwrap());
         self.surface.resize(&self.context, width, height);
     }
+
+    #[inline]
+    /// Change the swap interval of the associated surface
+    pub fn set_swap_interval(&self, interval: glutin::surface::SwapInterval)
+                             -> Result<(), glutin::error::Error>
+    {
+        self.surface.set_swap_interval(&self.context, interval)
+    }
 }
 
 impl<T: SurfaceTypeTrait + ResizeableSurface> Deref for ContextSurfacePair<T> {
@@ -78,18 +106,34 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> Deref for ContextSurfacePair<T> {
 ///
 /// The `Display` uses **glutin** for the **Window** and its associated GL **Context**.
 ///
-/// These are stored alongside a glium-specific context.
+/// These are stored alongside a glium-specific context. The `Display` takes ownership of the
+/// glutin surface passed to its constructor, and the surface lives as long as the `Display` or
+/// one of its clones. There is no need to keep it around: `draw`, `resize`, `swap_buffers`
+/// and `set_swap_interval` operate on it directly.
 #[derive(Clone)]
 pub struct Display<T: SurfaceTypeTrait + ResizeableSurface + 'static> {
     // contains everything related to the current glium context and its state
     context: Rc<context::Context>,
     // The glutin Surface alongside its associated glutin Context.
-    gl_context: Rc<RefCell<Takeable<ContextSurfacePair<T>>>>,
+    gl_context: SharedContextSurfacePair<T>,
+    // the pair whose surface is current, shared with the backend and the displays created
+    // with `new_shared`
+    current: Rc<RefCell<SharedContextSurfacePair<T>>>,
 }
 
+type SharedContextSurfacePair<T> = Rc<RefCell<Takeable<ContextSurfacePair<T>>>>;
+
 /// An implementation of the `Backend` trait for glutin.
 #[derive(Clone)]
-pub struct GlutinBackend<T: SurfaceTypeTrait + ResizeableSurface>(Rc<RefCell<Takeable<ContextSurfacePair<T>>>>);
+pub struct GlutinBackend<T: SurfaceTypeTrait + ResizeableSurface> {
+    // the pair of the display the backend has been created with
+    gl_context: SharedContextSurfacePair<T>,
+    // the pair whose surface is current, see `Display::new_shared`
+    current: Rc<RefCell<SharedContextSurfacePair<T>>>,
+    // addresses of the OpenGL functions that have already been loaded, null pointers are not
+    // stored so that missing functions are queried again
+    proc_addresses: RefCell<HashMap<String, *const c_void>>,
+}
 
 /// Error that can happen while creating a glium display.
 #[derive(Debug)]
@@ -100,6 +144,15 @@ pub enum DisplayCreationError {
     IncompatibleOpenGl(IncompatibleOpenGl),
 }
 
+/// Error that can happen while swapping the buffers of a glium display.
+#[derive(Debug)]
+pub enum DisplaySwapBuffersError {
+    /// Glutin has failed to swap the buffers of the surface.
+    GlutinError(glutin::error::Error),
+    /// Glium has refused to swap the buffers, for example because the context is lost.
+    SwapBuffersError(SwapBuffersError),
+}
+
 impl<T: SurfaceTypeTrait + ResizeableSurface> std::fmt::Debug for Display<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[glium::backend::glutin::Display]")
@@ -146,7 +199,7 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> Display<T> {
         surface: Surface<T>,
         debug: debug::DebugCallbackBehavior,
     ) -> Result<Self, IncompatibleOpenGl> {
-        Self::new_inner(context, surface, debug, true)
+        Self::new_inner(context, surface, debug, true, false)
     }
 
     /// The same as the `unchecked` constructor, but allows for specifying debug callback behaviour.
@@ -155,7 +208,19 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> Display<T> {
         surface: Surface<T>,
         debug: debug::DebugCallbackBehavior,
     ) -> Result<Self, IncompatibleOpenGl> {
-        Self::new_inner(context, surface, debug, false)
+        Self::new_inner(context, surface, debug, false, false)
+    }
+
+    /// The same as the `with_debug` constructor, except that the capabilities of the context
+    /// are only retrieved the first time they are needed.
+    ///
+    /// See `Context::new_lazy` for more details.
+    pub fn with_lazy_capabilities(
+        context: PossiblyCurrentContext,
+        surface: Surface<T>,
+        debug: debug::DebugCallbackBehavior,
+    ) -> Result<Self, IncompatibleOpenGl> {
+        Self::new_inner(context, surface, debug, true, true)
     }
 
     fn new_inner(
@@ -163,33 +228,215 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> Display<T> {
         surface: Surface<T>,
         debug: debug::DebugCallbackBehavior,
         checked: bool,
+        lazy_capabilities: bool,
     ) -> Result<Self, IncompatibleOpenGl> {
-        let context_surface_pair = ContextSurfacePair::new(context, surface);
+        let context_surface_pair = ContextSurfacePair::new(Rc::new(context), surface);
         let gl_window = Rc::new(RefCell::new(Takeable::new(context_surface_pair)));
-        let glutin_backend = GlutinBackend(gl_window.clone());
-        let context = unsafe { context::Context::new(glutin_backend, checked, debug) }?;
+        let current = Rc::new(RefCell::new(gl_window.clone()));
+        let glutin_backend = GlutinBackend {
+            gl_context: gl_window.clone(),
+            current: current.clone(),
+            proc_addresses: RefCell::new(HashMap::new()),
+        };
+        let context = unsafe {
+            if lazy_capabilities {
+                context::Context::new_lazy(glutin_backend, checked, debug)
+            } else {
+                context::Context::new(glutin_backend, checked, debug)
+            }
+        }?;
         Ok(Display {
             gl_context: gl_window,
             context,
+            current,
         })
     }
 
+    /// Creates a new `Display` that draws to `surface` with the glium context of an existing
+    /// display.
+    ///
+    /// Both displays use the same OpenGL context, so textures, buffers, programs and all other
+    /// objects created with one of them can be used with the other. No compatibility check is
+    /// performed, since it has already been done when `existing` was created.
+    ///
+    /// The surface must have been created with a config that is compatible with the context
+    /// of `existing`. The context is made current with the surface of a display when calling
+    /// `draw`, `draw_with_size` or `swap_buffers` on it, so a `Frame` must be finished before
+    /// drawing to another display.
+    pub fn new_shared(surface: Surface<T>, existing: &Display<T>) -> Self {
+        let context = existing.gl_context.borrow().context.clone();
+        let context_surface_pair = ContextSurfacePair::new(context, surface);
+
+        Display {
+            gl_context: Rc::new(RefCell::new(Takeable::new(context_surface_pair))),
+            context: existing.context.clone(),
+            current: existing.current.clone(),
+        }
+    }
+
+    /// Makes the surface of this display the current one if another display created with
+    /// `new_shared` has been used in the meantime.
+    fn make_surface_current(&self) -> Result<(), glutin::error::Error> {
+        if Rc::ptr_eq(&self.current.borrow(), &self.gl_context) {
+            return Ok(());
+        }
+
+        {
+            let pair = self.gl_context.borrow();
+            pair.context.make_current(&pair.surface)?;
+        }
+
+        *self.current.borrow_mut() = self.gl_context.clone();
+        Ok(())
+    }
+
     /// Resize the underlying surface.
     #[inline]
     pub fn resize(&self, new_size:(u32, u32)) {
         self.gl_context.borrow().resize(new_size)
     }
 
+    /// Swaps the buffers of the surface.
+    ///
+    /// `Frame::finish` already does this, so you only need to call this function if you draw
+    /// to the default framebuffer without a `Frame`. Contrary to `Context::swap_buffers`, the
+    /// error returned by glutin is kept if the swap fails.
+    pub fn swap_buffers(&self) -> Result<(), DisplaySwapBuffersError> {
+        self.make_surface_current().map_err(DisplaySwapBuffersError::GlutinError)?;
+        self.gl_context.borrow().swap_error.take();
+
+        match self.context.swap_buffers() {
+            Ok(()) => Ok(()),
+            Err(err) => match self.gl_context.borrow().swap_error.take() {
+                Some(err) => Err(DisplaySwapBuffersError::GlutinError(err)),
+                None => Err(DisplaySwapBuffersError::SwapBuffersError(err)),
+            },
+        }
+    }
+
+    /// Changes the swap interval of the surface.
+    ///
+    /// Passing `SwapInterval::DontWait` disables vsync, while `SwapInterval::Wait(n)` waits for
+    /// `n` vertical refreshes before each buffer swap. This can be called at any time, for
+    /// example to let the user toggle vsync from a settings menu.
+    #[inline]
+    pub fn set_swap_interval(&self, interval: glutin::surface::SwapInterval)
+                             -> Result<(), glutin::error::Error>
+    {
+        self.gl_context.borrow().set_swap_interval(interval)
+    }
+
     /// Start drawing on the backbuffer.
     ///
     /// This function returns a `Frame`, which can be used to draw on it. When the `Frame` is
     /// destroyed, the buffers are swapped.
     ///
     /// Note that destroying a `Frame` is immediate, even if vsync is enabled.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the display has been created with `new_shared` and its surface can't be made
+    /// current. Use `try_draw` to handle this error.
     #[inline]
     pub fn draw(&self) -> Frame {
+        self.try_draw().unwrap()
+    }
+
+    /// The same as `draw`, except that an error is returned if the surface of the display
+    /// can't be made current.
+    pub fn try_draw(&self) -> Result<Frame, glutin::error::Error> {
+        self.make_surface_current()?;
         let dimensions = self.get_framebuffer_dimensions();
-        Frame::new(self.context.clone(), dimensions)
+        Ok(Frame::new(self.context.clone(), dimensions))
+    }
+
+    /// Resizes the surface if needed, then starts drawing on the backbuffer.
+    ///
+    /// The `Display` doesn't own the window and can't know that it has been resized. Passing
+    /// the current size of the window, for example `window.inner_size().into()`, makes sure
+    /// that the returned `Frame` always matches it without having to call `resize` when
+    /// handling window events.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the surface can't be made current, see `draw`.
+    #[inline]
+    pub fn draw_with_size(&self, new_size: (u32, u32)) -> Frame {
+        self.try_draw_with_size(new_size).unwrap()
+    }
+
+    /// The same as `draw_with_size`, except that an error is returned if the surface of the
+    /// display can't be made current.
+    pub fn try_draw_with_size(&self, new_size: (u32, u32))
+                              -> Result<Frame, glutin::error::Error>
+    {
+        // `resize` replaces zero dimensions with one
+        let new_size = (new_size.0.max(1), new_size.1.max(1));
+
+        self.make_surface_current()?;
+        if self.get_framebuffer_dimensions() != new_size {
+            self.resize(new_size);
+        }
+
+        Ok(Frame::new(self.context.clone(), new_size))
+    }
+}
+
+#[cfg(feature = "simple_window_builder")]
+impl Display<glutin::surface::WindowSurface> {
+    /// Creates a new glium `Display` for an existing window, from its raw handles.
+    ///
+    /// This is useful to embed glium in a window that was created by another framework. The
+    /// OpenGL config is chosen from the `config_template`, then a context and a surface are
+    /// created for the window. If creating a desktop OpenGL context fails, an OpenGL ES
+    /// context is created instead.
+    ///
+    /// # Safety
+    ///
+    /// The handles must be valid and must stay valid for the whole lifetime of the `Display`.
+    pub unsafe fn from_raw_window_handle(
+        display_handle: raw_window_handle::RawDisplayHandle,
+        window_handle: raw_window_handle::RawWindowHandle,
+        dimensions: (u32, u32),
+        config_template: glutin::config::ConfigTemplateBuilder,
+    ) -> Result<Self, DisplayCreationError> {
+        use glutin::context::{ContextApi, ContextAttributesBuilder};
+        use glutin::display::DisplayApiPreference;
+        use glutin::surface::{SurfaceAttributesBuilder, WindowSurface};
+
+        #[cfg(target_os = "windows")]
+        let preference = DisplayApiPreference::Wgl(Some(window_handle));
+        #[cfg(target_os = "macos")]
+        let preference = DisplayApiPreference::Cgl;
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let preference = DisplayApiPreference::Egl;
+
+        let gl_display = glutin::display::Display::new(display_handle, preference)?;
+
+        // Just use the first configuration, like the `SimpleWindowBuilder` does
+        let template = config_template.compatible_with_native_window(window_handle).build();
+        let gl_config = gl_display.find_configs(template)?
+            .next()
+            .ok_or_else(|| glutin::error::Error::from(glutin::error::ErrorKind::NotFound))?;
+
+        // Make sure that no dimension is zero, which can happen for a minimized window.
+        let width = NonZeroU32::new(dimensions.0).unwrap_or(NonZeroU32::new(1).unwrap());
+        let height = NonZeroU32::new(dimensions.1).unwrap_or(NonZeroU32::new(1).unwrap());
+        let attrs = SurfaceAttributesBuilder::<WindowSurface>::new()
+            .build(window_handle, width, height);
+        let surface = gl_display.create_window_surface(&gl_config, &attrs)?;
+
+        let context_attributes = ContextAttributesBuilder::new().build(Some(window_handle));
+        let fallback_context_attributes = ContextAttributesBuilder::new()
+            .with_context_api(ContextApi::Gles(None))
+            .build(Some(window_handle));
+        let context = match gl_display.create_context(&gl_config, &context_attributes) {
+            Ok(context) => context,
+            Err(_) => gl_display.create_context(&gl_config, &fallback_context_attributes)?,
+        };
+        let context = context.make_current(&surface)?;
+
+        Display::new(context, surface)
     }
 }
 
@@ -212,6 +459,25 @@ impl Error for DisplayCreationError {
     }
 }
 
+impl fmt::Display for DisplaySwapBuffersError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            DisplaySwapBuffersError::GlutinError(err) => write!(fmt, "{}", err),
+            DisplaySwapBuffersError::SwapBuffersError(err) => write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl Error for DisplaySwapBuffersError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            DisplaySwapBuffersError::GlutinError(ref err) => Some(err),
+            DisplaySwapBuffersError::SwapBuffersError(ref err) => Some(err),
+        }
+    }
+}
+
 impl From<glutin::error::Error> for DisplayCreationError {
     #[inline]
     fn from(err: glutin::error::Error) -> DisplayCreationError {
@@ -245,51 +511,88 @@ impl<T: SurfaceTypeTrait + ResizeableSurface> Deref for GlutinBackend<T> {
     type Target = Rc<RefCell<Takeable<ContextSurfacePair<T>>>>;
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.gl_context
+    }
+}
+
+impl<T: SurfaceTypeTrait + ResizeableSurface> GlutinBackend<T> {
+    /// Returns the pair whose surface is current.
+    #[inline]
+    fn current(&self) -> SharedContextSurfacePair<T> {
+        self.current.borrow().clone()
     }
 }
 
 unsafe impl<T: SurfaceTypeTrait + ResizeableSurface> Backend for GlutinBackend<T> {
     #[inline]
     fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
-        match self.borrow().swap_buffers() {
+        let current = self.current();
+        let pair = current.borrow();
+        match pair.swap_buffers() {
             Ok(()) => Ok(()),
-            _ => Err(SwapBuffersError::ContextLost),
+            Err(err) => {
+                pair.swap_error.set(Some(err));
+                Err(SwapBuffersError::ContextLost)
+            },
         }
     }
 
     #[inline]
     unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
-        let symbol = CString::new(symbol).unwrap();
-        self.borrow().display().get_proc_address(&symbol) as *const _
+        if let Some(&address) = self.proc_addresses.borrow().get(symbol) {
+            return address;
+        }
+
+        let c_symbol = match symbol_to_cstring(symbol) {
+            Some(symbol) => symbol,
+            None => return ptr::null(),
+        };
+
+        let address = self.current().borrow().display().get_proc_address(&c_symbol)
+                          as *const c_void;
+        if !address.is_null() {
+            self.proc_addresses.borrow_mut().insert(symbol.to_owned(), address);
+        }
+        address
     }
 
     #[inline]
     fn get_framebuffer_dimensions(&self) -> (u32, u32) {
-        self.0.borrow().get_framebuffer_dimensions()
+        self.current().borrow().get_framebuffer_dimensions()
     }
 
     #[inline]
     fn resize(&self, new_size:(u32, u32)) {
-        self.borrow().resize(new_size)
+        self.current().borrow().resize(new_size)
     }
 
     #[inline]
     fn is_current(&self) -> bool {
-        self.borrow().is_current()
+        self.current().borrow().is_current()
     }
 
     #[inline]
     unsafe fn make_current(&self) {
-        let pair = self.borrow();
+        let current = self.current();
+        let pair = current.borrow();
         pair.context.make_current(&pair.surface).unwrap();
     }
 }
 
+/// Converts the name of an OpenGL function to a `CString`.
+///
+/// Returns `None` if the name contains a NUL byte, which can happen with malformed extension
+/// strings. Such a function is then treated as not present.
+#[inline]
+fn symbol_to_cstring(symbol: &str) -> Option<CString> {
+    CString::new(symbol).ok()
+}
+
 #[cfg(feature = "simple_window_builder")]
 /// Builder to simplify glium/glutin context creation.
 pub struct SimpleWindowBuilder {
-    builder: winit::window::WindowBuilder
+    builder: winit::window::WindowBuilder,
+    stereoscopy: bool,
 }
 
 #[cfg(feature = "simple_window_builder")]
@@ -299,7 +602,8 @@ impl SimpleWindowBuilder {
         Self {
             builder: winit::window::WindowBuilder::new()
                 .with_title("Simple Glium Window")
-                .with_inner_size(winit::dpi::PhysicalSize::new(800, 480))
+                .with_inner_size(winit::dpi::PhysicalSize::new(800, 480)),
+            stereoscopy: false,
         }
     }
 
@@ -316,6 +620,25 @@ impl SimpleWindowBuilder {
         self
     }
 
+    /// Requests the window to be created in fullscreen mode.
+    ///
+    /// Use `Fullscreen::Borderless(None)` for a borderless window covering the current monitor.
+    /// To switch to fullscreen after the window has been created, use
+    /// `Window::set_fullscreen` with `borderless_fullscreen` or `exclusive_fullscreen`.
+    pub fn with_fullscreen(mut self, fullscreen: Option<winit::window::Fullscreen>) -> Self {
+        self.builder = self.builder.with_fullscreen(fullscreen);
+        self
+    }
+
+    /// Requests a context with a left and a right back buffer, for quad-buffered stereoscopic
+    /// rendering. Use `Frame::draw_eye` to draw the image of each eye.
+    ///
+    /// Building the window panics if no stereoscopic configuration is available.
+    pub fn with_stereoscopy(mut self, stereoscopy: bool) -> Self {
+        self.stereoscopy = stereoscopy;
+        self
+    }
+
     /// Replace the used [`WindowBuilder`](winit::window::WindowBuilder),
     /// do this before you set other parameters or you'll overwrite the parameters.
     pub fn set_window_builder(mut self, window_builder: winit::window::WindowBuilder) -> Self {
@@ -330,13 +653,19 @@ impl SimpleWindowBuilder {
 
     /// Create a new [`Window`](winit::window::Window) and [`Display`]
     /// with the specified parameters.
+    ///
+    /// The first available OpenGL config is used. If creating a desktop OpenGL context fails,
+    /// an OpenGL ES context is created instead.
     pub fn build(self, event_loop: &winit::event_loop::EventLoop<()>) -> (winit::window::Window, Display<glutin::surface::WindowSurface>) {
         use glutin::prelude::*;
         use raw_window_handle::HasRawWindowHandle;
 
         // First we start by opening a new Window
         let display_builder = glutin_winit::DisplayBuilder::new().with_window_builder(Some(self.builder));
-        let config_template_builder = glutin::config::ConfigTemplateBuilder::new();
+        let mut config_template_builder = glutin::config::ConfigTemplateBuilder::new();
+        if self.stereoscopy {
+            config_template_builder = config_template_builder.with_stereoscopy(Some(true));
+        }
         let (window, gl_config) = display_builder
             .build(&event_loop, config_template_builder, |mut configs| {
                 // Just use the first configuration since we don't have any special preferences here
@@ -356,11 +685,63 @@ impl SimpleWindowBuilder {
         // Finally we can create a Surface, use it to make a PossiblyCurrentContext and create the glium Display
         let surface = unsafe { gl_config.display().create_window_surface(&gl_config, &attrs).unwrap() };
         let context_attributes = glutin::context::ContextAttributesBuilder::new().build(Some(window.raw_window_handle()));
-        let current_context = Some(unsafe {
-            gl_config.display().create_context(&gl_config, &context_attributes).expect("failed to create context")
-        }).unwrap().make_current(&surface).unwrap();
+        // Some platforms only support OpenGL ES, so we try it if desktop OpenGL isn't available
+        let fallback_context_attributes = glutin::context::ContextAttributesBuilder::new()
+            .with_context_api(glutin::context::ContextApi::Gles(None))
+            .build(Some(window.raw_window_handle()));
+        let current_context = unsafe {
+            gl_config.display().create_context(&gl_config, &context_attributes)
+                .or_else(|_| gl_config.display().create_context(&gl_config, &fallback_context_attributes))
+                .expect("failed to create context")
+        }.make_current(&surface).unwrap();
         let display = Display::from_context_surface(current_context, surface).unwrap();
 
         (window, display)
     }
 }
+
+/// Returns a borderless fullscreen mode covering the monitor that currently contains the
+/// window.
+///
+/// Pass the result to `Window::set_fullscreen`. The video mode of the monitor isn't changed.
+#[cfg(feature = "simple_window_builder")]
+pub fn borderless_fullscreen(window: &winit::window::Window) -> winit::window::Fullscreen {
+    winit::window::Fullscreen::Borderless(window.current_monitor())
+}
+
+/// Returns an exclusive fullscreen mode on the monitor that currently contains the window.
+///
+/// The video mode that matches the current resolution and refresh rate of the monitor is
+/// preferred, so that the monitor doesn't have to switch modes. If there is none, the video
+/// mode with the highest resolution and refresh rate is used.
+///
+/// Pass the result to `Window::set_fullscreen`. Returns `None` if the monitor can't be
+/// determined or doesn't report any video mode. The `Display` doesn't need to be recreated,
+/// but you should call `Display::resize` when the window is resized.
+#[cfg(feature = "simple_window_builder")]
+pub fn exclusive_fullscreen(window: &winit::window::Window) -> Option<winit::window::Fullscreen> {
+    let monitor = window.current_monitor()?;
+    let current_size = monitor.size();
+    let current_refresh_rate = monitor.refresh_rate_millihertz();
+
+    monitor.video_modes()
+        .max_by_key(|mode| {
+            let size = mode.size();
+            (size == current_size,
+             Some(mode.refresh_rate_millihertz()) == current_refresh_rate,
+             size.width as u64 * size.height as u64, mode.refresh_rate_millihertz(),
+             mode.bit_depth())
+        })
+        .map(winit::window::Fullscreen::Exclusive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::symbol_to_cstring;
+
+    #[test]
+    fn symbol_with_nul() {
+        assert!(symbol_to_cstring("glGetString").is_some());
+        assert!(symbol_to_cstring("glGet\0String").is_none());
+    }
+}
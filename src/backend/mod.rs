@@ -28,6 +28,7 @@ pub use crate::context::ReleaseBehavior;
 
 #[cfg(feature = "glutin")]
 pub mod glutin;
+pub mod raw;
 
 /// Trait for types that can be used as a backend for a glium context.
 ///
@@ -0,0 +1,124 @@
+/*!
+
+Backend implementation driven by closures.
+
+This backend can be used with any library that is able to create an OpenGL context and to load
+its functions, like SDL, GLFW or a custom EGL setup, without having to implement the `Backend`
+trait manually.
+
+```no_run
+# use std::os::raw::c_void;
+# fn get_proc_address(_: &str) -> *const c_void { unimplemented!() }
+# fn window_size() -> (u32, u32) { unimplemented!() }
+use glium::backend::raw::RawBackend;
+
+let backend = unsafe {
+    RawBackend::new(|symbol| get_proc_address(symbol), || window_size(), |_| {}, || true)
+};
+
+let context = unsafe {
+    glium::backend::Context::new(backend, true, Default::default())
+}.unwrap();
+# let _ = context;
+```
+
+*/
+use std::cell::RefCell;
+use std::os::raw::c_void;
+
+use crate::SwapBuffersError;
+use crate::backend::Backend;
+
+/// A `Backend` whose methods are forwarded to closures.
+pub struct RawBackend {
+    loader: RefCell<Box<dyn FnMut(&str) -> *const c_void>>,
+    get_dimensions: Box<dyn Fn() -> (u32, u32)>,
+    resize: Box<dyn Fn((u32, u32))>,
+    is_current: Box<dyn Fn() -> bool>,
+    make_current: Box<dyn Fn()>,
+    swap_buffers: Box<dyn Fn() -> Result<(), SwapBuffersError>>,
+}
+
+impl RawBackend {
+    /// Builds a new backend.
+    ///
+    /// - `loader` must return the address of the OpenGL function whose name is passed as
+    ///   parameter, or a null pointer if the function is not available.
+    /// - `get_dimensions` must return the dimensions of the default framebuffer.
+    /// - `resize` is called by `Backend::resize` with the new dimensions of the framebuffer.
+    /// - `is_current` must return true if the OpenGL context is current in the thread.
+    ///
+    /// By default, `make_current` and `swap_buffers` don't do anything. Use `with_make_current`
+    /// and `with_swap_buffers` to change this.
+    ///
+    /// # Safety
+    ///
+    /// The closures must describe a valid OpenGL context. In particular, the pointers returned
+    /// by `loader` must point to functions with the right signature.
+    pub unsafe fn new<L, D, R, C>(loader: L, get_dimensions: D, resize: R, is_current: C)
+                                  -> RawBackend
+        where L: FnMut(&str) -> *const c_void + 'static,
+              D: Fn() -> (u32, u32) + 'static,
+              R: Fn((u32, u32)) + 'static,
+              C: Fn() -> bool + 'static
+    {
+        RawBackend {
+            loader: RefCell::new(Box::new(loader)),
+            get_dimensions: Box::new(get_dimensions),
+            resize: Box::new(resize),
+            is_current: Box::new(is_current),
+            make_current: Box::new(|| {}),
+            swap_buffers: Box::new(|| Ok(())),
+        }
+    }
+
+    /// Sets the closure that makes the OpenGL context current in the thread.
+    #[inline]
+    pub fn with_make_current<F>(mut self, make_current: F) -> RawBackend
+        where F: Fn() + 'static
+    {
+        self.make_current = Box::new(make_current);
+        self
+    }
+
+    /// Sets the closure that swaps the buffers at the end of a frame.
+    #[inline]
+    pub fn with_swap_buffers<F>(mut self, swap_buffers: F) -> RawBackend
+        where F: Fn() -> Result<(), SwapBuffersError> + 'static
+    {
+        self.swap_buffers = Box::new(swap_buffers);
+        self
+    }
+}
+
+unsafe impl Backend for RawBackend {
+    #[inline]
+    fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+        (self.swap_buffers)()
+    }
+
+    #[inline]
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        (self.loader.borrow_mut())(symbol)
+    }
+
+    #[inline]
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        (self.get_dimensions)()
+    }
+
+    #[inline]
+    fn resize(&self, new_size: (u32, u32)) {
+        (self.resize)(new_size)
+    }
+
+    #[inline]
+    fn is_current(&self) -> bool {
+        (self.is_current)()
+    }
+
+    #[inline]
+    unsafe fn make_current(&self) {
+        (self.make_current)()
+    }
+}
@@ -168,6 +168,12 @@ impl Alloc {
         self.persistent_mapping.is_some()
     }
 
+    /// Returns true if the buffer was created with `glBufferStorage`.
+    #[inline]
+    pub fn uses_immutable_storage(&self) -> bool {
+        self.created_with_buffer_storage
+    }
+
     /// Changes the type of the buffer. Returns `Err` if this is forbidden.
     pub fn set_type(mut self, ty: BufferType) -> Result<Alloc, Alloc> {
         // FIXME: return Err for GLES2
@@ -786,13 +792,31 @@ impl Alloc {
                                   where D: Content
     {
         let size_to_read = range.end - range.start;
+        <D as Content>::read(size_to_read, |output| self.read_into(range, output))
+    }
+
+    /// Reads the content of the buffer into `output`, which must have the same size as the
+    /// range.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    ///
+    /// # Unsafety
+    ///
+    /// If the buffer uses persistent mapping, the caller of this function must handle
+    /// synchronization.
+    ///
+    pub unsafe fn read_into<D: ?Sized>(&self, range: Range<usize>, output: &mut D)
+                                       -> Result<(), ReadError>
+                                       where D: Content
+    {
+        let size_to_read = range.end - range.start;
 
         if self.persistent_mapping.is_some() {
             let mapping = ReadMapping { mapping: self.map_shared(range, true, false) };
-            <D as Content>::read(size_to_read, |output| {
-                ptr::copy_nonoverlapping(<D as Content>::to_void_ptr(&mapping) as *const u8, output as *mut D as *mut u8, size_to_read);
-                Ok(())
-            })
+            ptr::copy_nonoverlapping(<D as Content>::to_void_ptr(&mapping) as *const u8, output as *mut D as *mut u8, size_to_read);
+            Ok(())
 
         } else {
             let mut ctxt = self.context.make_current();
@@ -804,33 +828,31 @@ impl Alloc {
             self.assert_unmapped(&mut ctxt);
             self.barrier_for_buffer_update(&mut ctxt);
 
-            <D as Content>::read(size_to_read, |output| {
-                if ctxt.version >= &Version(Api::Gl, 4, 5) {
-                    ctxt.gl.GetNamedBufferSubData(self.id, range.start as gl::types::GLintptr,
-                                                  size_to_read as gl::types::GLsizeiptr,
-                                                  output as *mut _ as *mut _);
+            if ctxt.version >= &Version(Api::Gl, 4, 5) {
+                ctxt.gl.GetNamedBufferSubData(self.id, range.start as gl::types::GLintptr,
+                                              size_to_read as gl::types::GLsizeiptr,
+                                              output as *mut _ as *mut _);
 
-                } else if ctxt.version >= &Version(Api::Gl, 1, 5) {
-                    let bind = bind_buffer(&mut ctxt, self.id, self.ty);
-                    ctxt.gl.GetBufferSubData(bind, range.start as gl::types::GLintptr,
-                                             size_to_read as gl::types::GLsizeiptr,
-                                             output as *mut _ as *mut _);
+            } else if ctxt.version >= &Version(Api::Gl, 1, 5) {
+                let bind = bind_buffer(&mut ctxt, self.id, self.ty);
+                ctxt.gl.GetBufferSubData(bind, range.start as gl::types::GLintptr,
+                                         size_to_read as gl::types::GLsizeiptr,
+                                         output as *mut _ as *mut _);
 
-                } else if ctxt.extensions.gl_arb_vertex_buffer_object {
-                    let bind = bind_buffer(&mut ctxt, self.id, self.ty);
-                    ctxt.gl.GetBufferSubDataARB(bind, range.start as gl::types::GLintptr,
-                                                size_to_read as gl::types::GLsizeiptr,
-                                                output as *mut _ as *mut _);
+            } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+                let bind = bind_buffer(&mut ctxt, self.id, self.ty);
+                ctxt.gl.GetBufferSubDataARB(bind, range.start as gl::types::GLintptr,
+                                            size_to_read as gl::types::GLsizeiptr,
+                                            output as *mut _ as *mut _);
 
-                } else if ctxt.version >= &Version(Api::GlEs, 1, 0) {
-                    return Err(ReadError::NotSupported);
+            } else if ctxt.version >= &Version(Api::GlEs, 1, 0) {
+                return Err(ReadError::NotSupported);
 
-                } else {
-                    unreachable!()
-                }
+            } else {
+                unreachable!()
+            }
 
-                Ok(())
-            })
+            Ok(())
         }
     }
 
@@ -1304,11 +1326,12 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext<'_>, size: usiz
         None
     };
 
+    ctxt.log_errors("buffer creation");
     Ok((id, immutable, created_with_buffer_storage, persistent_mapping))
 }
 
 /// Returns true if a given buffer type is supported on a platform.
-fn is_buffer_type_supported(ctxt: &mut CommandContext<'_>, ty: BufferType) -> bool {
+pub fn is_buffer_type_supported(ctxt: &mut CommandContext<'_>, ty: BufferType) -> bool {
     match ty {
         // glium fails to initialize if they are not supported
         BufferType::ArrayBuffer | BufferType::ElementArrayBuffer => true,
@@ -1385,8 +1408,8 @@ fn is_buffer_type_supported(ctxt: &mut CommandContext<'_>, ty: BufferType) -> bo
 /// ## Unsafety
 ///
 /// Assumes that the type of buffer is supported by the backend.
-unsafe fn bind_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint, ty: BufferType)
-                      -> gl::types::GLenum
+pub(crate) unsafe fn bind_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint,
+                                 ty: BufferType) -> gl::types::GLenum
 {
     macro_rules! check {
         ($ctxt:expr, $input_id:expr, $input_ty:expr, $check:ident, $state_var:ident) => (
@@ -1493,10 +1516,8 @@ unsafe fn indexed_bind_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLui
                     panic!("Indexed buffer out of range");
                 }
 
-                if ctxt.state.$state_var.len() <= $input_index as usize {
-                    for _ in 0 .. 1 + ctxt.state.$state_var.len() - $input_index as usize {
-                        ctxt.state.$state_var.push(Default::default());
-                    }
+                while ctxt.state.$state_var.len() <= $input_index as usize {
+                    ctxt.state.$state_var.push(Default::default());
                 }
 
                 let unit = &mut ctxt.state.$state_var[$input_index as usize];
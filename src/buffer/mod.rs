@@ -48,6 +48,8 @@ pub use self::view::{Buffer, BufferAny, BufferMutSlice};
 pub use self::view::{BufferSlice, BufferAnySlice};
 pub use self::alloc::{Mapping, WriteMapping, ReadMapping, ReadError, CopyError};
 pub use self::alloc::{is_buffer_read_supported};
+pub(crate) use self::alloc::is_buffer_type_supported;
+pub(crate) use self::alloc::bind_buffer;
 pub use self::fences::Inserter;
 
 /// DEPRECATED. Only here for backwards compatibility.
@@ -182,7 +184,7 @@ unsafe impl<T> Content for [T] where T: Copy {
 }
 
 /// Error that can happen when creating a buffer.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BufferCreationError {
     /// Not enough memory to create the buffer.
     OutOfMemory,
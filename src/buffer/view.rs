@@ -1,5 +1,6 @@
 use std::fmt;
 use std::mem;
+use std::slice;
 use std::borrow::Cow;
 use crate::utils::range::RangeArgument;
 use std::marker::PhantomData;
@@ -100,6 +101,16 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         self.alloc.as_ref().unwrap().uses_persistent_mapping()
     }
 
+    /// Returns true if the storage of this buffer was allocated with `glBufferStorage`.
+    ///
+    /// The size of such a buffer can never change, which lets the driver skip some checks.
+    /// With `BufferMode::Default` and `BufferMode::Dynamic`, the storage is created with the
+    /// `GL_DYNAMIC_STORAGE_BIT` flag and can still be modified with `write`.
+    #[inline]
+    pub fn is_immutable_storage(&self) -> bool {
+        self.alloc.as_ref().unwrap().uses_immutable_storage()
+    }
+
     /// Uploads some data in this buffer.
     ///
     /// # Implementation
@@ -170,13 +181,26 @@ impl<T: ?Sized> Buffer<T> where T: Content {
 
     /// Maps the buffer in memory for reading.
     ///
+    /// This avoids the intermediate copy of `read`, which makes it the preferred way to read
+    /// back large buffers written by the GPU. The buffer is unmapped when the `ReadMapping` is
+    /// destroyed. Using the buffer in any other way requires the mapping to be destroyed first,
+    /// which is why this function takes a mutable borrow.
+    ///
+    /// # Synchronization
+    ///
+    /// Commands submitted by glium that write to the buffer, including transform feedback and
+    /// shader storage writes, are always finished before the data can be accessed: this function
+    /// issues the necessary memory barriers and waits on the fences of persistent-mapped buffers,
+    /// while OpenGL makes regular mappings wait for the GPU. If the buffer is written outside of
+    /// glium, for example by another context, you must wait on a fence yourself before mapping.
+    ///
     /// # Implementation
     ///
     /// - For persistent-mapped buffers, waits until the data is no longer accessed by the GPU then
     ///   returns a pointer to the existing mapping.
     /// - For immutable buffers, creates a temporary buffer containing the data of the buffer and
     ///   maps it.
-    /// - For other types, calls `glMapBuffer` or `glMapSubBuffer`.
+    /// - For other types, calls `glMapBufferRange` with `GL_MAP_READ_BIT`.
     ///
     pub fn map_read(&mut self) -> ReadMapping<'_, T> {
         self.fence.as_ref().unwrap().wait(&mut self.alloc.as_ref().unwrap().get_context().make_current(),
@@ -371,6 +395,14 @@ impl<T> Buffer<[T]> where [T]: Content, T: Copy {
     pub fn slice_mut<R: RangeArgument<usize>>(&mut self, range: R) -> Option<BufferMutSlice<'_, [T]>> {
         self.as_mut_slice().slice(range)
     }
+
+    /// Reads the content of the buffer into `output`, replacing its previous content.
+    ///
+    /// Contrary to `read`, this reuses the allocation of `output` if it is large enough.
+    #[inline]
+    pub fn read_into(&self, output: &mut Vec<T>) -> Result<(), ReadError> {
+        self.as_slice().read_into(output)
+    }
 }
 
 impl<T> Buffer<[T]> where T: PixelValue {
@@ -665,6 +697,26 @@ impl<'a, T> BufferSlice<'a, [T]> where [T]: Content + 'a {
             marker: PhantomData,
         })
     }
+
+    /// Reads the content of the slice into `output`, replacing its previous content.
+    ///
+    /// Contrary to `read`, this reuses the allocation of `output` if it is large enough.
+    pub fn read_into(&self, output: &mut Vec<T>) -> Result<(), ReadError> {
+        self.fence.wait(&mut self.alloc.get_context().make_current(),
+                        self.bytes_start .. self.bytes_end);
+
+        let len = self.len();
+        output.clear();
+        output.reserve(len);
+
+        unsafe {
+            let dest = slice::from_raw_parts_mut(output.as_mut_ptr(), len);
+            self.alloc.read_into::<[T]>(self.bytes_start .. self.bytes_end, dest)?;
+            output.set_len(len);
+        }
+
+        Ok(())
+    }
 }
 
 impl<'a, T> BufferSlice<'a, [T]> where T: PixelValue + 'a {
@@ -839,13 +891,26 @@ impl<'a, T: ?Sized> BufferMutSlice<'a, T> where T: Content + 'a {
 
     /// Maps the buffer in memory for reading.
     ///
+    /// This avoids the intermediate copy of `read`, which makes it the preferred way to read
+    /// back large buffers written by the GPU. The buffer is unmapped when the `ReadMapping` is
+    /// destroyed. Using the buffer in any other way requires the mapping to be destroyed first,
+    /// which is why this function takes a mutable borrow.
+    ///
+    /// # Synchronization
+    ///
+    /// Commands submitted by glium that write to the buffer, including transform feedback and
+    /// shader storage writes, are always finished before the data can be accessed: this function
+    /// issues the necessary memory barriers and waits on the fences of persistent-mapped buffers,
+    /// while OpenGL makes regular mappings wait for the GPU. If the buffer is written outside of
+    /// glium, for example by another context, you must wait on a fence yourself before mapping.
+    ///
     /// # Implementation
     ///
     /// - For persistent-mapped buffers, waits until the data is no longer accessed by the GPU then
     ///   returns a pointer to the existing mapping.
     /// - For immutable buffers, creates a temporary buffer containing the data of the buffer and
     ///   maps it.
-    /// - For other types, calls `glMapBuffer` or `glMapSubBuffer`.
+    /// - For other types, calls `glMapBufferRange` with `GL_MAP_READ_BIT`.
     ///
     #[inline]
     pub fn map_read(self) -> ReadMapping<'a, T> {
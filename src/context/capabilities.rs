@@ -34,6 +34,12 @@ pub struct Capabilities {
     /// An empty list means that the backend doesn't have a compiler.
     pub supported_glsl_versions: Vec<Version>,
 
+    /// The highest version of GLSL supported by the compiler, parsed from
+    /// `GL_SHADING_LANGUAGE_VERSION`.
+    ///
+    /// `None` if the backend doesn't have a compiler or if the string couldn't be parsed.
+    pub glsl_version: Option<Version>,
+
     /// Returns a version or release number. Vendor-specific information may follow the version
     /// number.
     pub version: String,
@@ -73,6 +79,9 @@ pub struct Capabilities {
     /// True if the default framebuffer is in sRGB.
     pub srgb: bool,
 
+    /// True if conservative rasterization can be enabled with `GL_CONSERVATIVE_RASTERIZATION_NV`.
+    pub conservative_rasterization: bool,
+
     /// Number of bits in the default framebuffer's depth buffer
     pub depth_bits: Option<u16>,
 
@@ -98,6 +107,14 @@ pub struct Capabilities {
     /// Maximum size of a texture (i.e. GL_MAX_TEXTURE_SIZE)
     pub max_texture_size: gl::types::GLint,
 
+    /// Maximum width and height of a render buffer (i.e. GL_MAX_RENDERBUFFER_SIZE). `None` if
+    /// render buffers are not supported.
+    pub max_renderbuffer_size: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a multisample render buffer (i.e. GL_MAX_SAMPLES). `None`
+    /// if multisample render buffers are not supported.
+    pub max_renderbuffer_samples: Option<gl::types::GLint>,
+
     /// Maximum size of a buffer texture. `None` if this is not supported.
     pub max_texture_buffer_size: Option<gl::types::GLint>,
 
@@ -110,6 +127,10 @@ pub struct Capabilities {
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
+    /// Maximum number of vertices that a geometry shader can output. `None` if geometry shaders
+    /// are not supported.
+    pub max_geometry_output_vertices: Option<gl::types::GLint>,
+
     /// Number of available buffer bind points for `GL_ATOMIC_COUNTER_BUFFER`.
     pub max_indexed_atomic_counter_buffer: gl::types::GLint,
 
@@ -194,6 +215,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             get_supported_glsl(gl, version, extensions)
         },
 
+        glsl_version: {
+            let s = gl.GetString(gl::SHADING_LANGUAGE_VERSION);
+            if s.is_null() {
+                None
+            } else {
+                CStr::from_ptr(s as *const _).to_str().ok()
+                                             .and_then(crate::version::parse_glsl_version)
+            }
+        },
+
         version: {
             let s = gl.GetString(gl::VERSION);
             assert!(!s.is_null());
@@ -320,6 +351,8 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        conservative_rasterization: extensions.gl_nv_conservative_raster,
+
         depth_bits: {
             let mut value = 0;
 
@@ -408,7 +441,10 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             val
         },
 
-        max_texture_max_anisotropy: if !extensions.gl_ext_texture_filter_anisotropic {
+        max_texture_max_anisotropy: if !(version >= &Version(Api::Gl, 4, 6) ||
+                                         extensions.gl_arb_texture_filter_anisotropic ||
+                                         extensions.gl_ext_texture_filter_anisotropic)
+        {
             None
 
         } else {
@@ -425,6 +461,32 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             val
         },
 
+        max_renderbuffer_size: {
+            if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 2, 0) ||
+               extensions.gl_arb_framebuffer_object || extensions.gl_ext_framebuffer_object
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_RENDERBUFFER_SIZE, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
+        max_renderbuffer_samples: {
+            if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_arb_framebuffer_object || extensions.gl_ext_framebuffer_multisample
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_SAMPLES, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
         max_texture_buffer_size: {
             if version >= &Version(Api::Gl, 3, 0) || extensions.gl_arb_texture_buffer_object ||
                extensions.gl_ext_texture_buffer_object || extensions.gl_oes_texture_buffer ||
@@ -473,6 +535,21 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             None
         },
 
+        max_geometry_output_vertices: if version >= &Version(Api::Gl, 3, 2) ||
+            version >= &Version(Api::GlEs, 3, 2) || extensions.gl_arb_geometry_shader4 ||
+            extensions.gl_ext_geometry_shader4 || extensions.gl_ext_geometry_shader ||
+            extensions.gl_oes_geometry_shader
+        {
+            Some({
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_GEOMETRY_OUTPUT_VERTICES, &mut val);
+                val
+            })
+
+        } else {
+            None
+        },
+
         max_indexed_atomic_counter_buffer: if version >= &Version(Api::Gl, 4, 2) {      // TODO: ARB_shader_atomic_counters   // TODO: GLES
             let mut val = 0;
             gl.GetIntegerv(gl::MAX_ATOMIC_COUNTER_BUFFER_BINDINGS, &mut val);
@@ -482,7 +559,9 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
         },
 
         max_indexed_shader_storage_buffer: {
-            if version >= &Version(Api::Gl, 4, 3) || extensions.gl_arb_shader_storage_buffer_object {      // TODO: GLES
+            if version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 1) ||
+               extensions.gl_arb_shader_storage_buffer_object
+            {
                 let mut val = 0;
                 gl.GetIntegerv(gl::MAX_SHADER_STORAGE_BUFFER_BINDINGS, &mut val);
                 val
@@ -603,6 +682,60 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
     }
 }
 
+/// Loads the capabilities while the default framebuffer is bound.
+///
+/// Some capabilities, like the number of depth bits, are properties of the default framebuffer.
+/// When the capabilities are loaded lazily, another framebuffer may be bound. This function
+/// binds the default framebuffer, loads the capabilities, then binds the previous framebuffers
+/// again so that glium's state cache stays valid.
+///
+/// *Safety*: the OpenGL context corresponding to `gl` must be current in the thread.
+pub unsafe fn get_capabilities_with_default_framebuffer(gl: &gl::Gl, version: &Version,
+                                                        extensions: &ExtensionsList)
+                                                        -> Capabilities
+{
+    if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) ||
+       extensions.gl_arb_framebuffer_object
+    {
+        let (mut draw, mut read) = (0, 0);
+        gl.GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut draw);
+        gl.GetIntegerv(gl::READ_FRAMEBUFFER_BINDING, &mut read);
+
+        if draw == 0 && read == 0 {
+            return get_capabilities(gl, version, extensions);
+        }
+
+        gl.BindFramebuffer(gl::FRAMEBUFFER, 0);
+        let capabilities = get_capabilities(gl, version, extensions);
+        gl.BindFramebuffer(gl::DRAW_FRAMEBUFFER, draw as gl::types::GLuint);
+        gl.BindFramebuffer(gl::READ_FRAMEBUFFER, read as gl::types::GLuint);
+        capabilities
+
+    } else if version >= &Version(Api::GlEs, 2, 0) || extensions.gl_ext_framebuffer_object {
+        let mut framebuffer = 0;
+        gl.GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut framebuffer);
+
+        if framebuffer == 0 {
+            return get_capabilities(gl, version, extensions);
+        }
+
+        if version >= &Version(Api::GlEs, 2, 0) {
+            gl.BindFramebuffer(gl::FRAMEBUFFER, 0);
+            let capabilities = get_capabilities(gl, version, extensions);
+            gl.BindFramebuffer(gl::FRAMEBUFFER, framebuffer as gl::types::GLuint);
+            capabilities
+        } else {
+            gl.BindFramebufferEXT(gl::FRAMEBUFFER_EXT, 0);
+            let capabilities = get_capabilities(gl, version, extensions);
+            gl.BindFramebufferEXT(gl::FRAMEBUFFER_EXT, framebuffer as gl::types::GLuint);
+            capabilities
+        }
+
+    } else {
+        get_capabilities(gl, version, extensions)
+    }
+}
+
 /// Gets the list of GLSL versions supported by the backend.
 ///
 /// *Safety*: the OpenGL context corresponding to `gl` must be current in the thread.
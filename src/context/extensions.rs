@@ -64,6 +64,7 @@ extensions! {
     "GL_ARB_depth_texture" => gl_arb_depth_texture,
     "GL_ARB_direct_state_access" => gl_arb_direct_state_access,
     "GL_ARB_draw_buffers" => gl_arb_draw_buffers,
+    "GL_ARB_draw_buffers_blend" => gl_arb_draw_buffers_blend,
     "GL_ARB_draw_elements_base_vertex" => gl_arb_draw_elements_base_vertex,
     "GL_ARB_compatibility" => gl_arb_compatibility,
     "GL_ARB_ES2_compatibility" => gl_arb_es2_compatibility,
@@ -106,6 +107,7 @@ extensions! {
     "GL_ARB_texture_compression_bptc" => gl_arb_texture_compression_bptc,
     "GL_ARB_texture_cube_map" => gl_arb_texture_cube_map,
     "GL_ARB_texture_cube_map_array" => gl_arb_texture_cube_map_array,
+    "GL_ARB_texture_filter_anisotropic" => gl_arb_texture_filter_anisotropic,
     "GL_ARB_texture_float" => gl_arb_texture_float,
     "GL_ARB_texture_multisample" => gl_arb_texture_multisample,
     "GL_ARB_texture_non_power_of_two" => gl_arb_texture_non_power_of_two,
@@ -113,8 +115,12 @@ extensions! {
     "GL_ARB_texture_rgb10_a2ui" => gl_arb_texture_rgb10_a2ui,
     "GL_ARB_texture_stencil8" => gl_arb_texture_stencil8,
     "GL_ARB_texture_storage" => gl_arb_texture_storage,
+    "GL_ARB_texture_swizzle" => gl_arb_texture_swizzle,
+    "GL_ARB_texture_view" => gl_arb_texture_view,
     "GL_ARB_timer_query" => gl_arb_timer_query,
+    "GL_ARB_transform_feedback2" => gl_arb_transform_feedback2,
     "GL_ARB_transform_feedback3" => gl_arb_transform_feedback3,
+    "GL_ARB_transform_feedback_instanced" => gl_arb_transform_feedback_instanced,
     "GL_ARB_uniform_buffer_object" => gl_arb_uniform_buffer_object,
     "GL_ARB_vertex_array_object" => gl_arb_vertex_array_object,
     "GL_ARB_vertex_buffer_object" => gl_arb_vertex_buffer_object,
@@ -147,6 +153,7 @@ extensions! {
     "GL_EXT_packed_float" => gl_ext_packed_float,
     "GL_EXT_primitive_bounding_box" => gl_ext_primitive_bounding_box,
     "GL_EXT_provoking_vertex" => gl_ext_provoking_vertex,
+    "GL_EXT_read_format_bgra" => gl_ext_read_format_bgra,
     "GL_EXT_robustness" => gl_ext_robustness,
     "GL_EXT_sRGB_write_control" => gl_ext_srgb_write_control,
     "GL_EXT_texture3D" => gl_ext_texture3d,
@@ -163,6 +170,7 @@ extensions! {
     "GL_EXT_texture_shared_exponent" => gl_ext_texture_shared_exponent,
     "GL_EXT_texture_snorm" => gl_ext_texture_snorm,
     "GL_EXT_texture_sRGB" => gl_ext_texture_srgb,
+    "GL_EXT_texture_swizzle" => gl_ext_texture_swizzle,
     "GL_EXT_transform_feedback" => gl_ext_transform_feedback,
     "GL_GREMEDY_string_marker" => gl_gremedy_string_marker,
     "GL_KHR_debug" => gl_khr_debug,
@@ -171,9 +179,11 @@ extensions! {
     "GL_KHR_robust_buffer_access_behavior" => gl_khr_robust_buffer_access_behavior,
     "GL_NV_fbo_color_attachments" => gl_nv_fbo_color_attachments,
     "GL_NV_conditional_render" => gl_nv_conditional_render,
+    "GL_NV_conservative_raster" => gl_nv_conservative_raster,
     "GL_NV_copy_buffer" => gl_nv_copy_buffer,
     "GL_NV_depth_clamp" => gl_nv_depth_clamp,
     "GL_NV_framebuffer_multisample" => gl_nv_framebuffer_multisample,
+    "GL_NV_framebuffer_multisample_coverage" => gl_nv_framebuffer_multisample_coverage,
     "GL_NV_half_float" => gl_nv_half_float,
     "GL_NV_internalformat_sample_query" => gl_nv_internalformat_sample_query,
     "GL_NV_pixel_buffer_object" => gl_nv_pixel_buffer_object,
@@ -218,7 +228,7 @@ extensions! {
 /// Can panic if the version number doesn't match the backend, leading to unloaded functions
 /// being called.
 ///
-unsafe fn get_extensions_strings(gl: &gl::Gl, version: &Version) -> Vec<String> {
+pub unsafe fn get_extensions_strings(gl: &gl::Gl, version: &Version) -> Vec<String> {
     if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) {
         let mut num_extensions = 0;
         gl.GetIntegerv(gl::NUM_EXTENSIONS, &mut num_extensions);
@@ -0,0 +1,135 @@
+use super::Context;
+
+use crate::CapabilitiesSource;
+use crate::ContextExt;
+use crate::buffer::{self, BufferType};
+use crate::program;
+use crate::program::ComputeShader;
+use crate::texture;
+use crate::version::Api;
+use crate::version::Version;
+
+/// Summary of the optional features that are available with a context.
+///
+/// When glium has a function that checks whether a feature is supported, such as
+/// `program::is_subroutine_supported` or `texture::is_cubemap_arrays_supported`, the
+/// corresponding field is the result of this function. The other fields use the same condition
+/// as the code that uses the feature. This allows choosing a rendering path once at startup.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FeatureReport {
+    /// Compute shaders can be used with `ComputeShader`.
+    pub compute_shaders: bool,
+
+    /// Programs can contain a geometry shader.
+    pub geometry_shaders: bool,
+
+    /// Programs can contain tessellation shaders.
+    pub tessellation_shaders: bool,
+
+    /// Shader subroutines can be selected.
+    pub subroutines: bool,
+
+    /// Programs can be retrieved and created in binary form.
+    pub program_binaries: bool,
+
+    /// Textures can be made resident and accessed through handles.
+    pub bindless_textures: bool,
+
+    /// Uniform blocks can be bound to buffers.
+    pub uniform_buffers: bool,
+
+    /// Shader storage blocks can be bound to buffers.
+    pub shader_storage_buffers: bool,
+
+    /// Atomic counters can be bound to buffers.
+    pub atomic_counters: bool,
+
+    /// Buffers can be created with immutable storage, which allows persistent mapping.
+    pub buffer_storage: bool,
+
+    /// Transform feedback can be used.
+    pub transform_feedback: bool,
+
+    /// Several draw commands can be submitted with a single indirect draw call.
+    pub multi_draw_indirect: bool,
+
+    /// Texture views can be created.
+    pub texture_views: bool,
+
+    /// Two-dimensional texture arrays can be created.
+    pub texture_2d_arrays: bool,
+
+    /// Cubemap arrays can be created.
+    pub cubemap_arrays: bool,
+
+    /// Multisample textures can be created.
+    pub multisample_textures: bool,
+
+    /// Anisotropic filtering can be used in samplers.
+    pub anisotropic_filtering: bool,
+
+    /// Timestamp and time elapsed queries can be used.
+    pub timer_queries: bool,
+
+    /// Each draw buffer can have its own blending, with `DrawParameters::blend_per_buffer`.
+    pub indexed_blending: bool,
+
+    /// Conservative rasterization can be enabled.
+    pub conservative_rasterization: bool,
+
+    /// Debug messages can be received from the driver.
+    pub debug_output: bool,
+
+    /// The default framebuffer has buffers for the left and right eyes.
+    pub stereo: bool,
+}
+
+impl Context {
+    /// Returns a summary of the optional features that are available with this context.
+    pub fn feature_report(&self) -> FeatureReport {
+        let version = self.get_opengl_version();
+        let extensions = self.get_extensions();
+        let capabilities = self.get_capabilities();
+
+        let (uniform_buffers, shader_storage_buffers, atomic_counters, multi_draw_indirect) = {
+            let mut ctxt = self.make_current();
+            (buffer::is_buffer_type_supported(&mut ctxt, BufferType::UniformBuffer),
+             buffer::is_buffer_type_supported(&mut ctxt, BufferType::ShaderStorageBuffer),
+             buffer::is_buffer_type_supported(&mut ctxt, BufferType::AtomicCounterBuffer),
+             buffer::is_buffer_type_supported(&mut ctxt, BufferType::DrawIndirectBuffer))
+        };
+
+        FeatureReport {
+            compute_shaders: ComputeShader::is_supported(self),
+            geometry_shaders: program::is_geometry_shader_supported(self),
+            tessellation_shaders: program::is_tessellation_shader_supported(self),
+            subroutines: program::is_subroutine_supported(self),
+            program_binaries: program::is_binary_supported(self),
+            bindless_textures: extensions.gl_arb_bindless_texture,
+            uniform_buffers,
+            shader_storage_buffers,
+            atomic_counters,
+            buffer_storage: version >= &Version(Api::Gl, 4, 4) || extensions.gl_arb_buffer_storage,
+            // same as `vertex::is_transform_feedback_supported`, which requires a facade
+            transform_feedback: version >= &Version(Api::Gl, 3, 0) ||
+                                version >= &Version(Api::GlEs, 3, 0) ||
+                                extensions.gl_ext_transform_feedback,
+            multi_draw_indirect,
+            texture_views: version >= &Version(Api::Gl, 4, 3) || extensions.gl_arb_texture_view,
+            texture_2d_arrays: texture::is_texture_2d_array_supported(self),
+            cubemap_arrays: texture::is_cubemap_arrays_supported(self),
+            multisample_textures: texture::is_texture_2d_multisample_supported(self),
+            anisotropic_filtering: capabilities.max_texture_max_anisotropy.is_some(),
+            timer_queries: version >= &Version(Api::Gl, 3, 3) || extensions.gl_arb_timer_query,
+            indexed_blending: version >= &Version(Api::Gl, 4, 0) ||
+                              version >= &Version(Api::GlEs, 3, 2) ||
+                              (version >= &Version(Api::Gl, 3, 0) &&
+                               extensions.gl_arb_draw_buffers_blend),
+            conservative_rasterization: capabilities.conservative_rasterization,
+            debug_output: version >= &Version(Api::Gl, 4, 5) ||
+                          version >= &Version(Api::GlEs, 3, 2) ||
+                          extensions.gl_khr_debug || extensions.gl_arb_debug_output,
+            stereo: capabilities.stereo,
+        }
+    }
+}
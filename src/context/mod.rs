@@ -4,43 +4,63 @@ use crate::gl;
 
 use std::collections::HashMap;
 use std::mem;
+use std::ops::Deref;
 use std::ptr;
 use std::str;
 use std::borrow::Cow;
-use std::cell::{Cell, RefCell, RefMut};
+use std::cell::{Cell, OnceCell, RefCell, RefMut};
 use std::marker::PhantomData;
 use std::ffi::CStr;
 use std::rc::Rc;
 use std::os::raw;
 use std::hash::BuildHasherDefault;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use fnv::FnvHasher;
 
+use crate::DrawError;
+use crate::GlError;
 use crate::IncompatibleOpenGl;
 use crate::SwapBuffersError;
+use crate::BufferExt;
 use crate::CapabilitiesSource;
 use crate::ContextExt;
+use crate::GlObject;
+use crate::ToGlEnum;
 use crate::backend::Backend;
 use crate::version;
 use crate::version::Api;
 use crate::version::Version;
 
+use crate::buffer::{self, BufferAnySlice, BufferType};
 use crate::debug;
 use crate::fbo;
+use crate::framebuffer::{DefaultFramebufferAttachment, DrawBufferError};
+use crate::index::IndicesSource;
 use crate::ops;
+use crate::program::Program;
 use crate::sampler_object;
 use crate::texture;
 use crate::uniforms;
+use crate::vertex::MultiVerticesSource;
 use crate::vertex_array_object;
 
 pub use self::capabilities::{ReleaseBehavior, Capabilities, Profile};
 pub use self::extensions::ExtensionsList;
+pub use self::feature_report::FeatureReport;
 pub use self::state::GlState;
+pub use self::state_snapshot::GlStateSnapshot;
+#[cfg(feature = "debug_state_cache")]
+pub use self::state_check::StateMismatch;
 pub use self::uuid::UuidError;
 
 mod capabilities;
 mod extensions;
+mod feature_report;
 mod state;
+#[cfg(feature = "debug_state_cache")]
+mod state_check;
+mod state_snapshot;
 mod uuid;
 
 /// Stores the state and information required for glium to execute commands. Most public glium
@@ -59,9 +79,14 @@ pub struct Context {
     /// Tells whether or not the backend supports each extension.
     extensions: ExtensionsList,
 
-    /// Constants defined by the backend and retrieved at initialization. For example, number
-    /// of texture units, maximum size of the viewport, etc.
-    capabilities: Capabilities,
+    /// Constants defined by the backend and retrieved at initialization, or the first time
+    /// they are needed if the context was created with `new_lazy`. For example, number of
+    /// texture units, maximum size of the viewport, etc.
+    capabilities: OnceCell<Capabilities>,
+
+    /// Names of all the extensions supported by the backend, including the ones that glium
+    /// doesn't know about. Retrieved the first time `has_extension` is called.
+    extensions_names: OnceCell<Vec<String>>,
 
     /// Glue between glium and the code that handles windowing. Contains functions that allows
     /// you to swap buffers, retrieve the size of the framebuffer, etc.
@@ -71,8 +96,19 @@ pub struct Context {
     /// call.
     check_current_context: bool,
 
+    /// Identifier of this context, compared with `CURRENT_CONTEXT_ID` to determine whether the
+    /// user has promised with `assume_current` that its context is current.
+    id: usize,
+
     /// The callback that is used by the debug output feature.
-    debug_callback: Option<debug::DebugCallback>,
+    debug_callback: RefCell<Option<debug::DebugCallback>>,
+
+    /// Whether or not glium must report the uniforms of a program that haven't been set before
+    /// a draw command through the debug callback.
+    warn_unset_uniforms: Cell<bool>,
+
+    /// Whether or not finishing a `Frame` swaps the buffers.
+    frame_swaps_buffers: Cell<bool>,
 
     /// Whether or not errors triggered by ARB_debug_output (and similar extensions) should be
     /// reported to the user when `DebugCallbackBehavior::DebugMessageOnError` is used. This must
@@ -97,6 +133,33 @@ pub struct Context {
     /// List of images handles that are resident. We need to call `MakeImageHandleResidentARB`
     /// when rebuilding the context.
     resident_image_handles: RefCell<Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
+
+    /// Errors returned by `glGetError` after each operation, or `None` if error logging is
+    /// disabled.
+    error_log: RefCell<Option<Vec<(GlError, &'static str)>>>,
+}
+
+/// Restores the binding of a pixel pack or unpack buffer when dropped.
+struct PixelBufferBindingGuard<'a> {
+    ctxt: CommandContext<'a>,
+    previous: gl::types::GLuint,
+    ty: BufferType,
+}
+
+impl<'a> Drop for PixelBufferBindingGuard<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { buffer::bind_buffer(&mut self.ctxt, self.previous, self.ty); }
+    }
+}
+
+/// Identifier that will be given to the next context.
+static NEXT_CONTEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    /// Identifier of the context that has been passed to `assume_current` in this thread, or
+    /// `0` if the backend must be asked.
+    static CURRENT_CONTEXT_ID: Cell<usize> = const { Cell::new(0) };
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -115,7 +178,7 @@ pub struct CommandContext<'a> {
     pub extensions: &'a ExtensionsList,
 
     /// Capabilities of the backend.
-    pub capabilities: &'a Capabilities,
+    pub capabilities: LazyCapabilities<'a>,
 
     /// Whether or not errors triggered by ARB_debug_output (and similar extensions) should be
     /// reported to the user (by panicking).
@@ -136,12 +199,37 @@ pub struct CommandContext<'a> {
     /// List of image handles and their access that need to be made resident.
     pub resident_image_handles: RefMut<'a, Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
 
+    /// Errors returned by `glGetError`, or `None` if error logging is disabled.
+    pub error_log: RefMut<'a, Option<Vec<(GlError, &'static str)>>>,
+
     /// This marker is here to prevent `CommandContext` from implementing `Send`
     // TODO: use this when possible
     //impl<'a, 'b> !Send for CommandContext<'a, 'b> {}
     marker: PhantomData<*mut u8>,
 }
 
+/// Gives access to the capabilities of the backend, and loads them the first time they are
+/// accessed if this hasn't been done yet.
+pub struct LazyCapabilities<'a> {
+    capabilities: &'a OnceCell<Capabilities>,
+    gl: &'a gl::Gl,
+    version: &'a Version,
+    extensions: &'a ExtensionsList,
+}
+
+impl<'a> Deref for LazyCapabilities<'a> {
+    type Target = Capabilities;
+
+    #[inline]
+    fn deref(&self) -> &Capabilities {
+        // the context is current, since a `CommandContext` exists
+        self.capabilities.get_or_init(|| unsafe {
+            capabilities::get_capabilities_with_default_framebuffer(self.gl, self.version,
+                                                                    self.extensions)
+        })
+    }
+}
+
 impl Context {
     /// Builds a new context.
     ///
@@ -152,6 +240,10 @@ impl Context {
     /// current in the same thread as this context. Passing `true` makes things safe but
     /// is slightly slower.
     ///
+    /// When passing `true`, the backend is asked whether its context is current before each
+    /// operation. Use `assume_current` to skip these checks when you know that the context is
+    /// current.
+    ///
     /// The OpenGL context must be newly-created. If you make modifications to the context before
     /// passing it to this function, glium's state cache may mismatch the actual one.
     ///
@@ -161,6 +253,37 @@ impl Context {
         callback_behavior: DebugCallbackBehavior,
     ) -> Result<Rc<Context>, IncompatibleOpenGl>
         where B: Backend + 'static
+    {
+        Context::new_impl(backend, check_current_context, callback_behavior, false)
+    }
+
+    /// Builds a new context whose capabilities are only retrieved the first time they are
+    /// needed.
+    ///
+    /// Retrieving the capabilities involves a lot of queries, especially the list of
+    /// multisampling modes supported by each internal format. With this function, these
+    /// queries are made by the first operation that needs the capabilities instead, which is
+    /// often the first texture or buffer creation. Whether this is worth it depends on the
+    /// driver; the `init` and `init_lazy` benchmarks can be used to compare the two.
+    ///
+    /// The compatibility check is still performed. See `new` for the other parameters.
+    pub unsafe fn new_lazy<B>(
+        backend: B,
+        check_current_context: bool,
+        callback_behavior: DebugCallbackBehavior,
+    ) -> Result<Rc<Context>, IncompatibleOpenGl>
+        where B: Backend + 'static
+    {
+        Context::new_impl(backend, check_current_context, callback_behavior, true)
+    }
+
+    unsafe fn new_impl<B>(
+        backend: B,
+        check_current_context: bool,
+        callback_behavior: DebugCallbackBehavior,
+        lazy_capabilities: bool,
+    ) -> Result<Rc<Context>, IncompatibleOpenGl>
+        where B: Backend + 'static
     {
         backend.make_current();
 
@@ -171,7 +294,11 @@ impl Context {
         let extensions = extensions::get_extensions(&gl, &version);
         check_gl_compatibility(&version, &extensions)?;
 
-        let capabilities = capabilities::get_capabilities(&gl, &version, &extensions);
+        let capabilities = OnceCell::new();
+        if !lazy_capabilities {
+            let _ = capabilities.set(capabilities::get_capabilities(&gl, &version, &extensions));
+        }
+
         let report_debug_output_errors = Cell::new(true);
 
         let vertex_array_objects = vertex_array_object::VertexAttributesSystem::new();
@@ -203,18 +330,23 @@ impl Context {
             version,
             extensions,
             capabilities,
-            debug_callback,
+            extensions_names: OnceCell::new(),
+            debug_callback: RefCell::new(debug_callback),
+            warn_unset_uniforms: Cell::new(false),
+            frame_swaps_buffers: Cell::new(true),
             report_debug_output_errors,
             backend: RefCell::new(Box::new(backend)),
             check_current_context,
+            id: NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed),
             framebuffer_objects: Some(framebuffer_objects),
             vertex_array_objects,
             samplers,
             resident_texture_handles,
             resident_image_handles,
+            error_log: RefCell::new(None),
         });
 
-        if context.debug_callback.is_some() {
+        if context.debug_callback.borrow().is_some() {
             init_debug_callback(&context, synchronous);
         }
 
@@ -257,6 +389,7 @@ impl Context {
         }
 
         new_backend.make_current();
+        CURRENT_CONTEXT_ID.with(|id| id.set(0));
 
         *self.state.borrow_mut() = Default::default();
         // FIXME: verify version, capabilities and extensions
@@ -277,6 +410,42 @@ impl Context {
         Ok(())
     }
 
+    /// Makes the backend's OpenGL context current if `check_current_context` was passed,
+    /// unless `assume_current` has been called for this context.
+    fn make_backend_current(&self) {
+        if self.check_current_context && CURRENT_CONTEXT_ID.with(|id| id.get()) != self.id {
+            let backend = self.backend.borrow();
+            if !backend.is_current() {
+                unsafe { backend.make_current() };
+                debug_assert!(backend.is_current());
+            }
+
+            // another context is now current, so any previous promise no longer holds
+            CURRENT_CONTEXT_ID.with(|id| id.set(0));
+        }
+    }
+
+    /// Tells glium that the OpenGL context of this `Context` is the current one in this thread.
+    ///
+    /// Glium will not ask the backend whether its context is current before the next
+    /// operations, until another glium context is used in this thread or `swap_buffers` is
+    /// called. This avoids the cost of the checks made when the context was created with
+    /// `check_current_context`, for example for the duration of a frame.
+    ///
+    /// This function does nothing if the context was created with `check_current_context`
+    /// set to `false`, as the backend is never asked in this situation.
+    ///
+    /// # Safety
+    ///
+    /// The OpenGL context of the backend must be current in this thread. Like passing `false`
+    /// for `check_current_context`, you must ensure that no other OpenGL context is made
+    /// current, by glium or by other code, until another glium context is used or
+    /// `swap_buffers` is called.
+    #[inline]
+    pub unsafe fn assume_current(&self) {
+        CURRENT_CONTEXT_ID.with(|id| id.set(self.id));
+    }
+
     /// Swaps the buffers in the backend.
     pub fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
         if self.state.borrow().lost_context {
@@ -316,6 +485,11 @@ impl Context {
 
         // swapping
         let err = backend.swap_buffers();
+
+        // other OpenGL contexts are likely to be made current between two frames, so the
+        // backend is asked again at the next operation
+        CURRENT_CONTEXT_ID.with(|id| id.set(0));
+
         if let Err(SwapBuffersError::ContextLost) = err {
             self.state.borrow_mut().lost_context = true;
         }
@@ -341,6 +515,16 @@ impl Context {
         version::get_supported_glsl_version(self.get_opengl_version())
     }
 
+    /// Returns the highest GLSL version supported by the compiler.
+    ///
+    /// This is the version reported by `GL_SHADING_LANGUAGE_VERSION`, which is usually higher
+    /// than the one returned by `get_supported_glsl_version`. If the driver doesn't report a
+    /// valid version, falls back to `get_supported_glsl_version`.
+    #[inline]
+    pub fn get_glsl_version(&self) -> Version {
+        self.capabilities().glsl_version.unwrap_or_else(|| self.get_supported_glsl_version())
+    }
+
     /// Returns true if the given GLSL version is supported.
     #[inline]
     pub fn is_glsl_version_supported(&self, version: &Version) -> bool {
@@ -369,6 +553,24 @@ impl Context {
         &self.capabilities().renderer
     }
 
+    /// Returns true if the backend supports the extension whose name is passed as parameter.
+    ///
+    /// The name must be the exact token of the extension, like `"GL_ARB_bindless_texture"`.
+    /// The comparison is case-sensitive. Contrary to `CapabilitiesSource::get_extensions`, this
+    /// works with any extension, including the ones that glium doesn't know about.
+    #[inline]
+    pub fn has_extension(&self, name: &str) -> bool {
+        let names = self.extensions_names.get_or_init(|| {
+            self.make_backend_current();
+            unsafe {
+                extensions::get_extensions_strings(&self.gl, &self.version)
+                    .into_iter().filter(|e| !e.is_empty()).collect()
+            }
+        });
+
+        names.iter().any(|e| e == name)
+    }
+
     /// Returns true if the context is in debug mode.
     ///
     /// Debug mode may provide additional error and performance issue reporting functionality.
@@ -419,23 +621,44 @@ impl Context {
             return true;
         }
 
+        self.check_reset_status() != ResetStatus::NoError
+    }
+
+    /// Calls `glGetGraphicsResetStatus` and returns whether the context has been reset since
+    /// the last call.
+    ///
+    /// After a reset, all the objects that belong to this context are invalid. If the status is
+    /// `GuiltyContextReset`, the reset was caused by this context and you must destroy it and
+    /// create a new one. The same thing should be done for the other statuses except `NoError`,
+    /// as glium has no way to restore its objects.
+    ///
+    /// Always returns `NoError` if none of OpenGL 4.5, OpenGL ES 3.2, `GL_KHR_robustness`,
+    /// `GL_EXT_robustness` or `GL_ARB_robustness` is available.
+    pub fn check_reset_status(&self) -> ResetStatus {
         let mut ctxt = self.make_current();
 
-        let lost = if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                      ctxt.version >= &Version(Api::GlEs, 3, 2) ||
-                      ctxt.extensions.gl_khr_robustness
+        let status = if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                        ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                        ctxt.extensions.gl_khr_robustness
         {
-            unsafe { ctxt.gl.GetGraphicsResetStatus() != gl::NO_ERROR }
+            unsafe { ctxt.gl.GetGraphicsResetStatus() }
         } else if ctxt.extensions.gl_ext_robustness {
-            unsafe { ctxt.gl.GetGraphicsResetStatusEXT() != gl::NO_ERROR }
+            unsafe { ctxt.gl.GetGraphicsResetStatusEXT() }
         } else if ctxt.extensions.gl_arb_robustness {
-            unsafe { ctxt.gl.GetGraphicsResetStatusARB() != gl::NO_ERROR }
+            unsafe { ctxt.gl.GetGraphicsResetStatusARB() }
         } else {
-            false
+            gl::NO_ERROR
         };
 
-        if lost { ctxt.state.lost_context = true; }
-        lost
+        let status = match status {
+            gl::GUILTY_CONTEXT_RESET => ResetStatus::GuiltyContextReset,
+            gl::INNOCENT_CONTEXT_RESET => ResetStatus::InnocentContextReset,
+            gl::UNKNOWN_CONTEXT_RESET => ResetStatus::UnknownContextReset,
+            _ => ResetStatus::NoError,
+        };
+
+        if status != ResetStatus::NoError { ctxt.state.lost_context = true; }
+        status
     }
 
     /// Returns the behavior when the current OpenGL context is changed.
@@ -477,6 +700,57 @@ impl Context {
         }
     }
 
+    /// Builds in advance the vertex array object that is used when drawing with these vertex
+    /// sources, indices and program.
+    ///
+    /// Glium creates and caches a vertex array object the first time a combination of buffers
+    /// and program is used for drawing. Calling this function at load time moves this cost
+    /// out of the first frame that uses the combination. The parameters are the same as the
+    /// ones of `Surface::draw`.
+    ///
+    /// Returns `AttributeTypeMismatch`, `AttributeMissing` or `VertexAttributeCollision` if
+    /// the vertex sources don't match the attributes of the program, like `Surface::draw`
+    /// would. This function does nothing if vertex array objects are not supported by the
+    /// backend. See also `Program::warm_up`.
+    pub fn warm_vao_cache<'a, 'b, V, I>(&self, vertex_buffers: V, indices: I, program: &Program)
+                                        -> Result<(), DrawError>
+        where V: MultiVerticesSource<'b>, I: Into<IndicesSource<'a>>
+    {
+        ops::warm_vao_cache(self, vertex_buffers, indices.into(), program)
+    }
+
+    /// Purges the caches of OpenGL objects that glium creates internally, which are framebuffer
+    /// objects, vertex array objects and sampler objects. This does not touch any of the objects
+    /// that you created yourself.
+    ///
+    /// ## Drop order
+    ///
+    /// The textures, buffers, programs and other objects that you create hold a reference to the
+    /// context, and destroy their OpenGL object when they are dropped. Therefore the context is
+    /// always destroyed last, and releases its internal caches at this moment.
+    ///
+    /// Call this function if you need the internal objects to be released earlier, for example
+    /// to free memory in a long-running application. They are created again when needed.
+    pub fn purge_internal_caches(&self) {
+        let mut ctxt = self.make_current();
+
+        fbo::FramebuffersContainer::purge_all(&mut ctxt);
+        vertex_array_object::VertexAttributesSystem::purge_all(&mut ctxt);
+
+        let samplers = mem::take(&mut *ctxt.samplers);
+        for (_, sampler) in samplers {
+            // deleting a sampler unbinds it from the texture units
+            let id = sampler.get_id();
+            sampler.destroy(&mut ctxt);
+
+            for unit in ctxt.state.texture_units.iter_mut() {
+                if unit.sampler == id {
+                    unit.sampler = 0;
+                }
+            }
+        }
+    }
+
     /// Returns an estimate of the amount of video memory available in bytes.
     ///
     /// Returns `None` if no estimate is available.
@@ -501,6 +775,122 @@ impl Context {
         }
     }
 
+    /// Returns true if the default framebuffer has buffers for the left and right eyes, in other
+    /// words if quad-buffered stereoscopic rendering is possible.
+    #[inline]
+    pub fn is_stereo(&self) -> bool {
+        self.capabilities().stereo
+    }
+
+    /// Chooses the buffer of the default framebuffer that the next draw commands on the
+    /// default framebuffer will draw upon. This calls `glDrawBuffer`.
+    ///
+    /// The default is the back buffer. Drawing upon the buffers of the right eye makes it
+    /// possible to do quad-buffered stereoscopic rendering, but requires a context created
+    /// with stereoscopy.
+    ///
+    /// The choice stays in effect for the following frames until it is changed again.
+    pub fn set_default_draw_buffer(&self, buffer: DefaultFramebufferAttachment)
+                                   -> Result<(), DrawBufferError>
+    {
+        let mut ctxt = self.make_current();
+
+        match buffer {
+            DefaultFramebufferAttachment::BackRight | DefaultFramebufferAttachment::FrontRight
+                if !ctxt.capabilities.stereo => return Err(DrawBufferError::StereoNotSupported),
+            _ => ()
+        };
+
+        let buffer = buffer.to_glenum();
+        if ctxt.state.default_framebuffer_draw == Some(buffer) {
+            return Ok(());
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 1, 0) {
+            unsafe {
+                fbo::bind_framebuffer(&mut ctxt, 0, true, false);
+                ctxt.gl.DrawBuffer(buffer);
+            }
+            ctxt.state.default_framebuffer_draw = Some(buffer);
+
+        } else if buffer == gl::BACK_LEFT {
+            // OpenGL ES always draws upon the back buffer
+            ctxt.state.default_framebuffer_draw = Some(buffer);
+
+        } else {
+            return Err(DrawBufferError::NotSupported);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the mapping of normalized device depth coordinates to window depth coordinates.
+    /// This calls `glDepthRange` on desktop OpenGL and `glDepthRangef` on OpenGL ES.
+    ///
+    /// Both values are clamped to `[0.0, 1.0]`. Draw commands always apply the `range` of
+    /// their `Depth` parameters, so this is mostly useful before calling raw OpenGL functions.
+    pub fn set_depth_range(&self, near: f32, far: f32) {
+        let mut ctxt = self.make_current();
+        crate::draw_parameters::set_depth_range(&mut ctxt,
+                                                (near.clamp(0.0, 1.0), far.clamp(0.0, 1.0)));
+    }
+
+    /// Returns the value of an integer state of OpenGL, as returned by `glGetIntegerv`.
+    ///
+    /// `pname` is the raw value of a `GL_*` constant, for example `0x0D33` for
+    /// `GL_MAX_TEXTURE_SIZE`. If the state contains multiple values, only the first one is
+    /// returned. If `pname` is not valid, an OpenGL error is generated and `0` is returned.
+    ///
+    /// Querying the state of OpenGL may cause a synchronization with the GPU on some drivers.
+    /// Don't call this in performance-critical code.
+    #[inline]
+    pub fn get_integer(&self, pname: u32) -> i32 {
+        self.get_integer_v(pname, 1)[0]
+    }
+
+    /// Returns the first `count` values of an integer state of OpenGL, as returned by
+    /// `glGetIntegerv`.
+    ///
+    /// See `get_integer`. Reading more values than the state contains returns zeroes for the
+    /// missing ones.
+    pub fn get_integer_v(&self, pname: u32, count: usize) -> Vec<i32> {
+        let ctxt = self.make_current();
+        // OpenGL writes as many values as the state contains, which `get_state_len` bounds
+        let mut values: Vec<gl::types::GLint> = vec![0; count.max(get_state_len(&ctxt, pname))];
+        unsafe { ctxt.gl.GetIntegerv(pname, values.as_mut_ptr()) };
+
+        values.truncate(count);
+        values
+    }
+
+    /// Returns the value of a floating-point state of OpenGL, as returned by `glGetFloatv`.
+    ///
+    /// `pname` is the raw value of a `GL_*` constant, for example `0x0B12` for
+    /// `GL_POINT_SIZE_RANGE`. If the state contains multiple values, only the first one is
+    /// returned. If `pname` is not valid, an OpenGL error is generated and `0.0` is returned.
+    ///
+    /// Querying the state of OpenGL may cause a synchronization with the GPU on some drivers.
+    /// Don't call this in performance-critical code.
+    #[inline]
+    pub fn get_float(&self, pname: u32) -> f32 {
+        self.get_float_v(pname, 1)[0]
+    }
+
+    /// Returns the first `count` values of a floating-point state of OpenGL, as returned by
+    /// `glGetFloatv`.
+    ///
+    /// See `get_float`. Reading more values than the state contains returns zeroes for the
+    /// missing ones.
+    pub fn get_float_v(&self, pname: u32, count: usize) -> Vec<f32> {
+        let ctxt = self.make_current();
+        // OpenGL writes as many values as the state contains, which `get_state_len` bounds
+        let mut values: Vec<gl::types::GLfloat> = vec![0.0; count.max(get_state_len(&ctxt, pname))];
+        unsafe { ctxt.gl.GetFloatv(pname, values.as_mut_ptr()) };
+
+        values.truncate(count);
+        values
+    }
+
     /// Reads the content of the front buffer.
     ///
     /// You will only see the data that has finished being drawn.
@@ -527,6 +917,24 @@ impl Context {
         Ok(T::from_raw(Cow::Owned(data), dimensions.0, dimensions.1))
     }
 
+    /// Reads the content of the front buffer in the BGRA format.
+    ///
+    /// Same as `read_front_buffer`, except that the components of each pixel are in the blue,
+    /// green, red, alpha order. OpenGL ES requires the `GL_EXT_read_format_bgra` extension,
+    /// otherwise `ReadError::OutputFormatNotSupported` is returned.
+    pub fn read_front_buffer_bgra<T>(&self) -> Result<T, ops::ReadError>
+        where T: texture::Texture2dDataSink<texture::Bgra>
+    {
+        let mut ctxt = self.make_current();
+        let dimensions = self.get_framebuffer_dimensions();
+        let rect = crate::Rect { left: 0, bottom: 0, width: dimensions.0, height: dimensions.1 };
+
+        let mut data = Vec::with_capacity(0);
+        ops::read(&mut ctxt, ops::Source::DefaultFramebuffer(gl::FRONT_LEFT), &rect,
+                          &mut data, false)?;
+        Ok(T::from_raw(Cow::Owned(data), dimensions.0, dimensions.1))
+    }
+
     /// Execute an arbitrary closure with the OpenGL context active. Useful if another
     /// component needs to directly manipulate OpenGL state.
     ///
@@ -541,6 +949,48 @@ impl Context {
         action()
     }
 
+    /// Binds `buffer` to `GL_PIXEL_PACK_BUFFER`, calls `action` with the offset in bytes of the
+    /// slice within the buffer, then restores the previous binding.
+    ///
+    /// While the buffer is bound, `glReadPixels`, `glGetTexImage` and similar functions write to
+    /// the buffer instead of to client memory, and their pointer parameter is an offset within
+    /// the buffer. This is meant to be used with raw OpenGL calls. The OpenGL context is current
+    /// while `action` is executed, but glium functions must not be called from within it.
+    ///
+    /// The previous binding is restored even if `action` panics, so that a pixel buffer never
+    /// stays bound by accident.
+    pub fn with_pixel_pack_buffer<T, F>(&self, buffer: BufferAnySlice<'_>, action: F) -> T
+                                        where F: FnOnce(usize) -> T
+    {
+        let mut ctxt = self.make_current();
+        let previous = ctxt.state.pixel_pack_buffer_binding;
+        buffer.prepare_and_bind_for_pixel_pack(&mut ctxt);
+
+        let _guard = PixelBufferBindingGuard { ctxt, previous, ty: BufferType::PixelPackBuffer };
+        action(buffer.get_offset_bytes())
+    }
+
+    /// Binds `buffer` to `GL_PIXEL_UNPACK_BUFFER`, calls `action` with the offset in bytes of
+    /// the slice within the buffer, then restores the previous binding.
+    ///
+    /// While the buffer is bound, `glTexImage2D`, `glTexSubImage2D` and similar functions read
+    /// from the buffer instead of from client memory, and their pointer parameter is an offset
+    /// within the buffer. This is meant to be used with raw OpenGL calls. The OpenGL context is
+    /// current while `action` is executed, but glium functions must not be called from within it.
+    ///
+    /// The previous binding is restored even if `action` panics, so that a pixel buffer never
+    /// stays bound by accident.
+    pub fn with_pixel_unpack_buffer<T, F>(&self, buffer: BufferAnySlice<'_>, action: F) -> T
+                                          where F: FnOnce(usize) -> T
+    {
+        let mut ctxt = self.make_current();
+        let previous = ctxt.state.pixel_unpack_buffer_binding;
+        buffer.prepare_and_bind_for_pixel_unpack(&mut ctxt);
+
+        let _guard = PixelBufferBindingGuard { ctxt, previous, ty: BufferType::PixelUnpackBuffer };
+        action(buffer.get_offset_bytes())
+    }
+
     /// Asserts that there are no OpenGL errors pending.
     ///
     /// This function should be used in tests.
@@ -554,6 +1004,42 @@ impl Context {
         };
     }
 
+    /// Enables or disables the error log.
+    ///
+    /// When the error log is enabled, glium calls `glGetError` after each draw, clear, blit
+    /// and read operation and after creating buffers and textures, and stores all the errors
+    /// that were returned alongside the name of the operation. They can then be retrieved with
+    /// `take_errors`.
+    ///
+    /// This is mostly useful with backends that don't support the debug output (like
+    /// OpenGL ES 2.0), where the debug callback can't report errors. Note that calling
+    /// `glGetError` often can be slow.
+    ///
+    /// Disabling the error log discards the errors that haven't been retrieved yet.
+    pub fn set_error_logging(&self, enabled: bool) {
+        let mut log = self.error_log.borrow_mut();
+        match (enabled, log.is_some()) {
+            (true, false) => *log = Some(Vec::new()),
+            (false, true) => *log = None,
+            _ => ()
+        }
+    }
+
+    /// Returns and clears the errors stored in the error log.
+    ///
+    /// Errors that were triggered outside of glium's operations (for example with
+    /// `exec_in_context`) are also returned, with `"unknown operation"` as the name of the
+    /// operation. Returns an empty list if the error log is disabled.
+    pub fn take_errors(&self) -> Vec<(GlError, &'static str)> {
+        let mut ctxt = self.make_current();
+        ctxt.log_errors("unknown operation");
+
+        match ctxt.error_log.as_mut() {
+            Some(log) => mem::take(log),
+            None => Vec::new(),
+        }
+    }
+
     /// DEPRECATED. Renamed `finish`.
     #[inline]
     pub fn synchronize(&self) {
@@ -626,6 +1112,56 @@ impl Context {
             Ok(())
         }
     }
+
+    /// Enables or disables the warnings about uniforms that are not provided to a draw command.
+    ///
+    /// When enabled, each draw command checks whether the program has active uniforms that
+    /// weren't passed in the `Uniforms` object, and lists them in a message sent to the debug
+    /// callback with the `Application` source, the `Other` type and a `Low` severity. These
+    /// uniforms keep the value of the previous draw command that used the program, or zero if
+    /// they were never set, which is often not what was intended.
+    ///
+    /// The default callback ignores low-severity messages, so you need to use
+    /// `DebugCallbackBehavior::PrintAll` or a custom callback to see them. This check is
+    /// disabled by default because it has a cost for each draw command.
+    #[inline]
+    pub fn set_unset_uniforms_warnings(&self, enabled: bool) {
+        self.warn_unset_uniforms.set(enabled);
+    }
+
+    /// Sets whether `Frame::finish` swaps the buffers. The default is `true`.
+    ///
+    /// Pass `false` if your application presents the frames itself, for example with
+    /// `Display::swap_buffers`. Finishing a `Frame` then only ends it, and the buffers are never
+    /// swapped implicitly. A `Frame` must still be finished before being dropped.
+    #[inline]
+    pub fn set_frame_swaps_buffers(&self, swaps: bool) {
+        self.frame_swaps_buffers.set(swaps);
+    }
+
+    /// Returns true if finishing a `Frame` swaps the buffers. See `set_frame_swaps_buffers`.
+    #[inline]
+    pub fn frame_swaps_buffers(&self) -> bool {
+        self.frame_swaps_buffers.get()
+    }
+
+    /// Returns true if the uniforms that are not provided must be reported before a draw command.
+    #[inline]
+    pub(crate) fn unset_uniforms_warnings_enabled(&self) -> bool {
+        self.warn_unset_uniforms.get()
+    }
+
+    /// Sends a message generated by glium to the debug callback, if there is one.
+    pub(crate) fn report_debug_message(&self, ty: debug::MessageType, severity: debug::Severity,
+                                       message: &str)
+    {
+        if let Ok(mut callback) = self.debug_callback.try_borrow_mut() {
+            if let Some(callback) = callback.as_mut() {
+                callback(debug::Source::Application, ty, severity, 0,
+                         self.report_debug_output_errors.get(), message);
+            }
+        }
+    }
 }
 
 impl ContextExt for Context {
@@ -635,33 +1171,39 @@ impl ContextExt for Context {
     }
 
     fn make_current(&self) -> CommandContext<'_> {
-        if self.check_current_context {
-            let backend = self.backend.borrow();
-            if !backend.is_current() {
-                unsafe { backend.make_current() };
-                debug_assert!(backend.is_current());
-            }
-        }
+        self.make_backend_current();
 
         CommandContext {
             gl: &self.gl,
             state: self.state.borrow_mut(),
             version: &self.version,
             extensions: &self.extensions,
-            capabilities: &self.capabilities,
+            capabilities: LazyCapabilities {
+                capabilities: &self.capabilities,
+                gl: &self.gl,
+                version: &self.version,
+                extensions: &self.extensions,
+            },
             report_debug_output_errors: &self.report_debug_output_errors,
             vertex_array_objects: &self.vertex_array_objects,
             framebuffer_objects: self.framebuffer_objects.as_ref().unwrap(),
             samplers: self.samplers.borrow_mut(),
             resident_texture_handles: self.resident_texture_handles.borrow_mut(),
             resident_image_handles: self.resident_image_handles.borrow_mut(),
+            error_log: self.error_log.borrow_mut(),
             marker: PhantomData,
         }
     }
 
     #[inline]
     fn capabilities(&self) -> &Capabilities {
-        &self.capabilities
+        self.capabilities.get_or_init(|| {
+            self.make_backend_current();
+            unsafe {
+                capabilities::get_capabilities_with_default_framebuffer(&self.gl, &self.version,
+                                                                        &self.extensions)
+            }
+        })
     }
 }
 
@@ -678,7 +1220,7 @@ impl CapabilitiesSource for Context {
 
     #[inline]
     fn get_capabilities(&self) -> &Capabilities {
-        &self.capabilities
+        self.capabilities()
     }
 }
 
@@ -699,13 +1241,19 @@ impl Drop for Context {
                 state: self.state.borrow_mut(),
                 version: &self.version,
                 extensions: &self.extensions,
-                capabilities: &self.capabilities,
+                capabilities: LazyCapabilities {
+                    capabilities: &self.capabilities,
+                    gl: &self.gl,
+                    version: &self.version,
+                    extensions: &self.extensions,
+                },
                 report_debug_output_errors: &self.report_debug_output_errors,
                 vertex_array_objects: &self.vertex_array_objects,
                 framebuffer_objects: self.framebuffer_objects.as_ref().unwrap(),
                 samplers: self.samplers.borrow_mut(),
                 resident_texture_handles: self.resident_texture_handles.borrow_mut(),
                 resident_image_handles: self.resident_image_handles.borrow_mut(),
+                error_log: self.error_log.borrow_mut(),
                 marker: PhantomData,
             };
 
@@ -732,6 +1280,26 @@ impl Drop for Context {
     }
 }
 
+impl<'a> CommandContext<'a> {
+    /// If error logging is enabled, retrieves all the pending OpenGL errors and adds them to
+    /// the log with the name of the operation that triggered them.
+    pub fn log_errors(&mut self, operation: &'static str) {
+        let log = match self.error_log.as_mut() {
+            Some(log) => log,
+            None => return,
+        };
+
+        // an implementation can have several error flags set at the same time, but a lost
+        // context may keep returning `GL_CONTEXT_LOST`
+        for _ in 0 .. 16 {
+            match GlError::from_gl_enum(unsafe { self.gl.GetError() }) {
+                Some(err) => log.push((err, operation)),
+                None => break,
+            }
+        }
+    }
+}
+
 impl<'a> CapabilitiesSource for CommandContext<'a> {
     #[inline]
     fn get_version(&self) -> &Version {
@@ -745,10 +1313,27 @@ impl<'a> CapabilitiesSource for CommandContext<'a> {
 
     #[inline]
     fn get_capabilities(&self) -> &Capabilities {
-        self.capabilities
+        &self.capabilities
     }
 }
 
+/// Returns the maximum number of values that `glGetIntegerv` or `glGetFloatv` can write for
+/// `pname`.
+fn get_state_len(ctxt: &CommandContext<'_>, pname: u32) -> usize {
+    // the lists of formats have a length that is given by another state, all the other states
+    // have at most 16 values (4x4 matrices)
+    let num_pname = match pname {
+        gl::COMPRESSED_TEXTURE_FORMATS => gl::NUM_COMPRESSED_TEXTURE_FORMATS,
+        gl::PROGRAM_BINARY_FORMATS => gl::NUM_PROGRAM_BINARY_FORMATS,
+        gl::SHADER_BINARY_FORMATS => gl::NUM_SHADER_BINARY_FORMATS,
+        _ => return 16,
+    };
+
+    let mut num = 0;
+    unsafe { ctxt.gl.GetIntegerv(num_pname, &mut num) };
+    (num.max(0) as usize).max(16)
+}
+
 /// Checks whether the backend supports glium. Returns an `Err` if it doesn't.
 fn check_gl_compatibility(version: &Version, extensions: &ExtensionsList)
     -> Result<(), IncompatibleOpenGl>
@@ -789,6 +1374,23 @@ fn check_gl_compatibility(version: &Version, extensions: &ExtensionsList)
     }
 }
 
+/// Status of the context, as returned by `Context::check_reset_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResetStatus {
+    /// The context hasn't been reset.
+    NoError,
+
+    /// The context has been reset because of something done with this context. It must be
+    /// recreated.
+    GuiltyContextReset,
+
+    /// The context has been reset because of something done with another context.
+    InnocentContextReset,
+
+    /// The context has been reset, but the cause is unknown.
+    UnknownContextReset,
+}
+
 /// Describes the behavior that the debug output should have.
 pub enum DebugCallbackBehavior {
     /// Don't do anything. This is the default behavior in release.
@@ -894,7 +1496,7 @@ fn init_debug_callback(context: &Rc<Context>, synchronous: bool) {
         // destructor
 
         let user_param = user_param as *const Context;
-        let user_param: &mut Context = unsafe { mem::transmute(user_param) };
+        let user_param: &Context = unsafe { &*user_param };
 
         let message = unsafe {
             String::from_utf8(CStr::from_ptr(message).to_bytes().to_vec()).unwrap()
@@ -931,10 +1533,13 @@ fn init_debug_callback(context: &Rc<Context>, synchronous: bool) {
             _ => return,        // TODO: what to do in this situation?
         };
 
-        if let Some(callback) = user_param.debug_callback.as_mut() {
-            // FIXME: catch_panic here once it's stable
-            callback(source, ty, severity, id, user_param.report_debug_output_errors.get(),
-                     &message);
+        // the callback may already be borrowed if the user's callback itself triggers a message
+        if let Ok(mut callback) = user_param.debug_callback.try_borrow_mut() {
+            if let Some(callback) = callback.as_mut() {
+                // FIXME: catch_panic here once it's stable
+                callback(source, ty, severity, id, user_param.report_debug_output_errors.get(),
+                         &message);
+            }
         }
     }
 
@@ -36,6 +36,9 @@ pub struct GlState {
     /// Whether GL_DITHER is enabled
     pub enabled_dither: bool,
 
+    /// Whether GL_CONSERVATIVE_RASTERIZATION_NV is enabled
+    pub enabled_conservative_rasterization: bool,
+
     /// Whether GL_FRAMEBUFFER_SRGB is enabled
     pub enabled_framebuffer_srgb: bool,
 
@@ -54,6 +57,9 @@ pub struct GlState {
     /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
     pub enabled_primitive_fixed_restart: bool,
 
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
     /// Whether GL_RASTERIZER_DISCARD is enabled
     pub enabled_rasterizer_discard: bool,
 
@@ -159,9 +165,17 @@ pub struct GlState {
     /// `None` means "unknown".
     pub default_framebuffer_read: Option<gl::types::GLenum>,
 
+    /// The latest value passed to `glDrawBuffer` with the default framebuffer.
+    /// `None` means "unknown".
+    pub default_framebuffer_draw: Option<gl::types::GLenum>,
+
     /// The latest render buffer bound with `glBindRenderbuffer`.
     pub renderbuffer: gl::types::GLuint,
 
+    /// Whether the blending state has been set individually for each draw buffer, in which
+    /// case `enabled_blend`, `blend_equation` and `blend_func` are not reliable.
+    pub indexed_blend: bool,
+
     /// The latest values passed to `glBlendEquation`.
     pub blend_equation: (gl::types::GLenum, gl::types::GLenum),
 
@@ -179,6 +193,9 @@ pub struct GlState {
     /// The latest value passed to `glDepthMask`.
     pub depth_mask: bool,
 
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
     /// The latest values passed to `glDepthRange`.
     pub depth_range: (f32, f32),
 
@@ -217,8 +234,8 @@ pub struct GlState {
     /// The latest value passed to `glCullFace`.
     pub cull_face: gl::types::GLenum,
 
-    /// The latest value passed to `glPolygonMode`.
-    pub polygon_mode: gl::types::GLenum,
+    /// The latest values passed to `glPolygonMode`, for front and back faces.
+    pub polygon_mode: (gl::types::GLenum, gl::types::GLenum),
 
     /// The latest values passed to `glPolygonOffset`.
     pub polygon_offset: (gl::types::GLfloat, gl::types::GLfloat),
@@ -244,6 +261,12 @@ pub struct GlState {
     /// The latest value passed to `glPatchParameter` with `GL_PATCH_VERTICES`.
     pub patch_patch_vertices: gl::types::GLint,
 
+    /// The latest value passed to `glPatchParameter` with `GL_PATCH_DEFAULT_OUTER_LEVEL`.
+    pub patch_default_outer_level: [gl::types::GLfloat; 4],
+
+    /// The latest value passed to `glPatchParameter` with `GL_PATCH_DEFAULT_INNER_LEVEL`.
+    pub patch_default_inner_level: [gl::types::GLfloat; 2],
+
     /// The id of the active texture unit.
     /// IMPORTANT: this is a raw number (0, 1, 2, ...), not an
     ///            enumeration (GL_TEXTURE0, GL_TEXTURE1, ...).
@@ -388,6 +411,7 @@ impl Default for GlState {
             enabled_depth_clamp_near: false,
             enabled_depth_clamp_far: false,
             enabled_dither: false,
+            enabled_conservative_rasterization: false,
             enabled_framebuffer_srgb: false,
             enabled_multisample: true,
             enabled_polygon_offset_fill: false,
@@ -401,6 +425,7 @@ impl Default for GlState {
             enabled_line_smooth: false,
             enabled_polygon_smooth: false,
             enabled_primitive_fixed_restart: false,
+            enabled_primitive_restart: false,
             enabled_program_point_size: false,
             enabled_clip_planes: 0,
 
@@ -429,16 +454,19 @@ impl Default for GlState {
             read_framebuffer: 0,
             draw_framebuffer: 0,
             default_framebuffer_read: None,
+            default_framebuffer_draw: None,
             renderbuffer: 0,
             depth_func: gl::LESS,
             depth_mask: true,
             depth_range: (0.0, 1.0),
+            primitive_restart_index: 0,
             stencil_func_front: (gl::ALWAYS, 0, 0xffffffff),
             stencil_func_back: (gl::ALWAYS, 0, 0xffffffff),
             stencil_mask_front: 0xffffffff,
             stencil_mask_back: 0xffffffff,
             stencil_op_front: (gl::KEEP, gl::KEEP, gl::KEEP),
             stencil_op_back: (gl::KEEP, gl::KEEP, gl::KEEP),
+            indexed_blend: false,
             blend_equation: (gl::FUNC_ADD, gl::FUNC_ADD),
             blend_func: (gl::ONE, gl::ZERO, gl::ONE, gl::ZERO),
             blend_color: (0.0, 0.0, 0.0, 0.0),
@@ -447,13 +475,15 @@ impl Default for GlState {
             line_width: 1.0,
             point_size: 1.0,
             cull_face: gl::BACK,
-            polygon_mode: gl::FILL,
+            polygon_mode: (gl::FILL, gl::FILL),
             smooth: (gl::DONT_CARE, gl::DONT_CARE),
             provoking_vertex: gl::LAST_VERTEX_CONVENTION,
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
             clamp_color: gl::FIXED_ONLY,
             patch_patch_vertices: 3,
+            patch_default_outer_level: [1.0; 4],
+            patch_default_inner_level: [1.0; 2],
             active_texture: 0,
             texture_units: small_vec_one(),
             samples_passed_query: 0,
@@ -0,0 +1,146 @@
+use std::fmt;
+
+use super::Context;
+
+use crate::ContextExt;
+use crate::Handle;
+use crate::gl;
+use crate::version::Api;
+use crate::version::Version;
+
+/// Describes a difference between the state cached by glium and the actual OpenGL state.
+///
+/// See `Context::verify_state_cache`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateMismatch {
+    /// Name of the OpenGL state, for example `"GL_BLEND"`.
+    pub name: &'static str,
+
+    /// The value that glium believes is current.
+    pub cached: String,
+
+    /// The value returned by OpenGL.
+    pub actual: String,
+}
+
+impl fmt::Display for StateMismatch {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}: cached {}, actual {}", self.name, self.cached, self.actual)
+    }
+}
+
+#[inline]
+fn check<T>(mismatches: &mut Vec<StateMismatch>, name: &'static str, cached: T, actual: T)
+    where T: PartialEq + fmt::Debug
+{
+    if cached != actual {
+        mismatches.push(StateMismatch {
+            name,
+            cached: format!("{:?}", cached),
+            actual: format!("{:?}", actual),
+        });
+    }
+}
+
+impl Context {
+    /// Queries the actual OpenGL state and compares it with the state cached by glium.
+    ///
+    /// Glium avoids redundant OpenGL calls by remembering the state it has set. If some
+    /// external code modifies the state without restoring it, this cache becomes wrong and
+    /// draw commands may behave unexpectedly. This function returns the list of the states
+    /// that don't match, which is empty if everything is fine.
+    ///
+    /// Only some of the most commonly modified states are checked: capabilities such as
+    /// blending and depth testing, the blending and depth functions, masks, the viewport and
+    /// scissor box, and the bound program, vertex array, buffer and framebuffers.
+    ///
+    /// This function is very slow, as each query stalls the OpenGL pipeline. It is only
+    /// available if the `debug_state_cache` feature is enabled.
+    pub fn verify_state_cache(&self) -> Vec<StateMismatch> {
+        let ctxt = self.make_current();
+        let mut mismatches = Vec::new();
+
+        let get_integer = |name| unsafe {
+            let mut value = 0;
+            ctxt.gl.GetIntegerv(name, &mut value);
+            value
+        };
+
+        let is_enabled = |name| unsafe { ctxt.gl.IsEnabled(name) != 0 };
+
+        if !ctxt.state.indexed_blend {
+            check(&mut mismatches, "GL_BLEND", ctxt.state.enabled_blend, is_enabled(gl::BLEND));
+        }
+        check(&mut mismatches, "GL_CULL_FACE", ctxt.state.enabled_cull_face,
+              is_enabled(gl::CULL_FACE));
+        check(&mut mismatches, "GL_DEPTH_TEST", ctxt.state.enabled_depth_test,
+              is_enabled(gl::DEPTH_TEST));
+        check(&mut mismatches, "GL_DITHER", ctxt.state.enabled_dither, is_enabled(gl::DITHER));
+        check(&mut mismatches, "GL_SCISSOR_TEST", ctxt.state.enabled_scissor_test,
+              is_enabled(gl::SCISSOR_TEST));
+        check(&mut mismatches, "GL_STENCIL_TEST", ctxt.state.enabled_stencil_test,
+              is_enabled(gl::STENCIL_TEST));
+
+        if !ctxt.state.indexed_blend {
+            check(&mut mismatches, "GL_BLEND_EQUATION", ctxt.state.blend_equation,
+                  (get_integer(gl::BLEND_EQUATION_RGB) as gl::types::GLenum,
+                   get_integer(gl::BLEND_EQUATION_ALPHA) as gl::types::GLenum));
+            check(&mut mismatches, "GL_BLEND_FUNC", ctxt.state.blend_func,
+                  (get_integer(gl::BLEND_SRC_RGB) as gl::types::GLenum,
+                   get_integer(gl::BLEND_DST_RGB) as gl::types::GLenum,
+                   get_integer(gl::BLEND_SRC_ALPHA) as gl::types::GLenum,
+                   get_integer(gl::BLEND_DST_ALPHA) as gl::types::GLenum));
+        }
+
+        check(&mut mismatches, "GL_DEPTH_FUNC", ctxt.state.depth_func,
+              get_integer(gl::DEPTH_FUNC) as gl::types::GLenum);
+        check(&mut mismatches, "GL_DEPTH_WRITEMASK", ctxt.state.depth_mask,
+              get_integer(gl::DEPTH_WRITEMASK) != 0);
+        check(&mut mismatches, "GL_CULL_FACE_MODE", ctxt.state.cull_face,
+              get_integer(gl::CULL_FACE_MODE) as gl::types::GLenum);
+
+        let color_mask = unsafe {
+            let mut value = [0; 4];
+            ctxt.gl.GetBooleanv(gl::COLOR_WRITEMASK, value.as_mut_ptr());
+            (value[0], value[1], value[2], value[3])
+        };
+        check(&mut mismatches, "GL_COLOR_WRITEMASK", ctxt.state.color_mask, color_mask);
+
+        if let Some(viewport) = ctxt.state.viewport {
+            let actual = unsafe {
+                let mut value = [0; 4];
+                ctxt.gl.GetIntegerv(gl::VIEWPORT, value.as_mut_ptr());
+                (value[0], value[1], value[2], value[3])
+            };
+            check(&mut mismatches, "GL_VIEWPORT", viewport, actual);
+        }
+
+        if let Some(scissor) = ctxt.state.scissor {
+            let actual = unsafe {
+                let mut value = [0; 4];
+                ctxt.gl.GetIntegerv(gl::SCISSOR_BOX, value.as_mut_ptr());
+                (value[0], value[1], value[2], value[3])
+            };
+            check(&mut mismatches, "GL_SCISSOR_BOX", scissor, actual);
+        }
+
+        if let Handle::Id(program) = ctxt.state.program {
+            check(&mut mismatches, "GL_CURRENT_PROGRAM", program,
+                  get_integer(gl::CURRENT_PROGRAM) as gl::types::GLuint);
+        }
+
+        check(&mut mismatches, "GL_ARRAY_BUFFER_BINDING", ctxt.state.array_buffer_binding,
+              get_integer(gl::ARRAY_BUFFER_BINDING) as gl::types::GLuint);
+
+        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            check(&mut mismatches, "GL_VERTEX_ARRAY_BINDING", ctxt.state.vertex_array,
+                  get_integer(gl::VERTEX_ARRAY_BINDING) as gl::types::GLuint);
+            check(&mut mismatches, "GL_READ_FRAMEBUFFER_BINDING", ctxt.state.read_framebuffer,
+                  get_integer(gl::READ_FRAMEBUFFER_BINDING) as gl::types::GLuint);
+            check(&mut mismatches, "GL_DRAW_FRAMEBUFFER_BINDING", ctxt.state.draw_framebuffer,
+                  get_integer(gl::DRAW_FRAMEBUFFER_BINDING) as gl::types::GLuint);
+        }
+
+        mismatches
+    }
+}
@@ -0,0 +1,331 @@
+use super::CommandContext;
+use super::Context;
+use super::state::TextureUnitState;
+
+use crate::ContextExt;
+use crate::Handle;
+use crate::gl;
+use crate::version::Api;
+use crate::version::Version;
+
+/// A copy of the OpenGL states that glium modifies the most often.
+///
+/// See `Context::save_state`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlStateSnapshot {
+    program: Option<gl::types::GLuint>,
+    vertex_array: Option<gl::types::GLuint>,
+    array_buffer: gl::types::GLuint,
+    element_array_buffer: gl::types::GLuint,
+    draw_framebuffer: gl::types::GLuint,
+    read_framebuffer: gl::types::GLuint,
+    // raw number of the active texture unit (0, 1, 2, ...)
+    active_texture: gl::types::GLenum,
+    // texture bound to each target of the active texture unit that the backend supports
+    textures: Vec<(gl::types::GLenum, gl::types::GLuint)>,
+    enabled_blend: bool,
+    enabled_cull_face: bool,
+    enabled_depth_test: bool,
+    enabled_scissor_test: bool,
+    enabled_stencil_test: bool,
+    blend_equation: (gl::types::GLenum, gl::types::GLenum),
+    blend_func: (gl::types::GLenum, gl::types::GLenum, gl::types::GLenum, gl::types::GLenum),
+    blend_color: (gl::types::GLclampf, gl::types::GLclampf,
+                  gl::types::GLclampf, gl::types::GLclampf),
+    depth_func: gl::types::GLenum,
+    depth_mask: bool,
+    color_mask: (gl::types::GLboolean, gl::types::GLboolean,
+                 gl::types::GLboolean, gl::types::GLboolean),
+    cull_face: gl::types::GLenum,
+    viewport: (gl::types::GLint, gl::types::GLint, gl::types::GLsizei, gl::types::GLsizei),
+    scissor: (gl::types::GLint, gl::types::GLint, gl::types::GLsizei, gl::types::GLsizei),
+}
+
+impl Context {
+    /// Queries the current OpenGL state and returns a copy of it.
+    ///
+    /// This is useful when glium is used inside of a frame owned by another renderer. Call
+    /// this function before drawing with glium, then pass the snapshot to `restore_state`
+    /// once you are done, and the other renderer finds the state as it left it.
+    ///
+    /// The snapshot contains the bound program, vertex array, array and element array
+    /// buffers and framebuffers, the active texture unit and the texture bound to each of its
+    /// targets, the blend, depth, scissor, stencil and face culling switches, the blending and
+    /// depth functions, the color and depth masks, the viewport and the scissor box. Any other
+    /// state modified by glium isn't restored.
+    ///
+    /// Glium's cache of the OpenGL state is updated with the values that have been queried,
+    /// so that the changes made by the other renderer are taken into account.
+    ///
+    /// Each query stalls the OpenGL pipeline, so this function is slow.
+    pub fn save_state(&self) -> GlStateSnapshot {
+        let mut ctxt = self.make_current();
+
+        let get_integer = |name| unsafe {
+            let mut value = 0;
+            ctxt.gl.GetIntegerv(name, &mut value);
+            value
+        };
+
+        let get_integer_4 = |name| unsafe {
+            let mut value = [0; 4];
+            ctxt.gl.GetIntegerv(name, value.as_mut_ptr());
+            (value[0], value[1], value[2], value[3])
+        };
+
+        let is_enabled = |name| unsafe { ctxt.gl.IsEnabled(name) != 0 };
+
+        let program = if ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                         ctxt.version >= &Version(Api::GlEs, 2, 0)
+        {
+            Some(get_integer(gl::CURRENT_PROGRAM) as gl::types::GLuint)
+        } else {
+            None
+        };
+
+        let vertex_array = if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                              ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                              ctxt.extensions.gl_arb_vertex_array_object ||
+                              ctxt.extensions.gl_oes_vertex_array_object ||
+                              ctxt.extensions.gl_apple_vertex_array_object
+        {
+            Some(get_integer(gl::VERTEX_ARRAY_BINDING) as gl::types::GLuint)
+        } else {
+            None
+        };
+
+        let (draw_framebuffer, read_framebuffer) = if has_separate_framebuffers(&ctxt) {
+            (get_integer(gl::DRAW_FRAMEBUFFER_BINDING) as gl::types::GLuint,
+             get_integer(gl::READ_FRAMEBUFFER_BINDING) as gl::types::GLuint)
+        } else {
+            let framebuffer = get_integer(gl::FRAMEBUFFER_BINDING) as gl::types::GLuint;
+            (framebuffer, framebuffer)
+        };
+
+        let color_mask = unsafe {
+            let mut value = [0; 4];
+            ctxt.gl.GetBooleanv(gl::COLOR_WRITEMASK, value.as_mut_ptr());
+            (value[0], value[1], value[2], value[3])
+        };
+
+        let blend_color = unsafe {
+            let mut value = [0.0; 4];
+            ctxt.gl.GetFloatv(gl::BLEND_COLOR, value.as_mut_ptr());
+            (value[0], value[1], value[2], value[3])
+        };
+
+        let textures = texture_targets(&ctxt).iter()
+            .map(|&(target, binding)| (target, get_integer(binding) as gl::types::GLuint))
+            .collect();
+
+        let snapshot = GlStateSnapshot {
+            program,
+            vertex_array,
+            array_buffer: get_integer(gl::ARRAY_BUFFER_BINDING) as gl::types::GLuint,
+            element_array_buffer: get_integer(gl::ELEMENT_ARRAY_BUFFER_BINDING)
+                                      as gl::types::GLuint,
+            draw_framebuffer,
+            read_framebuffer,
+            active_texture: get_integer(gl::ACTIVE_TEXTURE) as gl::types::GLenum - gl::TEXTURE0,
+            textures,
+            enabled_blend: is_enabled(gl::BLEND),
+            enabled_cull_face: is_enabled(gl::CULL_FACE),
+            enabled_depth_test: is_enabled(gl::DEPTH_TEST),
+            enabled_scissor_test: is_enabled(gl::SCISSOR_TEST),
+            enabled_stencil_test: is_enabled(gl::STENCIL_TEST),
+            blend_equation: (get_integer(gl::BLEND_EQUATION_RGB) as gl::types::GLenum,
+                             get_integer(gl::BLEND_EQUATION_ALPHA) as gl::types::GLenum),
+            blend_func: (get_integer(gl::BLEND_SRC_RGB) as gl::types::GLenum,
+                         get_integer(gl::BLEND_DST_RGB) as gl::types::GLenum,
+                         get_integer(gl::BLEND_SRC_ALPHA) as gl::types::GLenum,
+                         get_integer(gl::BLEND_DST_ALPHA) as gl::types::GLenum),
+            blend_color,
+            depth_func: get_integer(gl::DEPTH_FUNC) as gl::types::GLenum,
+            depth_mask: get_integer(gl::DEPTH_WRITEMASK) != 0,
+            color_mask,
+            cull_face: get_integer(gl::CULL_FACE_MODE) as gl::types::GLenum,
+            viewport: get_integer_4(gl::VIEWPORT),
+            scissor: get_integer_4(gl::SCISSOR_BOX),
+        };
+
+        update_cache(&mut ctxt, &snapshot, false);
+        snapshot
+    }
+
+    /// Sets the OpenGL state back to a snapshot returned by `save_state`.
+    ///
+    /// Glium's cache of the OpenGL state is updated accordingly, so glium can keep drawing
+    /// afterwards.
+    pub fn restore_state(&self, snapshot: &GlStateSnapshot) {
+        let mut ctxt = self.make_current();
+
+        unsafe {
+            if let Some(program) = snapshot.program {
+                ctxt.gl.UseProgram(program);
+            }
+
+            if let Some(vertex_array) = snapshot.vertex_array {
+                if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                   ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                   ctxt.extensions.gl_arb_vertex_array_object
+                {
+                    ctxt.gl.BindVertexArray(vertex_array);
+                } else if ctxt.extensions.gl_oes_vertex_array_object {
+                    ctxt.gl.BindVertexArrayOES(vertex_array);
+                } else {
+                    ctxt.gl.BindVertexArrayAPPLE(vertex_array);
+                }
+            }
+
+            // the element array buffer is part of the vertex array object, and core profiles
+            // don't have a default vertex array object
+            if snapshot.vertex_array != Some(0) {
+                ctxt.gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, snapshot.element_array_buffer);
+            }
+            ctxt.gl.BindBuffer(gl::ARRAY_BUFFER, snapshot.array_buffer);
+
+            if has_separate_framebuffers(&ctxt) {
+                ctxt.gl.BindFramebuffer(gl::DRAW_FRAMEBUFFER, snapshot.draw_framebuffer);
+                ctxt.gl.BindFramebuffer(gl::READ_FRAMEBUFFER, snapshot.read_framebuffer);
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+                ctxt.gl.BindFramebuffer(gl::FRAMEBUFFER, snapshot.draw_framebuffer);
+            } else if ctxt.extensions.gl_ext_framebuffer_object {
+                ctxt.gl.BindFramebufferEXT(gl::FRAMEBUFFER_EXT, snapshot.draw_framebuffer);
+            }
+
+            ctxt.gl.ActiveTexture(gl::TEXTURE0 + snapshot.active_texture);
+            for &(target, texture) in &snapshot.textures {
+                ctxt.gl.BindTexture(target, texture);
+            }
+
+            for &(name, enabled) in &[(gl::BLEND, snapshot.enabled_blend),
+                                      (gl::CULL_FACE, snapshot.enabled_cull_face),
+                                      (gl::DEPTH_TEST, snapshot.enabled_depth_test),
+                                      (gl::SCISSOR_TEST, snapshot.enabled_scissor_test),
+                                      (gl::STENCIL_TEST, snapshot.enabled_stencil_test)]
+            {
+                if enabled {
+                    ctxt.gl.Enable(name);
+                } else {
+                    ctxt.gl.Disable(name);
+                }
+            }
+
+            ctxt.gl.BlendEquationSeparate(snapshot.blend_equation.0, snapshot.blend_equation.1);
+            ctxt.gl.BlendFuncSeparate(snapshot.blend_func.0, snapshot.blend_func.1,
+                                      snapshot.blend_func.2, snapshot.blend_func.3);
+            ctxt.gl.BlendColor(snapshot.blend_color.0, snapshot.blend_color.1,
+                               snapshot.blend_color.2, snapshot.blend_color.3);
+            ctxt.gl.DepthFunc(snapshot.depth_func);
+            ctxt.gl.DepthMask(if snapshot.depth_mask { gl::TRUE } else { gl::FALSE });
+            ctxt.gl.ColorMask(snapshot.color_mask.0, snapshot.color_mask.1,
+                              snapshot.color_mask.2, snapshot.color_mask.3);
+            ctxt.gl.CullFace(snapshot.cull_face);
+            ctxt.gl.Viewport(snapshot.viewport.0, snapshot.viewport.1,
+                             snapshot.viewport.2, snapshot.viewport.3);
+            ctxt.gl.Scissor(snapshot.scissor.0, snapshot.scissor.1,
+                            snapshot.scissor.2, snapshot.scissor.3);
+        }
+
+        update_cache(&mut ctxt, snapshot, true);
+    }
+}
+
+/// Returns true if the draw and read framebuffers can be bound separately.
+#[inline]
+fn has_separate_framebuffers(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_framebuffer_object
+}
+
+/// Returns the texture targets supported by the backend, with the state that contains the
+/// texture bound to them.
+fn texture_targets(ctxt: &CommandContext<'_>) -> Vec<(gl::types::GLenum, gl::types::GLenum)> {
+    let mut targets = vec![(gl::TEXTURE_2D, gl::TEXTURE_BINDING_2D)];
+
+    if ctxt.version.0 == Api::Gl {
+        targets.push((gl::TEXTURE_1D, gl::TEXTURE_BINDING_1D));
+    }
+    if ctxt.version >= &Version(Api::Gl, 1, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        targets.push((gl::TEXTURE_3D, gl::TEXTURE_BINDING_3D));
+    }
+    if ctxt.version >= &Version(Api::Gl, 1, 3) || ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        targets.push((gl::TEXTURE_CUBE_MAP, gl::TEXTURE_BINDING_CUBE_MAP));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 0) {
+        targets.push((gl::TEXTURE_1D_ARRAY, gl::TEXTURE_BINDING_1D_ARRAY));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        targets.push((gl::TEXTURE_2D_ARRAY, gl::TEXTURE_BINDING_2D_ARRAY));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 1) {
+        targets.push((gl::TEXTURE_RECTANGLE, gl::TEXTURE_BINDING_RECTANGLE));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 1) || ctxt.version >= &Version(Api::GlEs, 3, 2) {
+        targets.push((gl::TEXTURE_BUFFER, gl::TEXTURE_BINDING_BUFFER));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.version >= &Version(Api::GlEs, 3, 1) {
+        targets.push((gl::TEXTURE_2D_MULTISAMPLE, gl::TEXTURE_BINDING_2D_MULTISAMPLE));
+    }
+    if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.version >= &Version(Api::GlEs, 3, 2) {
+        targets.push((gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
+                      gl::TEXTURE_BINDING_2D_MULTISAMPLE_ARRAY));
+    }
+    if ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.version >= &Version(Api::GlEs, 3, 2) {
+        targets.push((gl::TEXTURE_CUBE_MAP_ARRAY, gl::TEXTURE_BINDING_CUBE_MAP_ARRAY));
+    }
+
+    targets
+}
+
+/// Writes the content of the snapshot to glium's cache of the OpenGL state.
+///
+/// `restored` must be true if the snapshot has just been applied with `restore_state`.
+fn update_cache(ctxt: &mut CommandContext<'_>, snapshot: &GlStateSnapshot, restored: bool) {
+    let state = &mut *ctxt.state;
+
+    if let Some(program) = snapshot.program {
+        state.program = Handle::Id(program);
+    }
+    if let Some(vertex_array) = snapshot.vertex_array {
+        state.vertex_array = vertex_array;
+    }
+    state.array_buffer_binding = snapshot.array_buffer;
+    state.draw_framebuffer = snapshot.draw_framebuffer;
+    state.read_framebuffer = snapshot.read_framebuffer;
+
+    let active_texture = snapshot.active_texture as usize;
+    while state.texture_units.len() <= active_texture {
+        state.texture_units.push(TextureUnitState { texture: 0, sampler: 0 });
+    }
+    state.active_texture = snapshot.active_texture;
+    // glium only caches one texture per unit, so the cache is only kept if a single target has
+    // a texture bound, and otherwise reset so that glium binds its textures again
+    let mut bound = snapshot.textures.iter().map(|&(_, texture)| texture)
+                                            .filter(|&texture| texture != 0);
+    state.texture_units[active_texture].texture = match (bound.next(), bound.next()) {
+        (Some(texture), None) => texture,
+        _ => 0,
+    };
+
+    // `glEnable(GL_BLEND)`, `glBlendEquationSeparate` and `glBlendFuncSeparate` apply to all
+    // the draw buffers, but the queries only return the state of the first one
+    if restored || !state.indexed_blend {
+        state.indexed_blend = false;
+        state.enabled_blend = snapshot.enabled_blend;
+        state.blend_equation = snapshot.blend_equation;
+        state.blend_func = snapshot.blend_func;
+    }
+
+    state.enabled_cull_face = snapshot.enabled_cull_face;
+    state.enabled_depth_test = snapshot.enabled_depth_test;
+    state.enabled_scissor_test = snapshot.enabled_scissor_test;
+    state.enabled_stencil_test = snapshot.enabled_stencil_test;
+    state.blend_color = snapshot.blend_color;
+    state.depth_func = snapshot.depth_func;
+    state.depth_mask = snapshot.depth_mask;
+    state.color_mask = snapshot.color_mask;
+    state.cull_face = snapshot.cull_face;
+    state.viewport = Some(snapshot.viewport);
+    state.scissor = Some(snapshot.scissor);
+}
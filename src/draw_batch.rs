@@ -0,0 +1,216 @@
+/*!
+Batches of draw commands that are submitted together.
+
+When drawing a scene made of lots of objects, the order in which the draw commands are issued
+has an impact on performances. Switching programs, blending modes or textures between each
+draw command forces the OpenGL implementation to do a lot of work that could have been avoided.
+
+A `DrawBatch` collects draw commands and, when submitted, reorders them so that commands which
+share the same program, blending and first texture are executed next to each other. Glium's
+state cache then skips the redundant state changes.
+
+```no_run
+# use glium::Surface;
+# use glium::draw_batch::DrawBatch;
+# fn example<S: Surface>(surface: &mut S, vb: glium::VertexBuffer<[f32; 2]>,
+#                        ib: glium::IndexBuffer<u16>, program: glium::Program) {
+let mut batch = DrawBatch::new();
+batch.push(&vb, &ib, &program, glium::uniforms::EmptyUniforms, Default::default());
+batch.push(&vb, &ib, &program, glium::uniforms::EmptyUniforms, Default::default());
+batch.submit(surface).unwrap();
+# }
+```
+
+Sorting is stable: commands that share the same state are executed in the order in which they
+were pushed. If the order of all the commands matters, for example when drawing transparent
+objects from back to front, use `DrawBatch::ordered()` to disable sorting entirely.
+
+*/
+use std::mem;
+use std::vec::IntoIter;
+
+use crate::draw_parameters::{Blend, DrawParameters};
+use crate::index::IndicesSource;
+use crate::uniforms::{Uniforms, UniformValue};
+use crate::vertex::{MultiVerticesSource, VerticesSource};
+
+use crate::gl;
+use crate::DrawError;
+use crate::GlObject;
+use crate::Program;
+use crate::Surface;
+
+/// A list of draw commands that are submitted together to a surface.
+///
+/// All the commands of a batch must use the same type of uniforms. If you need to mix different
+/// kinds of uniforms, you can use `DynamicUniforms`.
+pub struct DrawBatch<'a, U> where U: Uniforms {
+    items: Vec<DrawItem<'a, U>>,
+    blends: Vec<Blend>,
+    sorted: bool,
+}
+
+struct DrawItem<'a, U> where U: Uniforms {
+    key: SortKey,
+    vertices: Vec<VerticesSource<'a>>,
+    indices: IndicesSource<'a>,
+    program: &'a Program,
+    uniforms: U,
+    draw_parameters: DrawParameters<'a>,
+}
+
+/// Key used to group draw commands together.
+///
+/// Contains the address of the program, the index of the blending mode in the list of blending
+/// modes of the batch, and the ID of the first texture found in the uniforms.
+type SortKey = (usize, usize, gl::types::GLuint);
+
+impl<'a, U> DrawBatch<'a, U> where U: Uniforms {
+    /// Builds a new empty batch whose commands are sorted by state when submitted.
+    #[inline]
+    pub fn new() -> DrawBatch<'a, U> {
+        DrawBatch {
+            items: Vec::new(),
+            blends: Vec::new(),
+            sorted: true,
+        }
+    }
+
+    /// Builds a new empty batch whose commands are submitted in the order in which they
+    /// were pushed.
+    ///
+    /// Use this for passes that depend on the order of the draw commands, like transparent
+    /// objects.
+    #[inline]
+    pub fn ordered() -> DrawBatch<'a, U> {
+        DrawBatch {
+            items: Vec::new(),
+            blends: Vec::new(),
+            sorted: false,
+        }
+    }
+
+    /// Enables or disables sorting the commands when the batch is submitted.
+    #[inline]
+    pub fn set_sorting(&mut self, sorted: bool) {
+        self.sorted = sorted;
+    }
+
+    /// Returns true if the commands will be sorted when the batch is submitted.
+    #[inline]
+    pub fn is_sorting(&self) -> bool {
+        self.sorted
+    }
+
+    /// Returns the number of draw commands in the batch.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if the batch doesn't contain any draw command.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes all the draw commands from the batch.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.blends.clear();
+    }
+
+    /// Adds a draw command to the batch.
+    ///
+    /// The parameters are the same as `Surface::draw`. Nothing is drawn until the batch
+    /// is submitted.
+    pub fn push<V, I>(&mut self, vertex_buffer: V, index_buffer: I, program: &'a Program,
+                      uniforms: U, draw_parameters: DrawParameters<'a>)
+                      where V: MultiVerticesSource<'a>, I: Into<IndicesSource<'a>>
+    {
+        let blend_index = match self.blends.iter().position(|b| *b == draw_parameters.blend) {
+            Some(index) => index,
+            None => {
+                self.blends.push(draw_parameters.blend);
+                self.blends.len() - 1
+            },
+        };
+
+        let key = (program as *const Program as usize, blend_index, first_texture_id(&uniforms));
+
+        self.items.push(DrawItem {
+            key,
+            vertices: vertex_buffer.iter().collect(),
+            indices: index_buffer.into(),
+            program,
+            uniforms,
+            draw_parameters,
+        });
+    }
+
+    /// Draws all the commands of the batch on the surface, then empties the batch.
+    ///
+    /// If a draw command returns an error, the remaining commands are discarded and
+    /// the error is returned.
+    pub fn submit<S>(&mut self, surface: &mut S) -> Result<(), DrawError> where S: Surface {
+        if self.sorted {
+            // `sort_by_key` is stable, which keeps the order of commands that share a state
+            self.items.sort_by_key(|item| item.key);
+        }
+
+        self.blends.clear();
+
+        for item in mem::take(&mut self.items) {
+            surface.draw(BatchVertices(item.vertices), item.indices, item.program,
+                         &item.uniforms, &item.draw_parameters)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, U> Default for DrawBatch<'a, U> where U: Uniforms {
+    #[inline]
+    fn default() -> DrawBatch<'a, U> {
+        DrawBatch::new()
+    }
+}
+
+/// List of vertex sources stored in a batch.
+struct BatchVertices<'a>(Vec<VerticesSource<'a>>);
+
+impl<'a> MultiVerticesSource<'a> for BatchVertices<'a> {
+    type Iterator = IntoIter<VerticesSource<'a>>;
+
+    #[inline]
+    fn iter(self) -> IntoIter<VerticesSource<'a>> {
+        self.0.into_iter()
+    }
+}
+
+/// Returns the ID of the first texture found in the uniforms, or 0 if there is none.
+fn first_texture_id<U>(uniforms: &U) -> gl::types::GLuint where U: Uniforms {
+    let mut id = 0;
+
+    uniforms.visit_values(|_, value| {
+        if id != 0 {
+            return;
+        }
+
+        id = match value {
+            UniformValue::Texture2d(tex, _) => tex.get_id(),
+            UniformValue::SrgbTexture2d(tex, _) => tex.get_id(),
+            UniformValue::CompressedTexture2d(tex, _) => tex.get_id(),
+            UniformValue::IntegralTexture2d(tex, _) => tex.get_id(),
+            UniformValue::UnsignedTexture2d(tex, _) => tex.get_id(),
+            UniformValue::DepthTexture2d(tex, _) => tex.get_id(),
+            UniformValue::Texture2dArray(tex, _) => tex.get_id(),
+            UniformValue::SrgbTexture2dArray(tex, _) => tex.get_id(),
+            UniformValue::Cubemap(tex, _) => tex.get_id(),
+            _ => 0,
+        };
+    });
+
+    id
+}
@@ -12,7 +12,9 @@ use crate::gl;
 /// you to choose how it should merge the two.
 ///
 /// If you want to add transparent objects one over another, use
-/// `Blend::alpha_blending()`.
+/// `Blend::alpha_blending()`, or `Blend::premultiplied_alpha()` if the colors written by
+/// your fragment shader are already multiplied by their alpha. If you want to add light
+/// on top of the scene, use `Blend::additive()`.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Blend {
     /// The blending function for color channels.
@@ -38,6 +40,49 @@ impl Blend {
             constant_value: (0.0, 0.0, 0.0, 0.0)
         }
     }
+
+    /// Returns a blend effect to add transparent objects whose colors are premultiplied by
+    /// their alpha over others.
+    ///
+    /// This is what you should use for text, UI and textures with premultiplied alpha.
+    /// Contrary to `alpha_blending`, the alpha of the destination stays correct when drawing
+    /// over transparent pixels, and linear filtering of the textures doesn't produce dark
+    /// fringes around the edges of the objects.
+    ///
+    /// The source color is multiplied by `1` and the destination color by
+    /// `1 - source alpha`, for both the color and alpha channels.
+    pub fn premultiplied_alpha() -> Blend {
+        Blend {
+            color: BlendingFunction::Addition {
+                source: LinearBlendingFactor::One,
+                destination: LinearBlendingFactor::OneMinusSourceAlpha,
+            },
+            alpha: BlendingFunction::Addition {
+                source: LinearBlendingFactor::One,
+                destination: LinearBlendingFactor::OneMinusSourceAlpha,
+            },
+            constant_value: (0.0, 0.0, 0.0, 0.0)
+        }
+    }
+
+    /// Returns a blend effect that adds the source color to the destination color.
+    ///
+    /// This is what you should use for lights, particles and glow effects, where drawing
+    /// an object can only make the framebuffer brighter. The order in which the objects are
+    /// drawn doesn't matter.
+    pub fn additive() -> Blend {
+        Blend {
+            color: BlendingFunction::Addition {
+                source: LinearBlendingFactor::One,
+                destination: LinearBlendingFactor::One,
+            },
+            alpha: BlendingFunction::Addition {
+                source: LinearBlendingFactor::One,
+                destination: LinearBlendingFactor::One,
+            },
+            constant_value: (0.0, 0.0, 0.0, 0.0)
+        }
+    }
 }
 
 impl Default for Blend {
@@ -66,6 +111,9 @@ pub enum BlendingFunction {
     ///
     /// For example writing `(0.5, 0.9, 0.4, 0.2)` over `(0.9, 0.1, 0.4, 0.3)` will
     /// result in `(0.5, 0.1, 0.4, 0.2)`.
+    ///
+    /// This corresponds to `GL_MIN`. No blending factor is applied to the source or the
+    /// destination.
     Min,
 
     /// For each individual component (red, green, blue, and alpha), the maximum value is chosen
@@ -73,6 +121,9 @@ pub enum BlendingFunction {
     ///
     /// For example writing `(0.5, 0.9, 0.4, 0.2)` over `(0.9, 0.1, 0.4, 0.3)` will
     /// result in `(0.9, 0.9, 0.4, 0.3)`.
+    ///
+    /// This corresponds to `GL_MAX`. No blending factor is applied to the source or the
+    /// destination.
     Max,
 
     /// For each individual component (red, green, blue, and alpha), a weighted addition
@@ -223,59 +274,103 @@ impl LinearBlendingFactor {
     }
 }
 
-pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(), DrawError> {
-    #[inline(always)]
-    fn blend_eq(ctxt: &mut CommandContext<'_>, blending_function: BlendingFunction)
-                -> Result<gl::types::GLenum, DrawError>
-    {
-        match blending_function {
-            BlendingFunction::AlwaysReplace |
-            BlendingFunction::Addition { .. } => Ok(gl::FUNC_ADD),
-            BlendingFunction::Subtraction { .. } => Ok(gl::FUNC_SUBTRACT),
-            BlendingFunction::ReverseSubtraction { .. } => Ok(gl::FUNC_REVERSE_SUBTRACT),
-
-            BlendingFunction::Min => {
-                if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
-                   !ctxt.extensions.gl_ext_blend_minmax
-                {
-                    Err(DrawError::BlendingParameterNotSupported)
-                } else {
-                    Ok(gl::MIN)
-                }
-            },
+#[inline(always)]
+fn blend_eq(ctxt: &mut CommandContext<'_>, blending_function: BlendingFunction)
+            -> Result<gl::types::GLenum, DrawError>
+{
+    match blending_function {
+        BlendingFunction::AlwaysReplace |
+        BlendingFunction::Addition { .. } => Ok(gl::FUNC_ADD),
+        BlendingFunction::Subtraction { .. } => Ok(gl::FUNC_SUBTRACT),
+        BlendingFunction::ReverseSubtraction { .. } => Ok(gl::FUNC_REVERSE_SUBTRACT),
+
+        BlendingFunction::Min => {
+            if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
+               !ctxt.extensions.gl_ext_blend_minmax
+            {
+                Err(DrawError::BlendingParameterNotSupported)
+            } else {
+                Ok(gl::MIN)
+            }
+        },
+
+        BlendingFunction::Max => {
+            if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
+               !ctxt.extensions.gl_ext_blend_minmax
+            {
+                Err(DrawError::BlendingParameterNotSupported)
+            } else {
+                Ok(gl::MAX)
+            }
+        },
+    }
+}
 
-            BlendingFunction::Max => {
-                if ctxt.version <= &Version(Api::GlEs, 2, 0) &&
-                   !ctxt.extensions.gl_ext_blend_minmax
-                {
-                    Err(DrawError::BlendingParameterNotSupported)
-                } else {
-                    Ok(gl::MAX)
-                }
-            },
-        }
+#[inline(always)]
+fn blending_factors(blending_function: BlendingFunction)
+                    -> Option<(LinearBlendingFactor, LinearBlendingFactor)>
+{
+    match blending_function {
+        BlendingFunction::AlwaysReplace |
+        BlendingFunction::Min |
+        BlendingFunction::Max => None,
+        BlendingFunction::Addition { source, destination } =>
+            Some((source, destination)),
+        BlendingFunction::Subtraction { source, destination } =>
+            Some((source, destination)),
+        BlendingFunction::ReverseSubtraction { source, destination } =>
+            Some((source, destination)),
     }
+}
 
-    #[inline(always)]
-    fn blending_factors(blending_function: BlendingFunction)
-                        -> Option<(LinearBlendingFactor, LinearBlendingFactor)>
-    {
-        match blending_function {
-            BlendingFunction::AlwaysReplace |
-            BlendingFunction::Min |
-            BlendingFunction::Max => None,
-            BlendingFunction::Addition { source, destination } =>
-                Some((source, destination)),
-            BlendingFunction::Subtraction { source, destination } =>
-                Some((source, destination)),
-            BlendingFunction::ReverseSubtraction { source, destination } =>
-                Some((source, destination)),
-        }
+/// Returns true if the factor reads the value passed to `glBlendColor`.
+#[inline(always)]
+fn uses_constant_value(factor: LinearBlendingFactor) -> bool {
+    matches!(factor, LinearBlendingFactor::ConstantColor |
+                     LinearBlendingFactor::OneMinusConstantColor |
+                     LinearBlendingFactor::ConstantAlpha |
+                     LinearBlendingFactor::OneMinusConstantAlpha)
+}
+
+/// Returns the color and alpha equations and the four blending factors of a blend effect.
+fn blend_parameters(ctxt: &mut CommandContext<'_>, blend: &Blend)
+                    -> Result<((gl::types::GLenum, gl::types::GLenum),
+                               [LinearBlendingFactor; 4]), DrawError>
+{
+    let equations = (blend_eq(ctxt, blend.color)?, blend_eq(ctxt, blend.alpha)?);
+
+    // Map to dummy factors if the blending equation does not use the factors.
+    let (color_factor_src, color_factor_dst) = blending_factors(blend.color)
+        .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
+    let (alpha_factor_src, alpha_factor_dst) = blending_factors(blend.alpha)
+        .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
+
+    Ok((equations, [color_factor_src, color_factor_dst, alpha_factor_src, alpha_factor_dst]))
+}
+
+#[inline]
+fn is_always_replace(blend: &Blend) -> bool {
+    blend.color == BlendingFunction::AlwaysReplace && blend.alpha == BlendingFunction::AlwaysReplace
+}
+
+pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend, blend_per_buffer: Option<&[Blend]>)
+                     -> Result<(), DrawError>
+{
+    if let Some(blends) = blend_per_buffer {
+        return sync_indexed_blending(ctxt, blends);
     }
 
-    if let (BlendingFunction::AlwaysReplace, BlendingFunction::AlwaysReplace) =
-           (blend.color, blend.alpha)
-    {
+    if ctxt.state.indexed_blend {
+        // The blending state of some draw buffers has been changed individually, which means
+        // that the cached values don't apply to all the draw buffers anymore. The cached flag is
+        // set to the opposite of the desired value so that `GL_BLEND` is set below.
+        ctxt.state.indexed_blend = false;
+        ctxt.state.enabled_blend = is_always_replace(&blend);
+        ctxt.state.blend_equation = (0, 0);
+        ctxt.state.blend_func = (0, 0, 0, 0);
+    }
+
+    if is_always_replace(&blend) {
         // Both color and alpha always replace. This equals no blending.
         if ctxt.state.enabled_blend {
             unsafe { ctxt.gl.Disable(gl::BLEND); }
@@ -288,46 +383,26 @@ pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(),
             ctxt.state.enabled_blend = true;
         }
 
-        let (color_eq, alpha_eq) = (blend_eq(ctxt, blend.color)?,
-                                    blend_eq(ctxt, blend.alpha)?);
+        let ((color_eq, alpha_eq), factors) = blend_parameters(ctxt, &blend)?;
         if ctxt.state.blend_equation != (color_eq, alpha_eq) {
             unsafe { ctxt.gl.BlendEquationSeparate(color_eq, alpha_eq); }
             ctxt.state.blend_equation = (color_eq, alpha_eq);
         }
 
-        // Map to dummy factors if the blending equation does not use the factors.
-        let (color_factor_src, color_factor_dst) = blending_factors(blend.color)
-            .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
-        let (alpha_factor_src, alpha_factor_dst) = blending_factors(blend.alpha)
-            .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
-
         // Updating the blending color if necessary.
-        if (color_factor_src == LinearBlendingFactor::ConstantColor ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_dst == LinearBlendingFactor::ConstantColor ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha) && ctxt.state.blend_color != blend.constant_value {
+        if factors.iter().any(|&f| uses_constant_value(f)) &&
+           ctxt.state.blend_color != blend.constant_value
+        {
             let (r, g, b, a) = blend.constant_value;
             unsafe { ctxt.gl.BlendColor(r, g, b, a); }
             ctxt.state.blend_color = blend.constant_value;
         }
 
         // Updating the blending function if necessary.
-        let color_factor_src = color_factor_src.to_glenum();
-        let color_factor_dst = color_factor_dst.to_glenum();
-        let alpha_factor_src = alpha_factor_src.to_glenum();
-        let alpha_factor_dst = alpha_factor_dst.to_glenum();
+        let color_factor_src = factors[0].to_glenum();
+        let color_factor_dst = factors[1].to_glenum();
+        let alpha_factor_src = factors[2].to_glenum();
+        let alpha_factor_dst = factors[3].to_glenum();
         if ctxt.state.blend_func != (color_factor_src, color_factor_dst,
                                      alpha_factor_src, alpha_factor_dst)
         {
@@ -343,3 +418,63 @@ pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(),
 
     Ok(())
 }
+
+/// Sets the blending equations and functions of each draw buffer individually.
+fn sync_indexed_blending(ctxt: &mut CommandContext<'_>, blends: &[Blend]) -> Result<(), DrawError> {
+    let core = ctxt.version >= &Version(Api::Gl, 4, 0) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 2);
+    let arb = ctxt.version >= &Version(Api::Gl, 3, 0) && ctxt.extensions.gl_arb_draw_buffers_blend;
+
+    if !core && !arb {
+        return Err(DrawError::IndexedBlendingNotSupported);
+    }
+
+    if blends.len() > ctxt.capabilities.max_draw_buffers as usize {
+        return Err(DrawError::IndexedBlendingNotSupported);
+    }
+
+    // Checking the parameters before modifying any state.
+    let mut parameters = Vec::with_capacity(blends.len());
+    for blend in blends {
+        parameters.push(blend_parameters(ctxt, blend)?);
+    }
+
+    ctxt.state.indexed_blend = true;
+
+    for (buffer, (blend, ((color_eq, alpha_eq), factors))) in
+        blends.iter().zip(parameters).enumerate()
+    {
+        let buffer = buffer as gl::types::GLuint;
+
+        if is_always_replace(blend) {
+            unsafe { ctxt.gl.Disablei(gl::BLEND, buffer); }
+            continue;
+        }
+
+        if factors.iter().any(|&f| uses_constant_value(f)) &&
+           ctxt.state.blend_color != blend.constant_value
+        {
+            let (r, g, b, a) = blend.constant_value;
+            unsafe { ctxt.gl.BlendColor(r, g, b, a); }
+            ctxt.state.blend_color = blend.constant_value;
+        }
+
+        unsafe {
+            ctxt.gl.Enablei(gl::BLEND, buffer);
+
+            if core {
+                ctxt.gl.BlendEquationSeparatei(buffer, color_eq, alpha_eq);
+                ctxt.gl.BlendFuncSeparatei(buffer, factors[0].to_glenum(),
+                                           factors[1].to_glenum(), factors[2].to_glenum(),
+                                           factors[3].to_glenum());
+            } else {
+                ctxt.gl.BlendEquationSeparateiARB(buffer, color_eq, alpha_eq);
+                ctxt.gl.BlendFuncSeparateiARB(buffer, factors[0].to_glenum(),
+                                              factors[1].to_glenum(), factors[2].to_glenum(),
+                                              factors[3].to_glenum());
+            }
+        }
+    }
+
+    Ok(())
+}
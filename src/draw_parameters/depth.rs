@@ -163,6 +163,27 @@ pub enum DepthClamp {
     ClampFar,
 }
 
+/// Calls `glDepthRange` on desktop OpenGL or `glDepthRangef` on OpenGL ES, if the range is
+/// different from the current one.
+pub fn set_depth_range(ctxt: &mut CommandContext<'_>, range: (f32, f32)) {
+    if range == ctxt.state.depth_range {
+        return;
+    }
+
+    // TODO: WebGL requires range.1 > range.0
+    unsafe {
+        if ctxt.version >= &Version(Api::Gl, 1, 0) {
+            ctxt.gl.DepthRange(range.0 as f64, range.1 as f64);
+        } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+            ctxt.gl.DepthRangef(range.0, range.1);
+        } else {
+            unreachable!();
+        }
+    }
+
+    ctxt.state.depth_range = range;
+}
+
 pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), DrawError> {
     // depth clamp
     {
@@ -250,13 +271,7 @@ pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), Dr
         return Err(DrawError::InvalidDepthRange);
     }
 
-    if depth.range != ctxt.state.depth_range {
-        // TODO: WebGL requires depth.range.1 > depth.range.0
-        unsafe {
-            ctxt.gl.DepthRange(depth.range.0 as f64, depth.range.1 as f64);
-        }
-        ctxt.state.depth_range = depth.range;
-    }
+    set_depth_range(ctxt, depth.range);
 
     if depth.test == DepthTest::Overwrite && !depth.write {
         // simply disabling GL_DEPTH_TEST
@@ -73,8 +73,10 @@ use crate::version::Api;
 use crate::index::PrimitiveType;
 
 use crate::QueryExt;
+use crate::TransformFeedbackSessionExt;
 use crate::CapabilitiesSource;
 use crate::DrawError;
+use crate::GlObject;
 use crate::Rect;
 use crate::ToGlEnum;
 use crate::vertex::TransformFeedbackSession;
@@ -84,10 +86,12 @@ use std::ops::Range;
 pub use self::blend::{Blend, BlendingFunction, LinearBlendingFactor};
 pub use self::depth::{Depth, DepthTest, DepthClamp};
 pub use self::query::{QueryCreationError};
-pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
+pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, TimestampQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
 pub use self::stencil::{StencilTest, StencilOperation, Stencil};
 
+pub(crate) use self::depth::set_depth_range;
+
 mod blend;
 mod depth;
 mod query;
@@ -257,6 +261,16 @@ pub struct DrawParameters<'a> {
     /// being written.
     pub blend: Blend,
 
+    /// If `Some`, overrides `blend` with a different effect for each draw buffer.
+    ///
+    /// The first element applies to the first draw buffer, the second element to the second
+    /// draw buffer, and so on. This allows for example to add colors in one attachment while
+    /// keeping the minimum value in another one. Since there is only one constant blending
+    /// color, all the effects that use `constant_value` must use the same value.
+    ///
+    /// This requires OpenGL 4.0, OpenGL ES 3.2 or the `GL_ARB_draw_buffers_blend` extension.
+    pub blend_per_buffer: Option<&'a [Blend]>,
+
     /// Allows you to disable some color components.
     ///
     /// This affects all attachments to the framebuffer. It's at the same level as the
@@ -297,6 +311,14 @@ pub struct DrawParameters<'a> {
     /// See the documentation of `PolygonMode` for more infos.
     pub polygon_mode: PolygonMode,
 
+    /// How to render the polygons that are facing away from the camera. The default value
+    /// is `None`, which means that `polygon_mode` is used for both front and back faces.
+    ///
+    /// Using a different mode for back faces is only possible with OpenGL compatibility
+    /// contexts. On other contexts, `polygon_mode` is used for both faces and a warning is sent
+    /// to the debug callback.
+    pub back_polygon_mode: Option<PolygonMode>,
+
     /// Whether multisample antialiasing (MSAA) should be used. Default value is `true`.
     ///
     /// Note that you will need to set the appropriate option when creating the window.
@@ -309,6 +331,17 @@ pub struct DrawParameters<'a> {
     /// Dithering will smoothen the transition between colors in your color buffer.
     pub dithering: bool,
 
+    /// Whether conservative rasterization is enabled. Default value is `false`.
+    ///
+    /// With conservative rasterization, every pixel that is even partially covered by a
+    /// primitive is rasterized. This is useful for voxelization or for building occlusion
+    /// buffers.
+    ///
+    /// This requires the `GL_NV_conservative_raster` extension. Check the
+    /// `conservative_rasterization` field of the capabilities. If it is not supported, drawing
+    /// will return a `ConservativeRasterizationNotSupported` error.
+    pub conservative_rasterization: bool,
+
     /// The viewport to use when drawing.
     ///
     /// The X and Y positions of your vertices are mapped to the viewport so that `(-1, -1)`
@@ -317,15 +350,29 @@ pub struct DrawParameters<'a> {
     ///
     /// You can specify a viewport greater than the target if you want to stretch the image.
     ///
-    /// `None` means "use the whole surface".
+    /// `None` means "use the whole surface", unless `inherit_viewport` is `true`.
     pub viewport: Option<Rect>,
 
+    /// If `true` and `viewport` is `None`, the viewport that is currently set is left untouched
+    /// instead of being reset to the whole surface. Default value is `false`.
+    ///
+    /// This is useful if you manage the viewport yourself, for example by drawing with a
+    /// viewport once then reusing it for many draws in a chain of framebuffers.
+    pub inherit_viewport: bool,
+
     /// If specified, only pixels in this rect will be displayed. Default is `None`.
     ///
     /// This is different from a viewport. The image will stretch to fill the viewport, but
     /// not the scissor box.
     pub scissor: Option<Rect>,
 
+    /// If `true` and `scissor` is `None`, the scissor box is set to the viewport. Default
+    /// value is `false`.
+    ///
+    /// This avoids having to pass the same rect as both `viewport` and `scissor`. If `viewport`
+    /// is `None`, the whole surface is used.
+    pub scissor_to_viewport: bool,
+
     /// If `false`, the pipeline will stop after the primitives generation stage. The default
     /// value is `true`.
     ///
@@ -355,6 +402,9 @@ pub struct DrawParameters<'a> {
 
     /// If set, the commands will only be executed if the specified query contains `true` or
     /// a number different than 0.
+    ///
+    /// Drawing returns `ConditionalRenderingNotSupported` if the backend supports neither
+    /// OpenGL 3.0 nor `GL_NV_conditional_render`.
     pub condition: Option<ConditionalRendering<'a>>,
 
     /// If set, then the generated primitives will be written back to a buffer.
@@ -396,6 +446,17 @@ pub struct DrawParameters<'a> {
     /// of type `FixedIndexRestartingNotSupported` will be returned.
     pub primitive_restart_index: bool,
 
+    /// If `Some`, will split the index buffer (if any is used in the draw call) at the given
+    /// index and start a new primitive of the same type. This uses `GL_PRIMITIVE_RESTART` and
+    /// is supported on OpenGL 3.1 and above, but not on OpenGL ES.
+    ///
+    /// The index must fit in the type of the indices of the index buffer, otherwise an error
+    /// of type `PrimitiveRestartIndexOutOfRange` is returned. This is ignored if
+    /// `primitive_restart_index` is `true`.
+    ///
+    /// The default value is `None`.
+    pub custom_primitive_restart_index: Option<u32>,
+
     /// If enabled, shifts the depth value of towards of away from the camera. This is useful for
     /// drawing decals and wireframes, for example.
     pub polygon_offset: PolygonOffset,
@@ -405,6 +466,30 @@ pub struct DrawParameters<'a> {
 
     /// Clip control depth mode. The default value is `NegativeOneToOne`.
     pub clip_control_depth: ClipControlDepth,
+
+    /// Tessellation levels used when drawing patches with a program that has a tessellation
+    /// evaluation shader but no tessellation control shader. The default value has all the
+    /// levels set to `1.0`.
+    ///
+    /// The number of vertices per patch is given by `PrimitiveType::Patches`.
+    ///
+    /// Changing the default levels is only possible with desktop OpenGL 4.0 or
+    /// `GL_ARB_tessellation_shader`. Otherwise a `TessellationNotSupported` error is returned.
+    pub default_tessellation_levels: TessellationLevels,
+
+    /// Overrides whether the colors written by the fragment shader are converted from linear
+    /// space to sRGB when the color attachment has an sRGB format (`GL_FRAMEBUFFER_SRGB`).
+    ///
+    /// If `None`, the program decides: the conversion is done unless the program was created
+    /// with `outputs_srgb` set to `true`. Attachments that don't have an sRGB format are never
+    /// converted, and in debug builds drawing on one with `Some(true)` emits a low-severity
+    /// debug message.
+    ///
+    /// This is ignored if the backend supports neither OpenGL 3.0, `GL_ARB_framebuffer_sRGB`,
+    /// `GL_EXT_framebuffer_sRGB` nor `GL_EXT_sRGB_write_control`.
+    ///
+    /// The default value is `None`.
+    pub srgb: Option<bool>,
 }
 
 /// Condition whether to render or not.
@@ -472,6 +557,25 @@ impl Default for PolygonOffset {
     }
 }
 
+/// Default tessellation levels of a patch.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TessellationLevels {
+    /// Value of `gl_TessLevelOuter` in the tessellation evaluation shader.
+    pub outer: [f32; 4],
+    /// Value of `gl_TessLevelInner` in the tessellation evaluation shader.
+    pub inner: [f32; 2],
+}
+
+impl Default for TessellationLevels {
+    #[inline]
+    fn default() -> TessellationLevels {
+        TessellationLevels {
+            outer: [1.0; 4],
+            inner: [1.0; 2],
+        }
+    }
+}
+
 /// Specifies the clip control origin.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ClipControlOrigin {
@@ -498,16 +602,21 @@ impl<'a> Default for DrawParameters<'a> {
             depth: Depth::default(),
             stencil: Default::default(),
             blend: Default::default(),
+            blend_per_buffer: None,
             color_mask: (true, true, true, true),
             line_width: None,
             point_size: None,
             backface_culling: BackfaceCullingMode::CullingDisabled,
             polygon_mode: PolygonMode::Fill,
+            back_polygon_mode: None,
             clip_planes_bitmask: 0,
             multisampling: true,
             dithering: true,
+            conservative_rasterization: false,
             viewport: None,
+            inherit_viewport: false,
             scissor: None,
+            scissor_to_viewport: false,
             draw_primitives: true,
             samples_passed_query: None,
             time_elapsed_query: None,
@@ -519,9 +628,12 @@ impl<'a> Default for DrawParameters<'a> {
             provoking_vertex: ProvokingVertex::LastVertex,
             primitive_bounding_box: (-1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0),
             primitive_restart_index: false,
+            custom_primitive_restart_index: None,
             polygon_offset: Default::default(),
             clip_control_origin: ClipControlOrigin::LowerLeft,
             clip_control_depth: ClipControlDepth::NegativeOneToOne,
+            default_tessellation_levels: Default::default(),
+            srgb: None,
         }
     }
 }
@@ -543,35 +655,62 @@ pub fn validate(context: &Context, params: &DrawParameters<'_>) -> Result<(), Dr
     Ok(())
 }
 
+/// Returns true if the backend can use a different polygon mode for front and back faces.
+///
+/// Core profiles and OpenGL ES only accept `GL_FRONT_AND_BACK`.
+pub(crate) fn is_per_face_polygon_mode_supported<C: ?Sized>(caps: &C) -> bool
+    where C: CapabilitiesSource
+{
+    caps.get_version() >= &Version(Api::Gl, 1, 0) &&
+        !matches!(caps.get_capabilities().profile, Some(context::Profile::Core))
+}
+
 #[doc(hidden)]
 pub fn sync(ctxt: &mut context::CommandContext<'_>, draw_parameters: &DrawParameters<'_>,
             dimensions: (u32, u32), primitives_types: PrimitiveType) -> Result<(), DrawError>
 {
     depth::sync_depth(ctxt, &draw_parameters.depth)?;
     stencil::sync_stencil(ctxt, &draw_parameters.stencil);
-    blend::sync_blending(ctxt, draw_parameters.blend)?;
+    blend::sync_blending(ctxt, draw_parameters.blend, draw_parameters.blend_per_buffer)?;
     sync_color_mask(ctxt, draw_parameters.color_mask);
     sync_line_width(ctxt, draw_parameters.line_width);
     sync_point_size(ctxt, draw_parameters.point_size);
-    sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
+    sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode,
+                      draw_parameters.back_polygon_mode);
     sync_clip_planes_bitmask(ctxt, draw_parameters.clip_planes_bitmask)?;
     sync_multisampling(ctxt, draw_parameters.multisampling);
     sync_dithering(ctxt, draw_parameters.dithering);
-    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.scissor,
+    sync_conservative_rasterization(ctxt, draw_parameters.conservative_rasterization)?;
+    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.inherit_viewport,
+                          draw_parameters.scissor, draw_parameters.scissor_to_viewport,
                           dimensions);
     sync_rasterizer_discard(ctxt, draw_parameters.draw_primitives)?;
+
+    // if the user didn't request a query, the session counts the primitives itself, unless
+    // its query has been interrupted by another one in which case it can't be resumed
+    let transform_feedback_primitives_written_query =
+        draw_parameters.transform_feedback_primitives_written_query.or_else(|| {
+            draw_parameters.transform_feedback
+                .and_then(|tf| tf.get_primitives_written_query())
+                .filter(|q| q.is_unused() ||
+                            ctxt.state.transform_feedback_primitives_written_query == q.get_id())
+        });
+
     sync_queries(ctxt, draw_parameters.samples_passed_query,
                       draw_parameters.time_elapsed_query,
                       draw_parameters.primitives_generated_query,
-                      draw_parameters.transform_feedback_primitives_written_query)?;
-    sync_conditional_render(ctxt, draw_parameters.condition);
+                      transform_feedback_primitives_written_query)?;
+    sync_conditional_render(ctxt, draw_parameters.condition)?;
     sync_smooth(ctxt, draw_parameters.smooth, primitives_types)?;
     sync_provoking_vertex(ctxt, draw_parameters.provoking_vertex)?;
     sync_primitive_bounding_box(ctxt, &draw_parameters.primitive_bounding_box);
-    sync_primitive_restart_index(ctxt, draw_parameters.primitive_restart_index)?;
+    sync_primitive_restart_index(ctxt, draw_parameters.primitive_restart_index,
+                                 draw_parameters.custom_primitive_restart_index)?;
     sync_polygon_offset(ctxt, draw_parameters.polygon_offset);
     sync_clip_control(ctxt, draw_parameters.clip_control_origin,
                       draw_parameters.clip_control_depth)?;
+    sync_tessellation_levels(ctxt, draw_parameters.default_tessellation_levels)?;
+    sync_framebuffer_srgb(ctxt, draw_parameters.srgb);
 
     Ok(())
 }
@@ -616,7 +755,7 @@ fn sync_point_size(ctxt: &mut context::CommandContext<'_>, point_size: Option<f3
 }
 
 fn sync_polygon_mode(ctxt: &mut context::CommandContext<'_>, backface_culling: BackfaceCullingMode,
-                     polygon_mode: PolygonMode)
+                     polygon_mode: PolygonMode, back_polygon_mode: Option<PolygonMode>)
 {
     // back-face culling
     // note: we never change the value of `glFrontFace`, whose default is GL_CCW
@@ -652,10 +791,21 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext<'_>, backface_culling: B
 
     // polygon mode
     unsafe {
-        let polygon_mode = polygon_mode.to_glenum();
-        if ctxt.state.polygon_mode != polygon_mode {
-            ctxt.gl.PolygonMode(gl::FRONT_AND_BACK, polygon_mode);
-            ctxt.state.polygon_mode = polygon_mode;
+        let front = polygon_mode.to_glenum();
+        let back = match back_polygon_mode {
+            // the draw command has already reported that the back mode is ignored otherwise
+            Some(back) if is_per_face_polygon_mode_supported(ctxt) => back.to_glenum(),
+            _ => front,
+        };
+
+        if ctxt.state.polygon_mode != (front, back) {
+            if front == back {
+                ctxt.gl.PolygonMode(gl::FRONT_AND_BACK, front);
+            } else {
+                ctxt.gl.PolygonMode(gl::FRONT, front);
+                ctxt.gl.PolygonMode(gl::BACK, back);
+            }
+            ctxt.state.polygon_mode = (front, back);
         }
     }
 }
@@ -716,8 +866,32 @@ fn sync_dithering(ctxt: &mut context::CommandContext<'_>, dithering: bool) {
     }
 }
 
+fn sync_conservative_rasterization(ctxt: &mut context::CommandContext<'_>, enabled: bool)
+                                   -> Result<(), DrawError>
+{
+    if ctxt.state.enabled_conservative_rasterization == enabled {
+        return Ok(());
+    }
+
+    if !ctxt.capabilities.conservative_rasterization {
+        return Err(DrawError::ConservativeRasterizationNotSupported);
+    }
+
+    unsafe {
+        if enabled {
+            ctxt.gl.Enable(gl::CONSERVATIVE_RASTERIZATION_NV);
+        } else {
+            ctxt.gl.Disable(gl::CONSERVATIVE_RASTERIZATION_NV);
+        }
+    }
+
+    ctxt.state.enabled_conservative_rasterization = enabled;
+    Ok(())
+}
+
 fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Option<Rect>,
-                         scissor: Option<Rect>, surface_dimensions: (u32, u32))
+                         inherit_viewport: bool, scissor: Option<Rect>,
+                         scissor_to_viewport: bool, surface_dimensions: (u32, u32))
 {
     // viewport
     if let Some(viewport) = viewport {
@@ -735,7 +909,7 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
             ctxt.state.viewport = Some(viewport);
         }
 
-    } else {
+    } else if !inherit_viewport {
         assert!(surface_dimensions.0 <= ctxt.capabilities.max_viewport_dims.0 as u32,
                 "Viewport dimensions are too large");
         assert!(surface_dimensions.1 <= ctxt.capabilities.max_viewport_dims.1 as u32,
@@ -751,11 +925,17 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
     }
 
     // scissor
-    if let Some(scissor) = scissor {
-        let scissor = (scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
-                       scissor.width as gl::types::GLsizei,
-                       scissor.height as gl::types::GLsizei);
+    let scissor = match scissor {
+        Some(scissor) => Some((scissor.left as gl::types::GLint,
+                               scissor.bottom as gl::types::GLint,
+                               scissor.width as gl::types::GLsizei,
+                               scissor.height as gl::types::GLsizei)),
+        // the viewport has been synchronized above
+        None if scissor_to_viewport => ctxt.state.viewport,
+        None => None,
+    };
 
+    if let Some(scissor) = scissor {
         unsafe {
             if ctxt.state.scissor != Some(scissor) {
                 ctxt.gl.Scissor(scissor.0, scissor.1, scissor.2, scissor.3);
@@ -845,9 +1025,13 @@ fn sync_queries(ctxt: &mut context::CommandContext<'_>,
 }
 
 fn sync_conditional_render(ctxt: &mut context::CommandContext<'_>,
-                           condition: Option<ConditionalRendering<'_>>)
+                           condition: Option<ConditionalRendering<'_>>) -> Result<(), DrawError>
 {
     if let Some(ConditionalRendering { query, wait, per_region }) = condition {
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_nv_conditional_render) {
+            return Err(DrawError::ConditionalRenderingNotSupported);
+        }
+
         match query {
             SamplesQueryParam::SamplesPassedQuery(ref q) => {
                 q.begin_conditional_render(ctxt, wait, per_region);
@@ -860,6 +1044,8 @@ fn sync_conditional_render(ctxt: &mut context::CommandContext<'_>,
     } else {
         TimeElapsedQuery::end_conditional_render(ctxt);
     }
+
+    Ok(())
 }
 
 fn sync_smooth(ctxt: &mut context::CommandContext<'_>,
@@ -996,7 +1182,7 @@ fn sync_primitive_bounding_box(ctxt: &mut context::CommandContext<'_>,
 }
 
 fn sync_primitive_restart_index(ctxt: &mut context::CommandContext<'_>,
-                                enabled: bool)
+                                enabled: bool, custom_index: Option<u32>)
                                 -> Result<(), DrawError>
 {
     // TODO: use GL_PRIMITIVE_RESTART (if possible) if
@@ -1017,6 +1203,28 @@ fn sync_primitive_restart_index(ctxt: &mut context::CommandContext<'_>,
         return Err(DrawError::FixedIndexRestartingNotSupported);
     }
 
+    // the fixed index takes precedence over `GL_PRIMITIVE_RESTART` anyway
+    let custom_index = if enabled { None } else { custom_index };
+
+    if let Some(index) = custom_index {
+        if !(ctxt.version >= &Version(Api::Gl, 3, 1)) {
+            return Err(DrawError::CustomIndexRestartingNotSupported);
+        }
+
+        if !ctxt.state.enabled_primitive_restart {
+            unsafe { ctxt.gl.Enable(gl::PRIMITIVE_RESTART); }
+            ctxt.state.enabled_primitive_restart = true;
+        }
+
+        if ctxt.state.primitive_restart_index != index {
+            unsafe { ctxt.gl.PrimitiveRestartIndex(index); }
+            ctxt.state.primitive_restart_index = index;
+        }
+
+    } else if ctxt.state.enabled_primitive_restart {
+        unsafe { ctxt.gl.Disable(gl::PRIMITIVE_RESTART); }
+        ctxt.state.enabled_primitive_restart = false;
+    }
 
     Ok(())
 }
@@ -1029,6 +1237,25 @@ fn set_flag_enabled(ctxt: &mut context::CommandContext<'_>, cap: gl::types::GLen
     }
 }
 
+fn sync_framebuffer_srgb(ctxt: &mut context::CommandContext<'_>, srgb: Option<bool>) {
+    // when `None`, the state has already been set when binding the program
+    let srgb = match srgb {
+        Some(srgb) => srgb,
+        None => return,
+    };
+
+    if !(ctxt.version >= &Version(Api::Gl, 3, 0)) && !ctxt.extensions.gl_arb_framebuffer_srgb &&
+       !ctxt.extensions.gl_ext_framebuffer_srgb && !ctxt.extensions.gl_ext_srgb_write_control
+    {
+        return;
+    }
+
+    if ctxt.state.enabled_framebuffer_srgb != srgb {
+        set_flag_enabled(ctxt, gl::FRAMEBUFFER_SRGB, srgb);
+        ctxt.state.enabled_framebuffer_srgb = srgb;
+    }
+}
+
 fn sync_polygon_offset(ctxt: &mut context::CommandContext<'_>, offset: PolygonOffset) {
     let (factor, units) = ctxt.state.polygon_offset;
 
@@ -1081,3 +1308,34 @@ fn sync_clip_control(ctxt: &mut context::CommandContext<'_>,
 
     Ok(())
 }
+
+fn sync_tessellation_levels(ctxt: &mut context::CommandContext<'_>, levels: TessellationLevels)
+                            -> Result<(), DrawError>
+{
+    if ctxt.state.patch_default_outer_level == levels.outer &&
+       ctxt.state.patch_default_inner_level == levels.inner
+    {
+        return Ok(());
+    }
+
+    // GLES doesn't have default tessellation levels
+    if ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.extensions.gl_arb_tessellation_shader {
+        if ctxt.state.patch_default_outer_level != levels.outer {
+            unsafe {
+                ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_OUTER_LEVEL, levels.outer.as_ptr());
+            }
+            ctxt.state.patch_default_outer_level = levels.outer;
+        }
+
+        if ctxt.state.patch_default_inner_level != levels.inner {
+            unsafe {
+                ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_INNER_LEVEL, levels.inner.as_ptr());
+            }
+            ctxt.state.patch_default_inner_level = levels.inner;
+        }
+    } else {
+        return Err(DrawError::TessellationNotSupported);
+    }
+
+    Ok(())
+}
@@ -104,8 +104,6 @@ impl RawQuery {
         let context = facade.get_context().clone();
         let ctxt = facade.get_context().make_current();
 
-        // FIXME: handle Timestamp separately
-
         let id = unsafe {
             let mut id = 0;
 
@@ -113,7 +111,7 @@ impl RawQuery {
                 match ty {
                     QueryType::AnySamplesPassed | QueryType::SamplesPassed |
                     QueryType::PrimitivesGenerated | QueryType::TimeElapsed |
-                    QueryType::TransformFeedbackPrimitivesWritten => (),
+                    QueryType::Timestamp | QueryType::TransformFeedbackPrimitivesWritten => (),
                     QueryType::AnySamplesPassedConservative if
                             ctxt.extensions.gl_arb_es3_compatibility ||
                             ctxt.version >= &Version(Api:: Gl, 4, 3) => (),
@@ -135,6 +133,7 @@ impl RawQuery {
                     QueryType::AnySamplesPassed if ctxt.extensions.gl_arb_occlusion_query2 => (),
                     QueryType::AnySamplesPassedConservative if ctxt.extensions.gl_arb_es3_compatibility => (),
                     QueryType::TimeElapsed if ctxt.extensions.gl_arb_timer_query => (),
+                    QueryType::Timestamp if ctxt.extensions.gl_arb_timer_query => (),
 
                     _ => return Err(QueryCreationError::NotSupported)
                 };
@@ -149,6 +148,7 @@ impl RawQuery {
                     QueryType::PrimitivesGenerated if ctxt.extensions.gl_ext_transform_feedback => (),
                     QueryType::TransformFeedbackPrimitivesWritten if ctxt.extensions.gl_ext_transform_feedback => (),
                     QueryType::TimeElapsed if ctxt.extensions.gl_arb_timer_query => (),
+                    QueryType::Timestamp if ctxt.extensions.gl_arb_timer_query => (),
                     _ => return Err(QueryCreationError::NotSupported)
                 };
 
@@ -247,6 +247,16 @@ impl RawQuery {
         }
     }
 
+    /// Returns the value of the query if it is available, or `None` otherwise. Never blocks.
+    #[inline]
+    pub fn try_get_u32(&self) -> Option<u32> {
+        if self.is_ready() {
+            Some(self.get_u32())
+        } else {
+            None
+        }
+    }
+
     /// Writes the value of the query to a buffer.
     pub fn write_u32_to_buffer(&self, target: BufferSlice<'_, u32>) -> Result<(), ToBufferError> {
         let mut ctxt = self.context.make_current();
@@ -320,7 +330,7 @@ impl RawQuery {
     unsafe fn raw_get_u64(&self, ctxt: &mut CommandContext<'_>, target: *mut gl::types::GLuint64)
                           -> Result<(), ()>
     {
-        if ctxt.version >= &Version(Api::Gl, 3, 3) {
+        if ctxt.version >= &Version(Api::Gl, 3, 3) || ctxt.extensions.gl_arb_timer_query {
             ctxt.gl.GetQueryObjectui64v(self.id, gl::QUERY_RESULT, target);
             Ok(())
 
@@ -329,6 +339,16 @@ impl RawQuery {
         }
     }
 
+    /// Returns the value of the query if it is available, or `None` otherwise. Never blocks.
+    #[inline]
+    pub fn try_get_u64(&self) -> Option<u64> {
+        if self.is_ready() {
+            Some(self.get_u64())
+        } else {
+            None
+        }
+    }
+
     /// Returns the value of the query. Blocks until it is available.
     ///
     /// This function doesn't block if `is_ready` returns true.
@@ -337,6 +357,20 @@ impl RawQuery {
         self.get_u32() != 0
     }
 
+    /// Records the current GPU time into this query once all the previous commands have
+    /// been executed.
+    ///
+    /// The query must have been created with `QueryType::Timestamp` and must not have been
+    /// used before.
+    pub fn query_counter(&self) {
+        assert!(match self.ty { QueryType::Timestamp => true, _ => false });
+        assert!(!self.has_been_used.get());
+
+        let ctxt = self.context.make_current();
+        unsafe { ctxt.gl.QueryCounter(self.id, gl::TIMESTAMP); }
+        self.has_been_used.set(true);
+    }
+
     /// If the query is active, unactivates it.
     fn deactivate(&self, ctxt: &mut CommandContext<'_>) {
         if ctxt.state.samples_passed_query == self.id {
@@ -809,6 +843,10 @@ macro_rules! impl_helper {
 ///
 /// If you just want to know whether or not some samples have been written, you should use
 /// a `AnySamplesPassedQuery` query instead.
+///
+/// The query is activated by passing it as the `samples_passed_query` of the `DrawParameters`.
+/// It can then be used with the `condition` draw parameter to skip later draw commands if no
+/// sample was written.
 #[derive(Debug)]
 pub struct SamplesPassedQuery {
     query: RawQuery,
@@ -820,6 +858,13 @@ impl SamplesPassedQuery {
     pub fn new<F: ?Sized>(facade: &F) -> Result<SamplesPassedQuery, QueryCreationError> where F: Facade {
         RawQuery::new(facade, QueryType::SamplesPassed).map(|q| SamplesPassedQuery { query: q })
     }
+
+    /// Returns the number of samples that have been written if the result is available, or
+    /// `None` if it isn't available yet or if the query hasn't been used. Never blocks.
+    #[inline]
+    pub fn try_get(&self) -> Option<u32> {
+        self.query.try_get_u32()
+    }
 }
 
 impl_helper!(SamplesPassedQuery, u32, get_u32);
@@ -827,7 +872,8 @@ impl_helper!(SamplesPassedQuery, u32, get_u32);
 /// A query that allows you to know the number of nanoseconds that have elapsed
 /// during the draw operations.
 ///
-/// TODO: not sure that it's nanoseconds
+/// `get` returns a `u32` and thus wraps after about four seconds. Use `try_get` to obtain the
+/// full value without blocking, for example by checking the queries of the previous frame.
 #[derive(Debug)]
 pub struct TimeElapsedQuery {
     query: RawQuery,
@@ -839,10 +885,68 @@ impl TimeElapsedQuery {
     pub fn new<F: ?Sized>(facade: &F) -> Result<TimeElapsedQuery, QueryCreationError> where F: Facade {
         RawQuery::new(facade, QueryType::TimeElapsed).map(|q| TimeElapsedQuery { query: q })
     }
+
+    /// Returns the elapsed time in nanoseconds if the result is available, or `None` if it
+    /// isn't available yet or if the query hasn't been used. Never blocks.
+    #[inline]
+    pub fn try_get(&self) -> Option<u64> {
+        self.query.try_get_u64()
+    }
 }
 
 impl_helper!(TimeElapsedQuery, u32, get_u32);
 
+/// A query that records the time, in nanoseconds, at which the GPU has finished executing all
+/// the commands submitted before it.
+///
+/// Contrary to the other queries, a timestamp query isn't passed to draw commands. The
+/// timestamp is recorded as soon as the query is created, and two timestamps can be
+/// subtracted to measure the GPU time spent between them.
+#[derive(Debug)]
+pub struct TimestampQuery {
+    query: RawQuery,
+}
+
+impl TimestampQuery {
+    /// Builds a new query and records the current timestamp.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F) -> Result<TimestampQuery, QueryCreationError> where F: Facade {
+        let query = RawQuery::new(facade, QueryType::Timestamp)?;
+        query.query_counter();
+        Ok(TimestampQuery { query })
+    }
+
+    /// Queries the counter to see if the result is already available.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.query.is_ready()
+    }
+
+    /// Returns the recorded timestamp in nanoseconds. Blocks until it is available.
+    ///
+    /// This function doesn't block if `is_ready` would return true.
+    #[inline]
+    pub fn get(self) -> u64 {
+        self.query.get_u64()
+    }
+
+    /// Returns the recorded timestamp in nanoseconds if it is available, or `None` otherwise.
+    /// Never blocks.
+    #[inline]
+    pub fn try_get(&self) -> Option<u64> {
+        self.query.try_get_u64()
+    }
+}
+
+impl GlObject for TimestampQuery {
+    type Id = gl::types::GLuint;
+
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.query.get_id()
+    }
+}
+
 /// A query type that allows you to know whether any sample has been written to the output during
 /// the operations executed with this query.
 ///
@@ -882,6 +986,13 @@ impl AnySamplesPassedQuery {
             Err(QueryCreationError::NotSupported)
         }
     }
+
+    /// Returns whether any sample has been written if the result is available, or `None` if it
+    /// isn't available yet or if the query hasn't been used. Never blocks.
+    #[inline]
+    pub fn try_get(&self) -> Option<bool> {
+        self.query.try_get_u32().map(|value| value != 0)
+    }
 }
 
 impl_helper!(AnySamplesPassedQuery, bool, get_bool);
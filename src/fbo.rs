@@ -205,6 +205,8 @@ impl<'a> FramebufferAttachments<'a> {
                         layers,
                         depth_buffer_bits: None,
                         stencil_buffer_bits: None,
+                        read_buffer_kind: None,
+                        samples,
                         marker: PhantomData,
                     })
 
@@ -346,6 +348,9 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,       // FIXME: count layers
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            read_buffer_kind: colors.iter().find(|&&(index, _)| index == 0)
+                                    .map(|&(_, LayeredAttachment(ref a))| a.get_texture().kind()),
+            samples: samples.filter(|&s| s != 0),
             marker: PhantomData,
         })
     }
@@ -535,6 +540,9 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            read_buffer_kind: colors.iter().find(|&&(index, _)| index == 0)
+                                    .map(|&(_, ref a)| a.kind()),
+            samples: samples.filter(|&s| s != 0),
             marker: PhantomData,
         })
     }
@@ -548,6 +556,9 @@ pub struct ValidatedAttachments<'a> {
     layers: Option<u32>,
     depth_buffer_bits: Option<u16>,
     stencil_buffer_bits: Option<u16>,
+    // kind of the color attachment `0`, which is the one that is read by `glBlitFramebuffer`
+    read_buffer_kind: Option<TextureKind>,
+    samples: Option<u32>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -577,6 +588,20 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Returns the kind of the color attachment that is read from when blitting, or `None` if
+    /// there is no such attachment.
+    #[inline]
+    pub fn get_read_buffer_kind(&self) -> Option<TextureKind> {
+        self.read_buffer_kind
+    }
+
+    /// Returns the number of samples of the attachments, or `None` if they are not
+    /// multisampled.
+    #[inline]
+    pub fn get_samples(&self) -> Option<u32> {
+        self.samples
+    }
 }
 
 /// An error that can happen while validating attachments.
@@ -604,6 +629,9 @@ pub enum ValidationError {
         /// Number of attachments that were given.
         obtained: usize,
     },
+
+    /// The requested mipmap level doesn't exist in the texture.
+    MipmapLevelOutOfRange,
 }
 
 impl fmt::Display for ValidationError {
@@ -620,6 +648,8 @@ impl fmt::Display for ValidationError {
                 "All attachments must have the same number of samples",
             TooManyColorAttachments {..} =>
                 "Backends only support a certain number of color attachments",
+            MipmapLevelOutOfRange =>
+                "The requested mipmap level doesn't exist in the texture",
         };
         match self {
             TooManyColorAttachments{ ref maximum, ref obtained } =>
@@ -0,0 +1,165 @@
+/*!
+Measuring the time spent on each frame.
+
+When vsync is enabled, the time between two frames only tells you the refresh rate of the
+monitor. To know how much work a frame really costs, you need to measure both the time spent
+by the CPU building the frame and the time spent by the GPU executing its commands.
+
+A `FrameTimer` brackets a frame between `begin_frame` and `end_frame`. The CPU time is
+measured with `std::time::Instant`, while the GPU time is measured with timestamp queries.
+
+```no_run
+# use glium::Surface;
+# use glium::frame_timer::FrameTimer;
+# fn example(display: glium::Display<glium::glutin::surface::WindowSurface>) {
+display.set_swap_interval(glium::glutin::surface::SwapInterval::DontWait).unwrap();
+let mut timer = FrameTimer::new(&display);
+
+loop {
+    timer.begin_frame();
+    let mut frame = display.draw();
+    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+    frame.finish().unwrap();
+    timer.end_frame();
+
+    println!("cpu: {:?}, gpu: {:?}", timer.cpu_frame_time(), timer.gpu_frame_time());
+}
+# }
+```
+
+The GPU results are only read once they are available, which means that `gpu_frame_time`
+usually reports the time of a frame that was submitted a few frames ago. Reading them never
+blocks.
+
+*/
+use std::cell::OnceCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::backend::Facade;
+use crate::context::Context;
+use crate::draw_parameters::TimestampQuery;
+
+/// Maximum number of frames whose GPU time hasn't been retrieved yet. If the GPU is too late,
+/// the oldest frames are discarded.
+const MAX_PENDING_FRAMES: usize = 8;
+
+/// Measures the CPU and GPU time spent on each frame.
+///
+/// If the backend doesn't support timestamp queries, only the CPU time is measured.
+pub struct FrameTimer {
+    context: Rc<Context>,
+
+    // whether timestamp queries are supported, determined the first time it is needed
+    gpu_time_supported: OnceCell<bool>,
+
+    // time at which the current frame started, and the matching GPU timestamp
+    current: Option<(Instant, Option<TimestampQuery>)>,
+
+    // frames whose GPU timestamps haven't been retrieved yet
+    pending: VecDeque<(TimestampQuery, TimestampQuery)>,
+
+    cpu_frame_time: Option<Duration>,
+    gpu_frame_time: Option<Duration>,
+}
+
+impl FrameTimer {
+    /// Builds a new `FrameTimer`.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F) -> FrameTimer where F: Facade {
+        FrameTimer {
+            context: facade.get_context().clone(),
+            gpu_time_supported: OnceCell::new(),
+            current: None,
+            pending: VecDeque::with_capacity(MAX_PENDING_FRAMES),
+            cpu_frame_time: None,
+            gpu_frame_time: None,
+        }
+    }
+
+    /// Returns true if the GPU time can be measured.
+    #[inline]
+    pub fn is_gpu_time_supported(&self) -> bool {
+        *self.gpu_time_supported.get_or_init(|| TimestampQuery::new(&self.context).is_ok())
+    }
+
+    /// Records a timestamp, or returns `None` if this isn't supported.
+    fn timestamp(&self) -> Option<TimestampQuery> {
+        if !self.is_gpu_time_supported() {
+            return None;
+        }
+
+        TimestampQuery::new(&self.context).ok()
+    }
+
+    /// Marks the start of a frame.
+    ///
+    /// Calling this function again before `end_frame` restarts the measurement.
+    pub fn begin_frame(&mut self) {
+        self.poll();
+        let query = self.timestamp();
+        self.current = Some((Instant::now(), query));
+    }
+
+    /// Marks the end of a frame.
+    ///
+    /// The CPU time of the frame is immediately available through `cpu_frame_time`. Does
+    /// nothing if `begin_frame` hasn't been called.
+    pub fn end_frame(&mut self) {
+        let (start, start_query) = match self.current.take() {
+            Some(current) => current,
+            None => return,
+        };
+
+        self.cpu_frame_time = Some(start.elapsed());
+
+        if let Some(start_query) = start_query {
+            if let Some(end_query) = self.timestamp() {
+                if self.pending.len() >= MAX_PENDING_FRAMES {
+                    self.pending.pop_front();
+                }
+
+                self.pending.push_back((start_query, end_query));
+            }
+        }
+
+        self.poll();
+    }
+
+    /// Returns the CPU time spent between the last calls to `begin_frame` and `end_frame`.
+    ///
+    /// Returns `None` if no frame has been measured yet.
+    #[inline]
+    pub fn cpu_frame_time(&self) -> Option<Duration> {
+        self.cpu_frame_time
+    }
+
+    /// Returns the GPU time of the most recent frame whose result is available.
+    ///
+    /// Returns `None` if timestamp queries are not supported or if no result is available yet.
+    #[inline]
+    pub fn gpu_frame_time(&self) -> Option<Duration> {
+        self.gpu_frame_time
+    }
+
+    /// Retrieves the results of the pending frames that are available, without blocking.
+    fn poll(&mut self) {
+        while self.pending.front().is_some_and(|(_, end)| end.is_ready()) {
+            let (start, end) = self.pending.pop_front().unwrap();
+            let start = start.get();
+            let end = end.get();
+            self.gpu_frame_time = Some(Duration::from_nanos(end.saturating_sub(start)));
+        }
+    }
+}
+
+impl fmt::Debug for FrameTimer {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt.debug_struct("FrameTimer")
+            .field("cpu_frame_time", &self.cpu_frame_time)
+            .field("gpu_frame_time", &self.gpu_frame_time)
+            .finish()
+    }
+}
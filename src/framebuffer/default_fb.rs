@@ -1,5 +1,7 @@
 //! Contains everything related to the default framebuffer.
 
+use std::error::Error;
+use std::fmt;
 use std::rc::Rc;
 
 use crate::backend::Facade;
@@ -16,8 +18,11 @@ use crate::uniforms;
 
 use crate::{Program, Surface};
 use crate::DrawError;
+use crate::ReadError;
+use crate::ClearError;
 
 use crate::fbo;
+use crate::gl;
 use crate::index;
 use crate::vertex;
 use crate::framebuffer::{SimpleFrameBuffer, MultiOutputFrameBuffer};
@@ -37,6 +42,64 @@ pub enum DefaultFramebufferAttachment {
     FrontRight,
 }
 
+impl ToGlEnum for DefaultFramebufferAttachment {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            DefaultFramebufferAttachment::BackLeft => gl::BACK_LEFT,
+            DefaultFramebufferAttachment::BackRight => gl::BACK_RIGHT,
+            DefaultFramebufferAttachment::FrontLeft => gl::FRONT_LEFT,
+            DefaultFramebufferAttachment::FrontRight => gl::FRONT_RIGHT,
+        }
+    }
+}
+
+/// An eye, when doing stereoscopic rendering.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Eye {
+    /// The left eye.
+    Left,
+    /// The right eye.
+    Right,
+}
+
+impl Eye {
+    /// Returns the back buffer that contains the image of this eye.
+    #[inline]
+    pub fn back_buffer(&self) -> DefaultFramebufferAttachment {
+        match *self {
+            Eye::Left => DefaultFramebufferAttachment::BackLeft,
+            Eye::Right => DefaultFramebufferAttachment::BackRight,
+        }
+    }
+}
+
+/// Error that can happen when choosing the buffer of the default framebuffer to draw upon.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DrawBufferError {
+    /// The backend can only draw upon the back buffer. This is the case of OpenGL ES.
+    NotSupported,
+
+    /// A buffer for the right eye has been requested, but the context doesn't support
+    /// stereoscopy.
+    StereoNotSupported,
+}
+
+impl fmt::Display for DrawBufferError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::DrawBufferError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Choosing the buffer to draw upon is not supported by the backend",
+            StereoNotSupported =>
+                "The context doesn't have buffers for the right eye",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for DrawBufferError {}
+
 /// A framebuffer which has only one color attachment.
 pub struct DefaultFramebuffer {
     context: Rc<Context>,
@@ -60,7 +123,18 @@ impl Surface for DefaultFramebuffer {
              depth: Option<f32>, stencil: Option<i32>)
     {
         // TODO: wrong attachment
-        ops::clear(&self.context, None, None, color, color_srgb, depth, stencil);
+        ops::clear(&self.context, None, None, color, color_srgb, (true, true, true, true),
+                   depth, stencil);
+    }
+
+    #[inline]
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        // TODO: wrong attachment
+        ops::clear(&self.context, None, None, color, color_srgb, color_mask, depth, stencil);
+        Ok(())
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -75,6 +149,11 @@ impl Surface for DefaultFramebuffer {
         self.context.capabilities().stencil_bits
     }
 
+    #[inline]
+    fn read_stencil(&self, rect: &Rect) -> Result<Vec<u8>, ReadError> {
+        ops::read_stencil(&self.context, None, rect)
+    }
+
     fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
                          index_buffer: I, program: &Program, uniforms: &U,
                          draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
@@ -105,6 +184,37 @@ impl Surface for DefaultFramebuffer {
                   uniforms, draw_parameters, self.get_dimensions())
     }
 
+    fn draw_multi<'a, 'b, V, U>(&mut self, vertex_buffer: V,
+                                indices: &[(index::IndicesSource<'a>, index::PrimitiveType)],
+                                program: &Program, uniforms: &U,
+                                draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                                where V: vertex::MultiVerticesSource<'b> + Copy,
+                                      U: uniforms::Uniforms
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        // TODO: wrong attachment
+        ops::draw_multi(&self.context, None, vertex_buffer, indices, program,
+                        uniforms, draw_parameters, self.get_dimensions())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -115,19 +225,43 @@ impl Surface for DefaultFramebuffer {
     #[inline]
     fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, None, self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_simple_framebuffer(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 }
 
@@ -69,7 +69,7 @@ Not yet supported
 use std::rc::Rc;
 use smallvec::SmallVec;
 
-use crate::texture::TextureAnyImage;
+use crate::texture::{Texture2d, TextureAnyImage};
 
 use crate::backend::Facade;
 use crate::context::Context;
@@ -87,18 +87,22 @@ use crate::uniforms;
 
 use crate::{Program, Surface};
 use crate::DrawError;
+use crate::ReadError;
+use crate::{BlitError, ClearError};
 
 use crate::fbo;
 
-pub use self::default_fb::{DefaultFramebufferAttachment, DefaultFramebuffer};
+pub use self::default_fb::{DefaultFramebufferAttachment, DefaultFramebuffer, DrawBufferError, Eye};
 pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
 pub use self::render_buffer::{StencilRenderBuffer, DepthStencilRenderBuffer};
 pub use self::render_buffer::CreationError as RenderBufferCreationError;
+pub use self::ping_pong::PingPong;
 pub use crate::fbo::is_dimensions_mismatch_supported;
 pub use crate::fbo::ValidationError;
 use crate::uniforms::MagnifySamplerFilter;
 
 mod default_fb;
+mod ping_pong;
 mod render_buffer;
 
 /// A framebuffer which has only one color attachment.
@@ -117,6 +121,20 @@ impl<'a> SimpleFrameBuffer<'a> {
         SimpleFrameBuffer::new_impl(facade, Some(color.to_color_attachment()), None, None, None)
     }
 
+    /// Creates a `SimpleFrameBuffer` whose color attachment is the given mipmap level of a
+    /// texture, with no depth nor stencil buffer.
+    ///
+    /// The dimensions of the framebuffer are the dimensions of this level. Returns
+    /// `MipmapLevelOutOfRange` if the texture doesn't have this level.
+    #[inline]
+    pub fn with_texture_level<F: ?Sized>(facade: &F, texture: &'a Texture2d, level: u32)
+                                         -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                                         where F: Facade
+    {
+        let mipmap = texture.mipmap(level).ok_or(ValidationError::MipmapLevelOutOfRange)?;
+        SimpleFrameBuffer::new(facade, mipmap)
+    }
+
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth
     /// buffer, but no stencil buffer.
     #[inline]
@@ -212,6 +230,26 @@ impl<'a> SimpleFrameBuffer<'a> {
     }
 
 
+    /// Tells the backend that the content of some attachments is no longer needed.
+    ///
+    /// This is a hint that avoids writing the content of the attachments back to memory
+    /// at the end of a rendering pass on tile-based GPUs, like the ones of mobile devices. For
+    /// example you can invalidate the depth buffer once you have drawn the scene. The content
+    /// of the invalidated attachments is undefined until you write to them again.
+    ///
+    /// Requires OpenGL 4.3, OpenGL ES 3.0 or `GL_ARB_invalidate_subdata`. Does nothing if
+    /// this is not supported.
+    #[inline]
+    pub fn invalidate(&self, attachments: &[Attachment]) {
+        ops::invalidate(&self.context, Some(&self.attachments), attachments, None);
+    }
+
+    /// Same as `invalidate`, but only for a part of the attachments.
+    #[inline]
+    pub fn invalidate_rect(&self, attachments: &[Attachment], rect: &Rect) {
+        ops::invalidate(&self.context, Some(&self.attachments), attachments, Some(rect));
+    }
+
     fn new_impl<F: ?Sized>(facade: &F, color: Option<ColorAttachment<'a>>,
                    depth: Option<DepthAttachment<'a>>, stencil: Option<StencilAttachment<'a>>,
                    depthstencil: Option<DepthStencilAttachment<'a>>)
@@ -270,7 +308,18 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
     fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
              depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb,
+                   (true, true, true, true), depth, stencil);
+    }
+
+    #[inline]
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, color_mask,
+                   depth, stencil);
+        Ok(())
     }
 
     #[inline]
@@ -288,6 +337,11 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         self.attachments.get_stencil_buffer_bits()
     }
 
+    #[inline]
+    fn read_stencil(&self, rect: &Rect) -> Result<Vec<u8>, ReadError> {
+        ops::read_stencil(&self.context, Some(&self.attachments), rect)
+    }
+
     fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
@@ -316,6 +370,35 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
+    fn draw_multi<'i, 'v, V, U>(&mut self, vb: V,
+        indices: &[(crate::index::IndicesSource<'i>, crate::index::PrimitiveType)],
+        program: &crate::Program, uniforms: &U,
+        draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v> + Copy
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                        draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw_multi(&self.context, Some(&self.attachments), vb, indices,
+                        program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -323,22 +406,57 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         target.blit_from_simple_framebuffer(self, source_rect, target_rect, filter)
     }
 
+    #[inline]
+    fn blit_color_with_filter<S>(&self, source_rect: &Rect, target: &S,
+                                 target_rect: &BlitTarget,
+                                 filter: uniforms::MagnifySamplerFilter)
+                                 -> Result<(), BlitError> where S: Surface
+    {
+        ops::validate_blit(&self.attachments, source_rect, target_rect, filter.to_glenum())?;
+        target.blit_from_simple_framebuffer(self, source_rect, target_rect, filter);
+        Ok(())
+    }
+
     #[inline]
     fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, None, self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_simple_framebuffer(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 }
 
@@ -530,7 +648,17 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
              depth: Option<f32>, stencil: Option<i32>)
     {
         ops::clear(&self.context, Some(&self.example_attachments), rect,
-                   color, color_srgb, depth, stencil);
+                   color, color_srgb, (true, true, true, true), depth, stencil);
+    }
+
+    #[inline]
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        ops::clear(&self.context, Some(&self.example_attachments), rect,
+                   color, color_srgb, color_mask, depth, stencil);
+        Ok(())
     }
 
     #[inline]
@@ -548,6 +676,11 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
         self.example_attachments.get_stencil_buffer_bits()
     }
 
+    #[inline]
+    fn read_stencil(&self, rect: &Rect) -> Result<Vec<u8>, ReadError> {
+        ops::read_stencil(&self.context, Some(&self.example_attachments), rect)
+    }
+
     fn draw<'i, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'i>>,
@@ -576,6 +709,35 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
+    fn draw_multi<'i, 'v, V, U>(&mut self, vb: V,
+        indices: &[(crate::index::IndicesSource<'i>, crate::index::PrimitiveType)],
+        program: &crate::Program, uniforms: &U,
+        draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v> + Copy
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw_multi(&self.context, Some(&self.build_attachments(program)), vb, indices,
+                        program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -583,22 +745,58 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
         target.blit_from_multioutput_framebuffer(self, source_rect, target_rect, filter)
     }
 
+    #[inline]
+    fn blit_color_with_filter<S>(&self, source_rect: &Rect, target: &S,
+                                 target_rect: &BlitTarget,
+                                 filter: uniforms::MagnifySamplerFilter)
+                                 -> Result<(), BlitError> where S: Surface
+    {
+        ops::validate_blit(&self.example_attachments, source_rect, target_rect,
+                           filter.to_glenum())?;
+        target.blit_from_multioutput_framebuffer(self, source_rect, target_rect, filter);
+        Ok(())
+    }
+
     #[inline]
     fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, None, self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_simple_framebuffer(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self, source: &SimpleFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
     fn blit_buffers_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self, source: &MultiOutputFrameBuffer<'_>, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 }
 
@@ -691,7 +889,18 @@ impl Surface for EmptyFrameBuffer {
     fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
              depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb,
+                   (true, true, true, true), depth, stencil);
+    }
+
+    #[inline]
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, color_mask,
+                   depth, stencil);
+        Ok(())
     }
 
     #[inline]
@@ -709,6 +918,11 @@ impl Surface for EmptyFrameBuffer {
         None
     }
 
+    #[inline]
+    fn read_stencil(&self, _: &Rect) -> Result<Vec<u8>, ReadError> {
+        Err(ReadError::NoStencilBuffer)
+    }
+
     fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
         uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
         where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
@@ -737,6 +951,35 @@ impl Surface for EmptyFrameBuffer {
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
+    fn draw_multi<'i, 'v, V, U>(&mut self, vb: V,
+        indices: &[(crate::index::IndicesSource<'i>, crate::index::PrimitiveType)],
+        program: &crate::Program, uniforms: &U,
+        draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where U: crate::uniforms::Uniforms, V: crate::vertex::MultiVerticesSource<'v> + Copy
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                        draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw_multi(&self.context, Some(&self.attachments), vb, indices,
+                        program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -744,12 +987,31 @@ impl Surface for EmptyFrameBuffer {
         unimplemented!()        // TODO:
     }
 
+    #[inline]
+    fn blit_color_with_filter<S>(&self, _: &Rect, _: &S, _: &BlitTarget,
+                                 _: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
+                                 where S: Surface
+    {
+        // there is no color buffer to copy
+        Ok(())
+    }
+
     #[inline]
     fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
                        filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
     {
         ops::blit(&self.context, None, self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask, srgb: bool)
+    {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
@@ -758,7 +1020,18 @@ impl Surface for EmptyFrameBuffer {
                                     filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self, source: &SimpleFrameBuffer<'_>,
+                                                      source_rect: &Rect, target_rect: &BlitTarget,
+                                                      filter: uniforms::MagnifySamplerFilter, mask: BlitMask, srgb: bool)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     #[inline]
@@ -768,7 +1041,19 @@ impl Surface for EmptyFrameBuffer {
                                          mask: BlitMask)
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self, source: &MultiOutputFrameBuffer<'_>,
+                                                           source_rect: &Rect, target_rect: &BlitTarget,
+                                                           filter: uniforms::MagnifySamplerFilter,
+                                                           mask: BlitMask, srgb: bool)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 }
 
@@ -779,6 +1064,17 @@ impl FboAttachments for EmptyFrameBuffer {
     }
 }
 
+/// One of the buffers of a framebuffer, whose content can be invalidated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Attachment {
+    /// The color buffer.
+    Color,
+    /// The depth buffer.
+    Depth,
+    /// The stencil buffer.
+    Stencil,
+}
+
 /// Describes an attachment for a color buffer.
 #[derive(Copy, Clone)]
 pub enum ColorAttachment<'a> {
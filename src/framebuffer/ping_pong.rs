@@ -0,0 +1,123 @@
+use crate::backend::Facade;
+use crate::texture::{Texture2d, TextureCreationError, UncompressedFloatFormat, MipmapsOption};
+use crate::TextureExt;
+
+use super::{SimpleFrameBuffer, ValidationError};
+
+/// Two textures of the same dimensions that are alternately used as the input and the output
+/// of a chain of post-processing passes.
+///
+/// Each call to `pass` reads from the texture that contains the result of the previous pass
+/// and draws into the other one, then swaps them. The framebuffer is built again for each pass,
+/// which is cheap as glium caches the underlying framebuffer objects.
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # fn example(display: glium::Display, scene: glium::Texture2d) {
+/// use glium::framebuffer::PingPong;
+///
+/// let mut ping_pong = PingPong::new(&display, 1024, 768).unwrap();
+///
+/// // the first pass reads the scene
+/// ping_pong.pass(|_, output| {
+///     scene.as_surface().fill(&*output, glium::uniforms::MagnifySamplerFilter::Linear);
+/// }).unwrap();
+///
+/// // the next passes read the result of the previous one
+/// for _ in 0 .. 4 {
+///     ping_pong.pass(|input, output| {
+///         // draw on `output` with a program that samples `input`
+///         # let _ = (input, output);
+///     }).unwrap();
+/// }
+///
+/// let result = ping_pong.result();
+/// # }
+/// ```
+pub struct PingPong {
+    textures: [Texture2d; 2],
+    // index of the texture that contains the result of the last pass
+    current: usize,
+}
+
+impl PingPong {
+    /// Builds two empty textures of the given dimensions in the default format.
+    pub fn new<F: ?Sized>(facade: &F, width: u32, height: u32)
+                          -> Result<PingPong, TextureCreationError> where F: Facade
+    {
+        Ok(PingPong::from_textures(Texture2d::empty(facade, width, height)?,
+                                   Texture2d::empty(facade, width, height)?))
+    }
+
+    /// Builds two empty textures of the given dimensions and format, without mipmaps.
+    ///
+    /// Use this to store HDR data, for example with `UncompressedFloatFormat::F16F16F16F16`.
+    pub fn with_format<F: ?Sized>(facade: &F, format: UncompressedFloatFormat, width: u32,
+                                  height: u32) -> Result<PingPong, TextureCreationError>
+                                  where F: Facade
+    {
+        let first = Texture2d::empty_with_format(facade, format, MipmapsOption::NoMipmap,
+                                                 width, height)?;
+        let second = Texture2d::empty_with_format(facade, format, MipmapsOption::NoMipmap,
+                                                  width, height)?;
+        Ok(PingPong::from_textures(first, second))
+    }
+
+    /// Builds a `PingPong` from two existing textures.
+    ///
+    /// The first texture is the input of the first pass.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the textures don't have the same dimensions.
+    pub fn from_textures(first: Texture2d, second: Texture2d) -> PingPong {
+        assert_eq!(first.dimensions(), second.dimensions(),
+                   "The two textures of a PingPong must have the same dimensions");
+
+        PingPong {
+            textures: [first, second],
+            current: 0,
+        }
+    }
+
+    /// Runs a pass that reads from the current result and draws into the other texture,
+    /// then makes the latter the current result.
+    ///
+    /// The closure receives the input texture and a framebuffer whose color attachment is the
+    /// output texture. Its return value is passed through.
+    pub fn pass<R, P>(&mut self, pass: P) -> Result<R, ValidationError>
+                      where P: FnOnce(&Texture2d, &mut SimpleFrameBuffer<'_>) -> R
+    {
+        let input = &self.textures[self.current];
+        let output = &self.textures[1 - self.current];
+
+        let result = {
+            let mut framebuffer = SimpleFrameBuffer::new(output.get_context(), output)?;
+            pass(input, &mut framebuffer)
+        };
+
+        self.current = 1 - self.current;
+        Ok(result)
+    }
+
+    /// Returns the texture that contains the result of the last pass.
+    ///
+    /// Before the first pass, this is the texture that is used as the input of the first pass.
+    #[inline]
+    pub fn result(&self) -> &Texture2d {
+        &self.textures[self.current]
+    }
+
+    /// Returns the dimensions of the textures.
+    #[inline]
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.textures[0].dimensions()
+    }
+
+    /// Destroys the `PingPong` and returns its textures, the current result first.
+    #[inline]
+    pub fn into_textures(self) -> (Texture2d, Texture2d) {
+        let [first, second] = self.textures;
+        if self.current == 0 { (first, second) } else { (second, first) }
+    }
+}
@@ -34,6 +34,21 @@ use crate::version::Api;
 pub enum CreationError {
     /// The requested format is not supported.
     FormatNotSupported,
+
+    /// The width or height exceeds `GL_MAX_RENDERBUFFER_SIZE`.
+    DimensionsNotSupported,
+
+    /// The number of samples is zero, exceeds `GL_MAX_SAMPLES`, or multisample render buffers
+    /// are not supported by the backend.
+    SamplesNotSupported,
+
+    /// Coverage sampling requires `GL_NV_framebuffer_multisample_coverage`, which is not
+    /// supported by the backend.
+    CoverageSamplingNotSupported,
+
+    /// The combination of coverage and color samples is not one of the modes listed in
+    /// `GL_MULTISAMPLE_COVERAGE_MODES_NV`.
+    CoverageModeNotSupported,
 }
 
 impl fmt::Display for CreationError {
@@ -41,6 +56,11 @@ impl fmt::Display for CreationError {
         use self::CreationError::*;
         let desc = match *self {
             FormatNotSupported => "The requested format is not supported",
+            DimensionsNotSupported => "The requested dimensions are not supported",
+            SamplesNotSupported => "The requested number of samples is not supported",
+            CoverageSamplingNotSupported => "Coverage sampling is not supported by the backend",
+            CoverageModeNotSupported =>
+                "The requested combination of coverage and color samples is not supported",
         };
         fmt.write_str(desc)
     }
@@ -70,7 +90,7 @@ impl RenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(RenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height, None)
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height, None)?
         })
     }
     /// Builds a new render buffer with multisampling.
@@ -81,7 +101,28 @@ impl RenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(RenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height, Some(samples))
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height, Some(samples))?
+        })
+    }
+
+    /// Builds a new render buffer with coverage sampling antialiasing (CSAA).
+    ///
+    /// The render buffer stores `color_samples` samples per pixel, but the coverage of the
+    /// primitives is computed with `coverage_samples` samples. This requires the
+    /// `GL_NV_framebuffer_multisample_coverage` extension. All the attachments of a framebuffer
+    /// must use the same numbers of samples.
+    pub fn new_multisample_coverage<F: ?Sized>(facade: &F, format: UncompressedFloatFormat,
+                                               width: u32, height: u32, coverage_samples: u32,
+                                               color_samples: u32)
+                                               -> Result<RenderBuffer, CreationError>
+                                               where F: Facade
+    {
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::UncompressedFloat(format));
+        let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
+
+        Ok(RenderBuffer {
+            buffer: RenderBufferAny::new_coverage(facade, format, TextureKind::Float, width, height,
+                                                  coverage_samples, color_samples)?
         })
     }
 }
@@ -134,7 +175,7 @@ impl DepthRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(DepthRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height, None)
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height, None)?
         })
     }
     /// Builds a new render buffer with multisampling.
@@ -145,7 +186,25 @@ impl DepthRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(DepthRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height, Some(samples))
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height, Some(samples))?
+        })
+    }
+
+    /// Builds a new render buffer with coverage sampling antialiasing (CSAA).
+    ///
+    /// See `RenderBuffer::new_multisample_coverage`.
+    pub fn new_multisample_coverage<F: ?Sized>(facade: &F, format: DepthFormat, width: u32,
+                                               height: u32, coverage_samples: u32,
+                                               color_samples: u32)
+                                               -> Result<DepthRenderBuffer, CreationError>
+                                               where F: Facade
+    {
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::DepthFormat(format));
+        let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
+
+        Ok(DepthRenderBuffer {
+            buffer: RenderBufferAny::new_coverage(facade, format, TextureKind::Depth, width, height,
+                                                  coverage_samples, color_samples)?
         })
     }
 }
@@ -197,7 +256,7 @@ impl StencilRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(StencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Stencil, width, height, None)
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Stencil, width, height, None)?
         })
     }
     /// Builds a new render buffer with multisampling.
@@ -208,7 +267,7 @@ impl StencilRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(StencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::Stencil, width, height, Some(samples))
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Stencil, width, height, Some(samples))?
         })
     }
 }
@@ -261,7 +320,7 @@ impl DepthStencilRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(DepthStencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::DepthStencil, width, height, None)
+            buffer: RenderBufferAny::new(facade, format, TextureKind::DepthStencil, width, height, None)?
         })
     }
     /// Builds a new render buffer with multisampling.
@@ -272,7 +331,7 @@ impl DepthStencilRenderBuffer {
         let format = image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer)?;
 
         Ok(DepthStencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, TextureKind::DepthStencil, width, height, Some(samples))
+            buffer: RenderBufferAny::new(facade, format, TextureKind::DepthStencil, width, height, Some(samples))?
         })
     }
 }
@@ -316,19 +375,32 @@ pub struct RenderBufferAny {
     width: u32,
     height: u32,
     samples: Option<u32>,
+    coverage_samples: Option<u32>,
     kind: TextureKind,
 }
 
 impl RenderBufferAny {
     /// Builds a new render buffer.
     fn new<F: ?Sized>(facade: &F, format: gl::types::GLenum, kind: TextureKind, width: u32, height: u32,
-              samples: Option<u32>) -> RenderBufferAny
+              samples: Option<u32>) -> Result<RenderBufferAny, CreationError>
         where F: Facade
     {
         unsafe {
-            // TODO: check that dimensions don't exceed GL_MAX_RENDERBUFFER_SIZE
             // FIXME: gles2 only supports very few formats
             let mut ctxt = facade.get_context().make_current();
+
+            let max_size = ctxt.capabilities.max_renderbuffer_size.unwrap_or(0) as u32;
+            if width > max_size || height > max_size {
+                return Err(CreationError::DimensionsNotSupported);
+            }
+
+            if let Some(samples) = samples {
+                let max_samples = ctxt.capabilities.max_renderbuffer_samples.unwrap_or(0) as u32;
+                if samples == 0 || samples > max_samples {
+                    return Err(CreationError::SamplesNotSupported);
+                }
+            }
+
             let mut id = 0;
 
             if ctxt.version >= &Version(Api::Gl, 4, 5) ||
@@ -434,14 +506,70 @@ impl RenderBufferAny {
                 unreachable!();
             }
 
-            RenderBufferAny {
+            Ok(RenderBufferAny {
                 context: facade.get_context().clone(),
                 id,
                 width,
                 height,
                 samples,
+                coverage_samples: None,
                 kind,
+            })
+        }
+    }
+
+    /// Builds a new render buffer with `glRenderbufferStorageMultisampleCoverageNV`.
+    fn new_coverage<F: ?Sized>(facade: &F, format: gl::types::GLenum, kind: TextureKind,
+                               width: u32, height: u32, coverage_samples: u32, color_samples: u32)
+                               -> Result<RenderBufferAny, CreationError>
+        where F: Facade
+    {
+        unsafe {
+            let mut ctxt = facade.get_context().make_current();
+
+            if !ctxt.extensions.gl_nv_framebuffer_multisample_coverage {
+                return Err(CreationError::CoverageSamplingNotSupported);
             }
+
+            let max_size = ctxt.capabilities.max_renderbuffer_size.unwrap_or(0) as u32;
+            if width > max_size || height > max_size {
+                return Err(CreationError::DimensionsNotSupported);
+            }
+
+            // the extension only accepts the modes that are listed by the implementation
+            let mut num_modes = 0;
+            ctxt.gl.GetIntegerv(gl::MAX_MULTISAMPLE_COVERAGE_MODES_NV, &mut num_modes);
+            let mut modes = vec![0; 2 * num_modes.max(0) as usize];
+            if !modes.is_empty() {
+                ctxt.gl.GetIntegerv(gl::MULTISAMPLE_COVERAGE_MODES_NV, modes.as_mut_ptr());
+            }
+
+            let requested = [coverage_samples as gl::types::GLint,
+                             color_samples as gl::types::GLint];
+            if !modes.chunks(2).any(|mode| mode == requested) {
+                return Err(CreationError::CoverageModeNotSupported);
+            }
+
+            let mut id = 0;
+            ctxt.gl.GenRenderbuffers(1, &mut id);
+            ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, id);
+            ctxt.state.renderbuffer = id;
+            ctxt.gl.RenderbufferStorageMultisampleCoverageNV(gl::RENDERBUFFER,
+                                                             coverage_samples as gl::types::GLsizei,
+                                                             color_samples as gl::types::GLsizei,
+                                                             format,
+                                                             width as gl::types::GLsizei,
+                                                             height as gl::types::GLsizei);
+
+            Ok(RenderBufferAny {
+                context: facade.get_context().clone(),
+                id,
+                width,
+                height,
+                samples: Some(color_samples),
+                coverage_samples: Some(coverage_samples),
+                kind,
+            })
         }
     }
 
@@ -458,6 +586,13 @@ impl RenderBufferAny {
         self.samples
     }
 
+    /// Returns the number of coverage samples of the render buffer, or `None` if it wasn't
+    /// created with coverage sampling.
+    #[inline]
+    pub fn get_coverage_samples(&self) -> Option<u32> {
+        self.coverage_samples
+    }
+
     /// Returns the context used to create this renderbuffer.
     #[inline]
     pub fn get_context(&self) -> &Rc<Context> {
@@ -106,6 +106,15 @@ pub enum ClientFormat {
     F32F32,
     F32F32F32,
     F32F32F32F32,
+    /// Same as `U8U8U8`, but the components are stored in the blue, green, red order.
+    ///
+    /// Not supported by OpenGL ES.
+    U8U8U8Bgr,
+    /// Same as `U8U8U8U8`, but the components are stored in the blue, green, red, alpha order.
+    ///
+    /// Can't be uploaded with OpenGL ES, and reading this format requires the
+    /// `GL_EXT_read_format_bgra` extension there.
+    U8U8U8U8Bgra,
 }
 
 impl ClientFormat {
@@ -152,6 +161,8 @@ impl ClientFormat {
             ClientFormat::F32F32 => 2 * mem::size_of::<f32>(),
             ClientFormat::F32F32F32 => 3 * mem::size_of::<f32>(),
             ClientFormat::F32F32F32F32 => 4 * mem::size_of::<f32>(),
+            ClientFormat::U8U8U8Bgr => 3 * mem::size_of::<u8>(),
+            ClientFormat::U8U8U8U8Bgra => 4 * mem::size_of::<u8>(),
         }
     }
 
@@ -196,6 +207,8 @@ impl ClientFormat {
             ClientFormat::F32F32 => 2,
             ClientFormat::F32F32F32 => 3,
             ClientFormat::F32F32F32F32 => 4,
+            ClientFormat::U8U8U8Bgr => 3,
+            ClientFormat::U8U8U8U8Bgra => 4,
         }
     }
 }
@@ -2070,6 +2083,12 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                                -> Result<(gl::types::GLenum, gl::types::GLenum),
                                          FormatNotSupportedError>
 {
+    if let ClientFormatAny::ClientFormat(client) = client {
+        if !is_client_format_upload_supported(context, client) {
+            return Err(FormatNotSupportedError);
+        }
+    }
+
     let value = match format {
         TextureFormatRequest::AnyCompressed if client.is_compressed() => {
             match client {
@@ -2165,6 +2184,8 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32) => Ok((gl::RG, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32) => Ok((gl::RGB, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32F32) => Ok((gl::RGBA, gl::FLOAT)),
+                ClientFormatAny::ClientFormat(ClientFormat::U8U8U8Bgr) => Ok((gl::BGR, gl::UNSIGNED_BYTE)),
+                ClientFormatAny::ClientFormat(ClientFormat::U8U8U8U8Bgra) => Ok((gl::BGRA, gl::UNSIGNED_BYTE)),
 
                 // this kind of situation shouldn't happen, it should have a special handling when
                 // client is compressed.
@@ -2216,6 +2237,8 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32) => Ok((gl::RG_INTEGER, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32) => Ok((gl::RGB_INTEGER, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32F32) => Ok((gl::RGBA_INTEGER, gl::FLOAT)),
+                ClientFormatAny::ClientFormat(ClientFormat::U8U8U8Bgr) => Ok((gl::BGR_INTEGER, gl::UNSIGNED_BYTE)),
+                ClientFormatAny::ClientFormat(ClientFormat::U8U8U8U8Bgra) => Ok((gl::BGRA_INTEGER, gl::UNSIGNED_BYTE)),
 
                 // this kind of situation shouldn't happen, it should have a special handling when
                 // client is compressed.
@@ -2262,6 +2285,8 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
             let format = match format {
                 gl::RGB => gl::BGR,
                 gl::RGBA => gl::BGRA,
+                gl::BGR => gl::RGB,
+                gl::BGRA => gl::RGBA,
                 f => return Err(FormatNotSupportedError)
             };
 
@@ -2271,3 +2296,16 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         value
     }
 }
+
+/// Returns true if the client format can be used to upload data with this context.
+///
+/// Desktop OpenGL supports every client format. OpenGL ES doesn't support BGR at all. With
+/// the `GL_EXT_texture_format_BGRA8888` extension, BGRA data can only be uploaded to storage
+/// whose internal format is `GL_BGRA_EXT`, which glium never allocates.
+fn is_client_format_upload_supported(context: &Context, client: ClientFormat) -> bool {
+    if context.get_opengl_version() >= &Version(Api::GlEs, 2, 0) {
+        !matches!(client, ClientFormat::U8U8U8Bgr | ClientFormat::U8U8U8U8Bgra)
+    } else {
+        true
+    }
+}
@@ -60,6 +60,46 @@ impl From<BufferCreationError> for CreationError {
     }
 }
 
+/// Error that can happen while creating an index buffer with `IndexBuffer::from_iter`.
+#[derive(Debug, Copy, Clone)]
+pub enum FromIterError {
+    /// An error happened while creating the index buffer.
+    CreationError(CreationError),
+
+    /// The iterator didn't produce the number of elements given by its `len()`.
+    LengthMismatch,
+}
+
+impl fmt::Display for FromIterError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::FromIterError::*;
+        let desc = match *self {
+            CreationError(_) =>
+                "An error happened while creating the index buffer",
+            LengthMismatch =>
+                "The iterator didn't produce the number of elements given by its length",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for FromIterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::FromIterError::*;
+        match *self {
+            CreationError(ref err) => Some(err),
+            LengthMismatch => None,
+        }
+    }
+}
+
+impl From<CreationError> for FromIterError {
+    #[inline]
+    fn from(err: CreationError) -> FromIterError {
+        FromIterError::CreationError(err)
+    }
+}
+
 /// A list of indices loaded in the graphics card's memory.
 #[derive(Debug)]
 pub struct IndexBuffer<T> where T: Index {
@@ -159,6 +199,44 @@ impl<T> IndexBuffer<T> where T: Index {
         IndexBuffer::empty_impl(facade, prim, len, BufferMode::Immutable)
     }
 
+    /// Builds a new index buffer from an iterator of indices.
+    ///
+    /// The indices are written directly to the buffer without being collected in a `Vec` first.
+    /// Returns `FromIterError::LengthMismatch` if the iterator doesn't produce the number of
+    /// elements given by its `len()`.
+    pub fn from_iter<F: ?Sized, I>(facade: &F, prim: PrimitiveType, iter: I)
+                                   -> Result<IndexBuffer<T>, FromIterError>
+                                   where F: Facade, I: IntoIterator<Item = T>,
+                                         I::IntoIter: ExactSizeIterator
+    {
+        let iter = iter.into_iter();
+        let len = iter.len();
+
+        let mut buffer = IndexBuffer::empty_impl(facade, prim, len, BufferMode::Default)?;
+
+        // mapping an empty range is invalid
+        if len == 0 {
+            return Ok(buffer);
+        }
+
+        {
+            let mut mapping = buffer.buffer.map_write();
+            let mut written = 0;
+            for index in iter {
+                if written == len {
+                    return Err(FromIterError::LengthMismatch);
+                }
+                mapping.set(written, index);
+                written += 1;
+            }
+            if written != len {
+                return Err(FromIterError::LengthMismatch);
+            }
+        }
+
+        Ok(buffer)
+    }
+
     #[inline]
     fn empty_impl<F: ?Sized>(facade: &F, prim: PrimitiveType, len: usize, mode: BufferMode)
                      -> Result<IndexBuffer<T>, CreationError>
@@ -201,6 +279,24 @@ impl<T> IndexBuffer<T> where T: Index {
             }
         })
     }
+
+    /// Replaces the indices starting at `offset` with the content of `data`.
+    ///
+    /// The rest of the buffer is left untouched, which is useful to update only a part of
+    /// a mesh.
+    ///
+    /// # Implementation
+    ///
+    /// Same as `Buffer::write`. Calls `glBufferSubData` for regular buffers.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `offset + data.len()` is superior to the length of the buffer.
+    #[inline]
+    pub fn write_at(&self, offset: usize, data: &[T]) {
+        let end = offset.checked_add(data.len()).expect("Index buffer write out of range");
+        self.buffer.slice(offset .. end).expect("Index buffer write out of range").write(data);
+    }
 }
 
 impl<T> Deref for IndexBuffer<T> where T: Index {
@@ -264,6 +360,29 @@ pub struct IndexBufferSlice<'a, T> where T: Index {
 }
 
 impl<'a, T: 'a> IndexBufferSlice<'a, T> where T: Index {
+    /// Uses a slice of any buffer as indices.
+    ///
+    /// This allows drawing with indices that have been written to a regular buffer, for example
+    /// by a compute shader, without copying them to an `IndexBuffer` first.
+    pub fn from_buffer_slice(buffer: BufferSlice<'a, [T]>, primitives: PrimitiveType)
+                             -> Result<IndexBufferSlice<'a, T>, CreationError>
+    {
+        let context = &**buffer.get_context();
+
+        if !primitives.is_supported(context) {
+            return Err(CreationError::PrimitiveTypeNotSupported);
+        }
+
+        if !T::is_supported(context) {
+            return Err(CreationError::IndexTypeNotSupported);
+        }
+
+        Ok(IndexBufferSlice {
+            buffer,
+            primitives,
+        })
+    }
+
     /// Returns the type of primitives associated with this index buffer.
     #[inline]
     pub fn get_primitives_type(&self) -> PrimitiveType {
@@ -22,6 +22,9 @@ There are two ways to specify the indices that must be used:
  - `NoIndices`, in which case the vertices will be used in the order in which they are in the
    vertex buffer.
 
+In addition to this, `Surface::draw_transform_feedback` draws the vertices that were written by
+a transform feedback session. The number of vertices to draw is determined by the GPU.
+
 ## Multidraw indirect
 
 In addition to indices, you can also use **multidraw indirect** rendering.
@@ -41,6 +44,7 @@ use crate::buffer::BufferAnySlice;
 
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::buffer::CreationError as BufferCreationError;
+pub use self::buffer::FromIterError;
 pub use self::multidraw::{DrawCommandsNoIndicesBuffer, DrawCommandNoIndices};
 pub use self::multidraw::{DrawCommandsIndicesBuffer, DrawCommandIndices};
 
@@ -86,6 +90,13 @@ pub enum IndicesSource<'a> {
         /// Type of primitives contained in the vertex source.
         primitives: PrimitiveType,
     },
+
+    /// Draw the vertices that were written by the last transform feedback session. The number
+    /// of vertices is read by the GPU, without any round-trip to the CPU.
+    TransformFeedback {
+        /// Type of primitives contained in the vertex source.
+        primitives: PrimitiveType,
+    },
 }
 
 impl<'a> IndicesSource<'a> {
@@ -97,8 +108,27 @@ impl<'a> IndicesSource<'a> {
             IndicesSource::MultidrawArray { primitives, .. } => primitives,
             IndicesSource::MultidrawElement { primitives, .. } => primitives,
             IndicesSource::NoIndices { primitives } => primitives,
+            IndicesSource::TransformFeedback { primitives } => primitives,
         }
     }
+
+    /// Returns the same source of indices, but with another type of primitives.
+    #[inline]
+    pub(crate) fn with_primitives_type(mut self, primitives_type: PrimitiveType)
+                                       -> IndicesSource<'a>
+    {
+        match self {
+            IndicesSource::IndexBuffer { ref mut primitives, .. } |
+            IndicesSource::MultidrawArray { ref mut primitives, .. } |
+            IndicesSource::MultidrawElement { ref mut primitives, .. } |
+            IndicesSource::NoIndices { ref mut primitives } |
+            IndicesSource::TransformFeedback { ref mut primitives } => {
+                *primitives = primitives_type;
+            },
+        }
+
+        self
+    }
 }
 
 /// List of available primitives.
@@ -214,6 +244,29 @@ impl PrimitiveType {
             },
         }
     }
+
+    /// Returns true if `count` vertices or indices form a valid list of primitives of this type.
+    ///
+    /// Only the adjacency primitive types are checked, as they are the only ones for which
+    /// OpenGL silently drops the vertices passed to the geometry shader. An empty list is
+    /// always valid.
+    pub fn is_valid_count(&self, count: usize) -> bool {
+        if count == 0 {
+            return true;
+        }
+
+        match self {
+            // each line is made of 4 vertices
+            PrimitiveType::LinesListAdjacency => count.is_multiple_of(4),
+            // the first and the last vertices are only used as adjacency information
+            PrimitiveType::LineStripAdjacency => count >= 4,
+            // each triangle is made of 6 vertices
+            PrimitiveType::TrianglesListAdjacency => count.is_multiple_of(6),
+            // `2 * n + 4` vertices for `n` triangles
+            PrimitiveType::TriangleStripAdjacency => count >= 6 && count.is_multiple_of(2),
+            _ => true,
+        }
+    }
 }
 
 impl ToGlEnum for PrimitiveType {
@@ -111,7 +111,9 @@ extern crate lazy_static;
 
 #[cfg(feature = "glutin")]
 pub use crate::backend::glutin::glutin;
-pub use crate::context::{Profile, UuidError};
+pub use crate::context::{FeatureReport, GlStateSnapshot, Profile, ResetStatus, UuidError};
+#[cfg(feature = "debug_state_cache")]
+pub use crate::context::StateMismatch;
 pub use crate::draw_parameters::{Blend, BlendingFunction, LinearBlendingFactor, BackfaceCullingMode};
 pub use crate::draw_parameters::{Depth, DepthTest, PolygonMode, DrawParameters, StencilTest, StencilOperation};
 pub use crate::draw_parameters::{Smooth};
@@ -122,7 +124,7 @@ pub use crate::program::ProgramCreationError::{CompilationError, LinkingError, S
 pub use crate::sync::{LinearSyncFence, SyncFence};
 pub use crate::texture::Texture2d;
 pub use crate::version::{Api, Version, get_supported_glsl_version};
-pub use crate::ops::ReadError;
+pub use crate::ops::{BlitError, ClearError, ReadError};
 
 use std::rc::Rc;
 use std::thread;
@@ -142,9 +144,12 @@ mod macros;
 pub mod backend;
 pub mod buffer;
 pub mod debug;
+pub mod draw_batch;
 pub mod draw_parameters;
+pub mod frame_timer;
 pub mod framebuffer;
 pub mod index;
+pub mod matrix;
 pub mod memory_object;
 pub mod pixel_buffer;
 pub mod program;
@@ -152,6 +157,8 @@ pub mod uniforms;
 pub mod vertex;
 pub mod semaphore;
 pub mod texture;
+pub mod tiled_renderer;
+pub mod wireframe;
 pub mod field;
 
 mod context;
@@ -401,6 +408,9 @@ trait TextureMipmapExt {
 
 /// Internal trait for transform feedback sessions.
 trait TransformFeedbackSessionExt {
+    /// Checks that the session can be used to draw with this program.
+    fn check_program(&self, _: &program::Program) -> Result<(), DrawError>;
+
     /// Updates the state of OpenGL to make the transform feedback session current.
     ///
     /// The second parameter must be the primitive type of the input vertex data.
@@ -411,6 +421,10 @@ trait TransformFeedbackSessionExt {
 
     /// Ensures that a buffer isn't used by transform feedback.
     fn ensure_buffer_out_of_transform_feedback(_: &mut CommandContext<'_>, _: gl::types::GLuint);
+
+    /// Returns the query that counts the primitives written by the session, if any.
+    fn get_primitives_written_query(&self)
+                                    -> Option<&draw_parameters::TransformFeedbackPrimitivesWrittenQuery>;
 }
 
 /// Internal trait for uniforms handling.
@@ -781,14 +795,56 @@ impl ToGlEnum for BlitMask {
 ///
 pub trait Surface {
     /// Clears some attachments of the target.
+    ///
+    /// Only the attachments whose value is `Some` are cleared. If `rect` is `Some`, only this
+    /// region is cleared, by enabling the scissor test for this operation. Otherwise the whole
+    /// target is cleared: the `scissor` of the `DrawParameters` used by previous draw commands
+    /// doesn't apply, as each draw command sets its own scissor box.
+    ///
+    /// The `color_mask` of the `DrawParameters` used by previous draw commands doesn't apply
+    /// to clear operations: all the color components are cleared. Use `clear_with_color_mask`
+    /// to keep some components untouched.
     fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
              depth: Option<f32>, stencil: Option<i32>);
 
+    /// Clears some attachments of the target, but only writes the color components whose
+    /// value in `color_mask` is `true`.
+    ///
+    /// The components of `color_mask` are in order: red, green, blue, alpha. The depth and
+    /// stencil attachments are not affected by the color mask.
+    ///
+    /// All the surfaces of glium support this. The default implementation, for other surfaces,
+    /// calls `clear` if no color component is masked, and otherwise returns
+    /// `ClearError::ColorMaskNotSupported` without clearing anything.
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        if color.is_some() && color_mask != (true, true, true, true) {
+            return Err(ClearError::ColorMaskNotSupported);
+        }
+
+        self.clear(rect, color, color_srgb, depth, stencil);
+        Ok(())
+    }
+
     /// Clears the color attachment of the target.
     fn clear_color(&mut self, red: f32, green: f32, blue: f32, alpha: f32) {
         self.clear(None, Some((red, green, blue, alpha)), false, None, None);
     }
 
+    /// Clears the color attachment of the target, but only writes the color components whose
+    /// value in `color_mask` is `true`.
+    ///
+    /// This is useful for example to reset the alpha channel of an accumulation buffer without
+    /// touching the color channels.
+    fn clear_color_masked(&mut self, red: f32, green: f32, blue: f32, alpha: f32,
+                          color_mask: (bool, bool, bool, bool)) -> Result<(), ClearError>
+    {
+        self.clear_with_color_mask(None, Some((red, green, blue, alpha)), false, color_mask,
+                                   None, None)
+    }
+
     /// Clears the color attachment of the target. The color is in sRGB format.
     fn clear_color_srgb(&mut self, red: f32, green: f32, blue: f32, alpha: f32) {
         self.clear(None, Some((red, green, blue, alpha)), true, None, None);
@@ -805,6 +861,9 @@ pub trait Surface {
     }
 
     /// Clears the color and depth attachments of the target.
+    ///
+    /// Both attachments are cleared with a single `glClear` call, which is cheaper than calling
+    /// `clear_color` and `clear_depth` one after the other.
     fn clear_color_and_depth(&mut self, color: (f32, f32, f32, f32), depth: f32) {
         self.clear(None, Some(color), false, Some(depth), None);
     }
@@ -862,6 +921,25 @@ pub trait Surface {
         self.get_stencil_buffer_bits().is_some()
     }
 
+    /// Reads the content of the stencil buffer in the given rectangle.
+    ///
+    /// The values are returned row by row, starting with the bottom row. Returns
+    /// `ReadError::NoStencilBuffer` if the surface doesn't have a stencil buffer.
+    ///
+    /// Note that OpenGL ES only supports reading the stencil buffer with the
+    /// `GL_NV_read_stencil` extension. Reading the stencil buffer of the default framebuffer
+    /// also requires the window to have been created with one, which is not always the case.
+    /// Render to a framebuffer object with a stencil attachment if you need this to work
+    /// everywhere.
+    ///
+    /// All the surfaces of glium support this. The default implementation, for other surfaces,
+    /// returns `ReadError::AttachmentTypeNotSupported`.
+    #[inline]
+    fn read_stencil(&self, rect: &Rect) -> Result<Vec<u8>, ReadError> {
+        let _ = rect;
+        Err(ReadError::AttachmentTypeNotSupported)
+    }
+
     /// Draws.
     ///
     /// This is probably the most complex function of glium. Check out the rest of the
@@ -873,6 +951,77 @@ pub trait Surface {
         V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
         U: uniforms::Uniforms;
 
+    /// Draws `instance_count` instances of `vertex_count` vertices without any vertex
+    /// attribute.
+    ///
+    /// The vertex shader is expected to compute everything from `gl_VertexID` and
+    /// `gl_InstanceID`, for example by indexing into a uniform or shader storage buffer. This
+    /// is equivalent to calling `draw` with `vertex::EmptyVertexAttributes`,
+    /// `vertex::EmptyInstanceAttributes` and `index::NoIndices`.
+    ///
+    /// Returns `DrawError::UnexpectedAttribute` if the program has vertex attributes.
+    fn draw_instanced_empty<U>(&mut self, program: &Program, vertex_count: usize,
+                               instance_count: usize, primitive: index::PrimitiveType,
+                               uniforms: &U, draw_parameters: &DrawParameters<'_>)
+                               -> Result<(), DrawError> where U: uniforms::Uniforms
+    {
+        if let Some((name, _)) = program.attributes().next() {
+            return Err(DrawError::UnexpectedAttribute { name: name.clone() });
+        }
+
+        self.draw((vertex::EmptyVertexAttributes { len: vertex_count },
+                   vertex::EmptyInstanceAttributes { len: instance_count }),
+                  index::NoIndices(primitive), program, uniforms, draw_parameters)
+    }
+
+    /// Draws the same vertices several times, with a different source of indices and primitive
+    /// type each time.
+    ///
+    /// This is useful when a vertex buffer contains primitives of different types, for example
+    /// triangles and lines in a user interface. Each element of `indices` is drawn with the
+    /// primitive type that accompanies it, which overrides the one of the source. For example
+    /// you can pass two slices of the same index buffer.
+    ///
+    /// The draws are issued one after the other with the same parameters. glium's surfaces
+    /// bind the framebuffer, the program and the uniforms once before the first draw, and only
+    /// rebind the vertex buffers when the index buffer changes. The default implementation, which
+    /// is used for surfaces that are implemented outside of glium, calls `draw` for each element
+    /// of `indices`. Stops at the first error.
+    fn draw_multi<'a, 'b, V, U>(&mut self, vertex_buffer: V,
+                                indices: &[(index::IndicesSource<'a>, index::PrimitiveType)],
+                                program: &Program, uniforms: &U,
+                                draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                                where V: vertex::MultiVerticesSource<'b> + Copy,
+                                      U: uniforms::Uniforms
+    {
+        for (source, primitives) in indices {
+            self.draw(vertex_buffer, source.clone().with_primitives_type(*primitives), program,
+                      uniforms, draw_parameters)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the vertices that were written by the last transform feedback session.
+    ///
+    /// `vertex_buffer` is usually the buffer that the session has written to. Contrary to
+    /// `draw`, the number of vertices isn't known by glium. Instead the GPU draws exactly the
+    /// number of vertices that were captured, which avoids reading it back on the CPU. The
+    /// session must have been destroyed before drawing.
+    ///
+    /// Returns `DrawError::DrawTransformFeedbackNotSupported` if the backend doesn't support
+    /// OpenGL 4.0 or `GL_ARB_transform_feedback2`.
+    fn draw_transform_feedback<'b, V, U>(&mut self, vertex_buffer: V,
+                                         primitive: index::PrimitiveType, program: &Program,
+                                         uniforms: &U, draw_parameters: &DrawParameters<'_>)
+                                         -> Result<(), DrawError>
+                                         where V: vertex::MultiVerticesSource<'b>,
+                                               U: uniforms::Uniforms
+    {
+        self.draw(vertex_buffer, index::IndicesSource::TransformFeedback { primitives: primitive },
+                  program, uniforms, draw_parameters)
+    }
+
     /// Blits from the default framebuffer.
     #[inline]
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
@@ -917,6 +1066,53 @@ pub trait Surface {
                                                  filter: uniforms::MagnifySamplerFilter,
                                                  mask: BlitMask);
 
+    /// Same as `blit_buffers_from_frame`, but also enables or disables `GL_FRAMEBUFFER_SRGB`
+    /// while copying the color buffer.
+    ///
+    /// When enabled, the colors are converted if the source and the target don't have the same
+    /// sRGB-ness, for example when resolving an sRGB multisampled buffer to a linear target.
+    /// When disabled, the values are copied untouched. Some drivers ignore this and always
+    /// perform the conversion. The `blit_buffers_from_*` methods that don't take this parameter
+    /// keep the current state.
+    ///
+    /// The default implementation, which is used for surfaces that are implemented outside of
+    /// glium, ignores `srgb`.
+    #[inline]
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask,
+                                         _srgb: bool)
+    {
+        self.blit_buffers_from_frame(source_rect, target_rect, filter, mask)
+    }
+
+    /// Same as `blit_buffers_from_simple_framebuffer`, but also enables or disables
+    /// `GL_FRAMEBUFFER_SRGB` while copying the color buffer. See
+    /// `blit_buffers_from_frame_with_srgb`.
+    #[inline]
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self,
+                                                      source: &framebuffer::SimpleFrameBuffer<'_>,
+                                                      source_rect: &Rect, target_rect: &BlitTarget,
+                                                      filter: uniforms::MagnifySamplerFilter,
+                                                      mask: BlitMask, _srgb: bool)
+    {
+        self.blit_buffers_from_simple_framebuffer(source, source_rect, target_rect, filter, mask)
+    }
+
+    /// Same as `blit_buffers_from_multioutput_framebuffer`, but also enables or disables
+    /// `GL_FRAMEBUFFER_SRGB` while copying the color buffer. See
+    /// `blit_buffers_from_frame_with_srgb`.
+    #[inline]
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self,
+                                                           source: &framebuffer::MultiOutputFrameBuffer<'_>,
+                                                           source_rect: &Rect,
+                                                           target_rect: &BlitTarget,
+                                                           filter: uniforms::MagnifySamplerFilter,
+                                                           mask: BlitMask, _srgb: bool)
+    {
+        self.blit_buffers_from_multioutput_framebuffer(source, source_rect, target_rect, filter,
+                                                       mask)
+    }
+
 
     /// Copies a rectangle of pixels from this surface to another surface.
     ///
@@ -929,10 +1125,37 @@ pub trait Surface {
     /// rectangles overlap, then the behavior is undefined.
     ///
     /// Note that there is no alpha blending, depth/stencil checking, etc. This function just
-    /// copies pixels.
+    /// copies pixels. No sRGB conversion is requested either; use one of the
+    /// `blit_buffers_from_*_with_srgb` methods if you need it.
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface;
 
+    /// Same as `blit_color`, but checks beforehand that the blit is valid for the source.
+    ///
+    /// OpenGL only allows `MagnifySamplerFilter::Nearest` when the color buffer that is read
+    /// from has an integral or unsigned format. Using `Linear` with such a format returns
+    /// `BlitError::LinearFilterOnIntegralFormat` and nothing is copied. If the source is
+    /// multisampled, the source and target rectangles must be identical, otherwise
+    /// `BlitError::MultisampledSourceRectMismatch` is returned.
+    ///
+    /// This maps directly to `glBlitFramebuffer`. The target rectangle can have a negative width
+    /// or height, in which case the image is flipped. For example a `target_rect` with a
+    /// `bottom` equal to the height of the target and a `height` equal to minus this height
+    /// flips the image vertically.
+    ///
+    /// The color buffer of the default framebuffer never has an integral format. The default
+    /// implementation, which is also used for surfaces that are implemented outside of glium,
+    /// calls `blit_color` without any check.
+    #[inline]
+    fn blit_color_with_filter<S>(&self, source_rect: &Rect, target: &S,
+                                 target_rect: &BlitTarget,
+                                 filter: uniforms::MagnifySamplerFilter)
+                                 -> Result<(), BlitError> where S: Surface
+    {
+        self.blit_color(source_rect, target, target_rect, filter);
+        Ok(())
+    }
+
 
     /// Copies the entire surface to a target surface. See `blit_color`.
     #[inline]
@@ -1057,9 +1280,20 @@ pub enum DrawError {
     /// You requested not to draw primitives, but this is not supported by the backend.
     TransformFeedbackNotSupported,
 
+    /// The transform feedback session was created with a different program than the one
+    /// used to draw.
+    TransformFeedbackProgramMismatch,
+
+    /// The transform feedback session doesn't have one buffer for each transform feedback
+    /// buffer of the program.
+    TransformFeedbackBuffersMismatch,
+
     /// See the documentation of the `draw_parameters` module for infos.
     WrongQueryOperation,
 
+    /// You requested conditional rendering, but this is not supported by the backend.
+    ConditionalRenderingNotSupported,
+
     /// You requested smoothing, but this is not supported by the backend.
     SmoothingNotSupported,
 
@@ -1078,14 +1312,80 @@ pub enum DrawError {
     /// Restarting indices (multiple objects per draw call) is not supported by the backend.
     FixedIndexRestartingNotSupported,
 
+    /// Restarting indices at a custom index is not supported by the backend.
+    CustomIndexRestartingNotSupported,
+
+    /// The custom primitive restart index doesn't fit in the type of the indices.
+    PrimitiveRestartIndexOutOfRange,
+
+    /// Blending per draw buffer is not supported by the backend, or more blending effects
+    /// than the number of draw buffers supported by the backend have been passed.
+    IndexedBlendingNotSupported,
+
     /// Changing the clip volume definition (origin and depth mode) is not supported by the backend.
     ClipControlNotSupported,
 
+    /// Drawing the vertices written by a transform feedback session is not supported by the
+    /// backend.
+    DrawTransformFeedbackNotSupported,
+
     /// Tried to enable a clip plane that does not exist.
     ClipPlaneIndexOutOfBounds,
 
     /// Tried to use too many image units simultaneously
     InsufficientImageUnits,
+
+    /// Tried to use more shader storage blocks than the number of bindings supported by the
+    /// backend, or the backend doesn't support shader storage buffers.
+    InsufficientShaderStorageBufferBindings,
+
+    /// A subroutine has been selected, but subroutines are not supported by the backend.
+    ///
+    /// Subroutines require OpenGL 4.0 or the `GL_ARB_shader_subroutine` extension.
+    SubroutinesNotSupported,
+
+    /// The number of indices, or of vertices if there is no index, doesn't match what the
+    /// adjacency primitive type requires.
+    ///
+    /// For example `TrianglesListAdjacency` requires a multiple of 6 indices.
+    InvalidAdjacencyIndicesCount,
+
+    /// The type of primitives doesn't match the input layout of the geometry shader.
+    ///
+    /// For example a geometry shader with `layout(triangles_adjacency) in;` can only be used
+    /// with `TrianglesListAdjacency` or `TriangleStripAdjacency`.
+    GeometryShaderInputMismatch,
+
+    /// Conservative rasterization has been requested, but is not supported by the backend.
+    ConservativeRasterizationNotSupported,
+
+    /// Tried to bind a multisample texture with a sampler. Multisample textures can only be
+    /// read with `texelFetch` and don't support filtering or mipmaps.
+    SamplerWithMultisampleTexture {
+        /// Name of the uniform you are trying to bind.
+        name: String,
+    },
+
+    /// An attribute of the program is provided by several vertices sources, for example by
+    /// both the per-vertex buffer and the per-instance buffer.
+    VertexAttributeCollision {
+        /// Name of the attribute in the vertex format.
+        name: String,
+    },
+
+    /// The program has a vertex attribute, but was used to draw without any vertex source.
+    ///
+    /// See `Surface::draw_instanced_empty`.
+    UnexpectedAttribute {
+        /// Name of one of the attributes of the program.
+        name: String,
+    },
+
+    /// A uniform has been set at a negative location other than -1.
+    InvalidUniformLocation {
+        /// The location of the uniform.
+        location: i32,
+    },
 }
 
 impl Error for DrawError {
@@ -1141,8 +1441,14 @@ impl fmt::Display for DrawError {
                 "If you don't use indices, then all vertices sources must have the same size",
             TransformFeedbackNotSupported =>
                 "Requested not to draw primitives, but this is not supported by the backend",
+            TransformFeedbackProgramMismatch =>
+                "The transform feedback session was created with a different program",
+            TransformFeedbackBuffersMismatch =>
+                "The transform feedback session doesn't have a buffer for each varying buffer of the program",
             WrongQueryOperation =>
                 "Wrong query operation",
+            ConditionalRenderingNotSupported =>
+                "Trying to use conditional rendering, but this is not supported by the backend",
             SmoothingNotSupported =>
                 "Trying to use smoothing, but this is not supported by the backend",
             ProvokingVertexNotSupported =>
@@ -1155,12 +1461,38 @@ impl fmt::Display for DrawError {
                 "One the blending parameters is not supported by the backend",
             FixedIndexRestartingNotSupported =>
                 "Restarting indices (multiple objects per draw call) is not supported by the backend",
+            CustomIndexRestartingNotSupported =>
+                "Restarting indices at a custom index is not supported by the backend",
+            PrimitiveRestartIndexOutOfRange =>
+                "The custom primitive restart index doesn't fit in the type of the indices",
+            IndexedBlendingNotSupported =>
+                "Blending per draw buffer is not supported by the backend",
             ClipControlNotSupported =>
                 "Changing the clip volume definition (origin and depth mode) is not supported by the backend",
+            DrawTransformFeedbackNotSupported =>
+                "Drawing the vertices written by a transform feedback session is not supported by the backend",
             ClipPlaneIndexOutOfBounds =>
                 "Tried to enable a clip plane that does not exist.",
             InsufficientImageUnits =>
                 "Tried to use more image uniforms that the implementation has support for",
+            InsufficientShaderStorageBufferBindings =>
+                "Tried to use more shader storage blocks that the implementation has support for",
+            SubroutinesNotSupported =>
+                "Subroutines are not supported by the backend",
+            InvalidAdjacencyIndicesCount =>
+                "The number of indices doesn't match the requirements of the adjacency primitive type",
+            GeometryShaderInputMismatch =>
+                "The type of primitives doesn't match the input layout of the geometry shader",
+            ConservativeRasterizationNotSupported =>
+                "Conservative rasterization is not supported by the backend",
+            SamplerWithMultisampleTexture { .. } =>
+                "Multisample textures can't be bound with a sampler",
+            VertexAttributeCollision { .. } =>
+                "An attribute of the program is provided by several vertices sources",
+            UnexpectedAttribute { .. } =>
+                "The program has a vertex attribute but no vertex source was provided",
+            InvalidUniformLocation { .. } =>
+                "Tried to set a uniform at a negative location",
         };
         match self {
             UniformTypeMismatch { ref name, ref expected } =>
@@ -1185,6 +1517,34 @@ impl fmt::Display for DrawError {
                     desc,
                     name,
                 ),
+            SamplerWithMultisampleTexture { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
+            VertexAttributeCollision { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
+            UnexpectedAttribute { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
+            InvalidUniformLocation { location } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    location,
+                ),
             UniformBlockLayoutMismatch { name, err } =>
                 write!(
                     fmt,
@@ -1259,6 +1619,9 @@ impl Frame {
 
     /// Stop drawing, swap the buffers, and consume the Frame.
     ///
+    /// The buffers are not swapped if `Context::set_frame_swaps_buffers` has been called with
+    /// `false`.
+    ///
     /// See the documentation of `SwapBuffersError` about what is being returned.
     #[inline]
     pub fn finish(mut self) -> Result<(), SwapBuffersError> {
@@ -1276,7 +1639,69 @@ impl Frame {
         }
 
         self.destroyed = true;
-        self.context.swap_buffers()
+
+        if self.context.frame_swaps_buffers() {
+            self.context.swap_buffers()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Tells the backend that the content of the depth and stencil buffers of the frame is
+    /// no longer needed.
+    ///
+    /// Call this once you have drawn the scene and before drawing anything that doesn't use
+    /// the depth and stencil buffers, like the user interface. On tile-based GPUs, like the ones
+    /// of mobile devices, this avoids writing these buffers back to memory. Their content is
+    /// undefined until you clear them.
+    ///
+    /// See `SimpleFrameBuffer::invalidate`.
+    #[inline]
+    pub fn invalidate_depth_stencil(&self) {
+        ops::invalidate(&self.context, None,
+                        &[framebuffer::Attachment::Depth, framebuffer::Attachment::Stencil], None);
+    }
+
+    /// Chooses the buffer of the default framebuffer to draw upon, for example the back buffer
+    /// of the right eye for stereoscopic rendering.
+    ///
+    /// See `Context::set_default_draw_buffer`.
+    #[inline]
+    pub fn set_draw_buffer(&mut self, buffer: framebuffer::DefaultFramebufferAttachment)
+                           -> Result<(), framebuffer::DrawBufferError>
+    {
+        self.context.set_default_draw_buffer(buffer)
+    }
+
+    /// Makes the next draw commands on this frame draw the image of the given eye.
+    ///
+    /// This requires a context that has been created with stereoscopy, see
+    /// `Context::is_stereo`. Draw the scene once for each eye before calling `finish`.
+    #[inline]
+    pub fn draw_eye(&mut self, eye: framebuffer::Eye) -> Result<(), framebuffer::DrawBufferError> {
+        self.set_draw_buffer(eye.back_buffer())
+    }
+
+    /// Reads the content of the buffer being drawn into an `image::RgbaImage`, for example to
+    /// take a screenshot before calling `finish`.
+    ///
+    /// The rows are flipped so that the first row of the image is the top of the frame, which
+    /// means that the result can be saved directly.
+    ///
+    /// Only available if the `image` feature is enabled.
+    #[cfg(feature = "image")]
+    pub fn read_to_image(&self) -> Result<image::RgbaImage, ReadError> {
+        use crate::texture::Texture2dDataSink;
+
+        let mut ctxt = self.context.make_current();
+        let rect = Rect { left: 0, bottom: 0, width: self.dimensions.0, height: self.dimensions.1 };
+        let buffer = ctxt.state.default_framebuffer_draw.unwrap_or(gl::BACK_LEFT);
+
+        let mut data = Vec::with_capacity(0);
+        ops::read(&mut ctxt, ops::Source::DefaultFramebuffer(buffer), &rect, &mut data, false)?;
+        Ok(<image::RgbaImage as Texture2dDataSink<_>>::from_raw(std::borrow::Cow::Owned(data),
+                                                                self.dimensions.0,
+                                                                self.dimensions.1))
     }
 }
 
@@ -1285,7 +1710,17 @@ impl Surface for Frame {
     fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
              depth: Option<f32>, stencil: Option<i32>)
     {
-        ops::clear(&self.context, None, rect, color, color_srgb, depth, stencil);
+        ops::clear(&self.context, None, rect, color, color_srgb, (true, true, true, true), depth,
+                   stencil);
+    }
+
+    #[inline]
+    fn clear_with_color_mask(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+                             color_srgb: bool, color_mask: (bool, bool, bool, bool),
+                             depth: Option<f32>, stencil: Option<i32>) -> Result<(), ClearError>
+    {
+        ops::clear(&self.context, None, rect, color, color_srgb, color_mask, depth, stencil);
+        Ok(())
     }
 
     fn get_dimensions(&self) -> (u32, u32) {
@@ -1300,6 +1735,11 @@ impl Surface for Frame {
         self.context.capabilities().stencil_bits
     }
 
+    #[inline]
+    fn read_stencil(&self, rect: &Rect) -> Result<Vec<u8>, ReadError> {
+        ops::read_stencil(&self.context, None, rect)
+    }
+
     fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
                          index_buffer: I, program: &Program, uniforms: &U,
                          draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
@@ -1329,6 +1769,36 @@ impl Surface for Frame {
                   uniforms, draw_parameters, self.dimensions)
     }
 
+    fn draw_multi<'a, 'b, V, U>(&mut self, vertex_buffer: V,
+                                indices: &[(index::IndicesSource<'a>, index::PrimitiveType)],
+                                program: &Program, uniforms: &U,
+                                draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+                                where V: vertex::MultiVerticesSource<'b> + Copy,
+                                      U: uniforms::Uniforms
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw_multi(&self.context, None, vertex_buffer, indices, program,
+                        uniforms, draw_parameters, self.dimensions)
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -1338,7 +1808,14 @@ impl Surface for Frame {
 
     fn blit_buffers_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask) {
         ops::blit(&self.context, None, self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    fn blit_buffers_from_frame_with_srgb(&self, source_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter, mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     fn blit_buffers_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer<'_>,
@@ -1346,7 +1823,17 @@ impl Surface for Frame {
                                             filter: uniforms::MagnifySamplerFilter,
                                             mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    fn blit_buffers_from_simple_framebuffer_with_srgb(&self, source: &framebuffer::SimpleFrameBuffer<'_>,
+                                                      source_rect: &Rect, target_rect: &BlitTarget,
+                                                      filter: uniforms::MagnifySamplerFilter,
+                                                      mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 
     fn blit_buffers_from_multioutput_framebuffer(&self,
@@ -1355,7 +1842,18 @@ impl Surface for Frame {
                                                  filter: uniforms::MagnifySamplerFilter,
                                                  mask: BlitMask) {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  mask.to_glenum(), source_rect, target_rect, filter.to_glenum())
+                  mask.to_glenum(), None, source_rect, target_rect,
+                  filter.to_glenum())
+    }
+
+    fn blit_buffers_from_multioutput_framebuffer_with_srgb(&self,
+                                                           source: &framebuffer::MultiOutputFrameBuffer<'_>,
+                                                           source_rect: &Rect, target_rect: &BlitTarget,
+                                                           filter: uniforms::MagnifySamplerFilter,
+                                                           mask: BlitMask, srgb: bool) {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask.to_glenum(), Some(srgb), source_rect, target_rect,
+                  filter.to_glenum())
     }
 }
 
@@ -1388,20 +1886,76 @@ impl fmt::Display for IncompatibleOpenGl {
 
 impl Error for IncompatibleOpenGl {}
 
+/// Error returned by `glGetError`.
+///
+/// See `Context::take_errors`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GlError {
+    /// `GL_INVALID_ENUM`
+    InvalidEnum,
+    /// `GL_INVALID_VALUE`
+    InvalidValue,
+    /// `GL_INVALID_OPERATION`
+    InvalidOperation,
+    /// `GL_INVALID_FRAMEBUFFER_OPERATION`
+    InvalidFramebufferOperation,
+    /// `GL_OUT_OF_MEMORY`
+    OutOfMemory,
+    /// `GL_STACK_UNDERFLOW`
+    StackUnderflow,
+    /// `GL_STACK_OVERFLOW`
+    StackOverflow,
+    /// `GL_CONTEXT_LOST`
+    ContextLost,
+    /// A value that isn't defined by the OpenGL specifications.
+    Unknown(gl::types::GLenum),
+}
+
+impl GlError {
+    /// Turns the return value of `glGetError` into a `GlError`, or `None` for `GL_NO_ERROR`.
+    #[inline]
+    fn from_gl_enum(value: gl::types::GLenum) -> Option<GlError> {
+        match value {
+            gl::NO_ERROR => None,
+            gl::INVALID_ENUM => Some(GlError::InvalidEnum),
+            gl::INVALID_VALUE => Some(GlError::InvalidValue),
+            gl::INVALID_OPERATION => Some(GlError::InvalidOperation),
+            gl::INVALID_FRAMEBUFFER_OPERATION => Some(GlError::InvalidFramebufferOperation),
+            gl::OUT_OF_MEMORY => Some(GlError::OutOfMemory),
+            gl::STACK_UNDERFLOW => Some(GlError::StackUnderflow),
+            gl::STACK_OVERFLOW => Some(GlError::StackOverflow),
+            gl::CONTEXT_LOST => Some(GlError::ContextLost),
+            value => Some(GlError::Unknown(value)),
+        }
+    }
+
+    #[inline]
+    fn desc(&self) -> &'static str {
+        match *self {
+            GlError::InvalidEnum => "GL_INVALID_ENUM",
+            GlError::InvalidValue => "GL_INVALID_VALUE",
+            GlError::InvalidOperation => "GL_INVALID_OPERATION",
+            GlError::InvalidFramebufferOperation => "GL_INVALID_FRAMEBUFFER_OPERATION",
+            GlError::OutOfMemory => "GL_OUT_OF_MEMORY",
+            GlError::StackUnderflow => "GL_STACK_UNDERFLOW",
+            GlError::StackOverflow => "GL_STACK_OVERFLOW",
+            GlError::ContextLost => "GL_CONTEXT_LOST",
+            GlError::Unknown(_) => "Unknown glGetError return value",
+        }
+    }
+}
+
+impl fmt::Display for GlError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.desc())
+    }
+}
+
+impl Error for GlError {}
+
 #[allow(dead_code)]
 #[inline]
 fn get_gl_error(ctxt: &mut context::CommandContext<'_>) -> Option<&'static str> {
-    match unsafe { ctxt.gl.GetError() } {
-        gl::NO_ERROR => None,
-        gl::INVALID_ENUM => Some("GL_INVALID_ENUM"),
-        gl::INVALID_VALUE => Some("GL_INVALID_VALUE"),
-        gl::INVALID_OPERATION => Some("GL_INVALID_OPERATION"),
-        gl::INVALID_FRAMEBUFFER_OPERATION => Some("GL_INVALID_FRAMEBUFFER_OPERATION"),
-        gl::OUT_OF_MEMORY => Some("GL_OUT_OF_MEMORY"),
-        gl::STACK_UNDERFLOW => Some("GL_STACK_UNDERFLOW"),
-        gl::STACK_OVERFLOW => Some("GL_STACK_OVERFLOW"),
-        gl::CONTEXT_LOST => Some("GL_CONTEXT_LOST"),
-        _ => Some("Unknown glGetError return value")
-    }
+    GlError::from_gl_enum(unsafe { ctxt.gl.GetError() }).map(|err| err.desc())
 }
 
@@ -0,0 +1,339 @@
+/*!
+Helpers to build the most common transformation matrices.
+
+Glium isn't a math library, but almost every program needs a projection matrix and a view
+matrix. The functions of this module build them without requiring an additional dependency.
+
+All the matrices are column-major `[[f32; 4]; 4]` arrays, which means that they can be passed
+directly as a `mat4` uniform. They follow the usual OpenGL conventions: the coordinate system
+is right-handed, the camera looks towards negative Z, and the depth is mapped to the
+`-1.0 .. 1.0` range. They are equivalent to the matrices produced by `gluPerspective`,
+`glOrtho` and `gluLookAt`.
+
+```
+let projection = glium::matrix::perspective(std::f32::consts::FRAC_PI_3, 4.0 / 3.0, 0.1, 100.0);
+let view = glium::matrix::look_at([0.0, 1.0, 5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+
+let uniforms = glium::uniform! {
+    projection: projection,
+    view: view,
+};
+# let _ = uniforms;
+```
+
+*/
+
+/// Builds a perspective projection matrix.
+///
+/// `fov_y` is the vertical field of view in radians, and `aspect_ratio` is the width of the
+/// viewport divided by its height. `near` and `far` are the distances to the clipping planes
+/// and must be strictly positive.
+pub fn perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> [[f32; 4]; 4] {
+    let f = 1.0 / (fov_y / 2.0).tan();
+
+    [
+        [f / aspect_ratio, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        [0.0, 0.0, (far + near) / (near - far), -1.0],
+        [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
+    ]
+}
+
+/// Builds a perspective projection matrix whose far plane is at infinity.
+///
+/// This is the limit of `perspective` when `far` goes to infinity. Objects are never clipped
+/// by the far plane, which is convenient for skyboxes and very large scenes. Because of
+/// rounding errors, very distant vertices can end up slightly beyond `1.0` in normalized
+/// device coordinates. Set `Depth::clamp` to `DepthClamp::ClampFar` to keep them visible.
+pub fn perspective_infinite(fov_y: f32, aspect_ratio: f32, near: f32) -> [[f32; 4]; 4] {
+    let f = 1.0 / (fov_y / 2.0).tan();
+
+    [
+        [f / aspect_ratio, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        [0.0, 0.0, -1.0, -1.0],
+        [0.0, 0.0, -2.0 * near, 0.0],
+    ]
+}
+
+/// Builds a perspective projection matrix with an infinite far plane and a reversed depth.
+///
+/// The near plane is mapped to a depth of `1.0` and the infinity to `0.0`. Contrary to the
+/// other functions of this module, this matrix is meant to be used with a `0.0 .. 1.0` depth
+/// range in clip space. Combined with a floating-point depth buffer, this gives an almost
+/// uniform precision over the whole scene and avoids z-fighting between distant objects.
+///
+/// Using this matrix requires the following draw parameters:
+///
+/// - `clip_control_depth` set to `ClipControlDepth::ZeroToOne`.
+/// - A depth test of `DepthTest::IfMore` or `DepthTest::IfMoreOrEqual`.
+/// - Clearing the depth buffer to `0.0` instead of `1.0`.
+///
+/// ```
+/// use glium::draw_parameters::ClipControlDepth;
+///
+/// let projection = glium::matrix::perspective_infinite_reverse_z(1.0, 16.0 / 9.0, 0.1);
+///
+/// let params = glium::DrawParameters {
+///     depth: glium::Depth {
+///         test: glium::DepthTest::IfMore,
+///         write: true,
+///         .. Default::default()
+///     },
+///     clip_control_depth: ClipControlDepth::ZeroToOne,
+///     .. Default::default()
+/// };
+/// # let _ = (projection, params);
+/// ```
+pub fn perspective_infinite_reverse_z(fov_y: f32, aspect_ratio: f32, near: f32)
+                                      -> [[f32; 4]; 4]
+{
+    let f = 1.0 / (fov_y / 2.0).tan();
+
+    [
+        [f / aspect_ratio, 0.0, 0.0, 0.0],
+        [0.0, f, 0.0, 0.0],
+        [0.0, 0.0, 0.0, -1.0],
+        [0.0, 0.0, near, 0.0],
+    ]
+}
+
+/// Builds an orthographic projection matrix.
+///
+/// The box delimited by the parameters is mapped to the `-1.0 .. 1.0` cube. `near` and `far`
+/// are distances along the negative Z axis.
+pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32)
+                    -> [[f32; 4]; 4]
+{
+    [
+        [2.0 / (right - left), 0.0, 0.0, 0.0],
+        [0.0, 2.0 / (top - bottom), 0.0, 0.0],
+        [0.0, 0.0, -2.0 / (far - near), 0.0],
+        [
+            -(right + left) / (right - left),
+            -(top + bottom) / (top - bottom),
+            -(far + near) / (far - near),
+            1.0,
+        ],
+    ]
+}
+
+/// Builds a view matrix for a camera at `eye` looking at `target`.
+///
+/// `up` is the direction of the top of the camera. It must not be parallel to the direction
+/// from `eye` to `target`.
+pub fn look_at(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> [[f32; 4]; 4] {
+    let f = normalize([target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]]);
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+
+    [
+        [s[0], u[0], -f[0], 0.0],
+        [s[1], u[1], -f[1], 0.0],
+        [s[2], u[2], -f[2], 0.0],
+        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+    ]
+}
+
+/// Builds the normal matrix of a model-view matrix.
+///
+/// This is the inverse-transpose of the upper-left 3x3 part of `model_view`, which must be
+/// used to transform normals instead of the model-view matrix itself. Otherwise normals are
+/// no longer perpendicular to the surface as soon as the model is scaled non-uniformly. The
+/// result can be passed directly as a `mat3` uniform.
+///
+/// If the upper-left 3x3 part of `model_view` is not invertible, for example because of a
+/// scale of zero, the result contains infinite or NaN values.
+///
+/// ```
+/// let view = glium::matrix::look_at([0.0, 1.0, 5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+///
+/// let uniforms = glium::uniform! {
+///     model_view: view,
+///     normal_matrix: glium::matrix::normal_matrix(view),
+/// };
+/// # let _ = uniforms;
+/// ```
+pub fn normal_matrix(model_view: [[f32; 4]; 4]) -> [[f32; 3]; 3] {
+    let a = [model_view[0][0], model_view[0][1], model_view[0][2]];
+    let b = [model_view[1][0], model_view[1][1], model_view[1][2]];
+    let c = [model_view[2][0], model_view[2][1], model_view[2][2]];
+
+    // the columns of the inverse-transpose are the cross products of the columns of the
+    // matrix, divided by its determinant
+    let (bc, ca, ab) = (cross(b, c), cross(c, a), cross(a, b));
+    let inv_det = 1.0 / dot(a, bc);
+
+    [
+        [bc[0] * inv_det, bc[1] * inv_det, bc[2] * inv_det],
+        [ca[0] * inv_det, ca[1] * inv_det, ca[2] * inv_det],
+        [ab[0] * inv_det, ab[1] * inv_det, ab[2] * inv_det],
+    ]
+}
+
+#[inline]
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline]
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = dot(v, v).sqrt();
+    [v[0] / len, v[1] / len, v[2] / len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{look_at, normal_matrix, orthographic, perspective, perspective_infinite};
+    use super::perspective_infinite_reverse_z;
+
+    fn assert_matrix_eq(a: [[f32; 4]; 4], b: [[f32; 4]; 4]) {
+        for (col_a, col_b) in a.iter().zip(b.iter()) {
+            for (x, y) in col_a.iter().zip(col_b.iter()) {
+                assert!((x - y).abs() < 1e-5, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    fn transform(m: [[f32; 4]; 4], p: [f32; 3]) -> [f32; 3] {
+        let mut out = [0.0; 4];
+        for (row, value) in out.iter_mut().enumerate() {
+            *value = m[0][row] * p[0] + m[1][row] * p[1] + m[2][row] * p[2] + m[3][row];
+        }
+        [out[0] / out[3], out[1] / out[3], out[2] / out[3]]
+    }
+
+    #[test]
+    fn perspective_reference() {
+        let m = perspective(std::f32::consts::FRAC_PI_2, 2.0, 1.0, 3.0);
+
+        assert_matrix_eq(m, [
+            [0.5, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, -2.0, -1.0],
+            [0.0, 0.0, -3.0, 0.0],
+        ]);
+    }
+
+    #[test]
+    fn perspective_depth_range() {
+        let m = perspective(1.0, 1.0, 0.5, 50.0);
+
+        assert!((transform(m, [0.0, 0.0, -0.5])[2] + 1.0).abs() < 1e-5);
+        assert!((transform(m, [0.0, 0.0, -50.0])[2] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn perspective_infinite_depth_range() {
+        let m = perspective_infinite(1.0, 1.0, 0.5);
+
+        assert!((transform(m, [0.0, 0.0, -0.5])[2] + 1.0).abs() < 1e-5);
+        assert!(transform(m, [0.0, 0.0, -1.0e6])[2] < 1.0);
+        assert!((transform(m, [0.0, 0.0, -1.0e6])[2] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn perspective_infinite_reverse_z_depth_range() {
+        let m = perspective_infinite_reverse_z(1.0, 1.0, 0.5);
+
+        assert!((transform(m, [0.0, 0.0, -0.5])[2] - 1.0).abs() < 1e-5);
+
+        // distant objects keep distinct depth values
+        let a = transform(m, [0.0, 0.0, -1.0e6])[2];
+        let b = transform(m, [0.0, 0.0, -1.001e6])[2];
+        assert!(a > 0.0 && b > 0.0 && a > b);
+    }
+
+    #[test]
+    fn orthographic_reference() {
+        let m = orthographic(0.0, 800.0, 0.0, 600.0, -1.0, 1.0);
+
+        assert_matrix_eq(m, [
+            [2.0 / 800.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0 / 600.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0, 0.0],
+            [-1.0, -1.0, 0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn look_at_identity() {
+        let m = look_at([0.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]);
+
+        assert_matrix_eq(m, [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn look_at_reference() {
+        let m = look_at([0.0, 0.0, 5.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_matrix_eq(m, [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, -5.0, 1.0],
+        ]);
+
+        // looking along the positive X axis
+        let m = look_at([1.0, 2.0, 3.0], [2.0, 2.0, 3.0], [0.0, 1.0, 0.0]);
+        let p = transform(m, [4.0, 2.0, 3.0]);
+        assert!(p[0].abs() < 1e-5 && p[1].abs() < 1e-5 && (p[2] + 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn normal_matrix_non_uniform_scale() {
+        let m = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 4.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [3.0, 5.0, 7.0, 1.0],
+        ];
+
+        assert_eq!(normal_matrix(m), [
+            [0.5, 0.0, 0.0],
+            [0.0, 0.25, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn normal_matrix_reference() {
+        // a rotation is its own inverse-transpose
+        let view = look_at([1.0, 2.0, 3.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let n = normal_matrix(view);
+        for (col_n, col_v) in n.iter().zip(view.iter()) {
+            for (x, y) in col_n.iter().zip(col_v.iter()) {
+                assert!((x - y).abs() < 1e-5, "{:?} != {:?}", n, view);
+            }
+        }
+
+        // the transpose of the result multiplied by the matrix gives the identity
+        let m = [
+            [1.0, 2.0, 0.0, 0.0],
+            [0.0, 1.0, 3.0, 0.0],
+            [4.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let n = normal_matrix(m);
+        for (i, col_n) in n.iter().enumerate() {
+            for (j, col_m) in m.iter().take(3).enumerate() {
+                let value: f32 = col_n.iter().zip(col_m.iter()).map(|(x, y)| x * y).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-5, "{:?}", n);
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use std::fmt;
+use std::error::Error;
+
 use crate::BlitTarget;
 use crate::Rect;
 
@@ -8,11 +11,62 @@ use crate::fbo::FramebuffersContainer;
 use crate::fbo::ValidatedAttachments;
 
 use crate::gl;
+use crate::texture::TextureKind;
 use crate::version::Version;
 use crate::version::Api;
 
+/// Error that can happen while blitting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlitError {
+    /// A linear filter has been requested but the color buffer that is read from has an integral
+    /// or unsigned format. Only `MagnifySamplerFilter::Nearest` is allowed with these formats.
+    LinearFilterOnIntegralFormat,
+
+    /// The color buffer that is read from is multisampled, but the source and target rectangles
+    /// are not identical. Resolving a multisampled buffer can't scale or move the image.
+    MultisampledSourceRectMismatch,
+}
+
+impl fmt::Display for BlitError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::BlitError::*;
+        let desc = match *self {
+            LinearFilterOnIntegralFormat =>
+                "A linear filter can't be used when the source color buffer has an integral or \
+                 unsigned format",
+            MultisampledSourceRectMismatch =>
+                "The source and target rectangles must be identical when the source color buffer \
+                 is multisampled",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for BlitError {}
+
+/// Checks that the color buffer of the framebuffer object made of `source` can be blitted
+/// with `filter` from `src_rect` to `target_rect`.
+///
+/// The color buffers of the default framebuffer are never integral and don't need this check.
+pub fn validate_blit(source: &ValidatedAttachments<'_>, src_rect: &Rect, target_rect: &BlitTarget,
+                     filter: gl::types::GLenum) -> Result<(), BlitError>
+{
+    if source.get_samples().is_some() &&
+       (src_rect.left != target_rect.left || src_rect.bottom != target_rect.bottom ||
+        src_rect.width as i32 != target_rect.width || src_rect.height as i32 != target_rect.height)
+    {
+        return Err(BlitError::MultisampledSourceRectMismatch);
+    }
+
+    match source.get_read_buffer_kind() {
+        Some(TextureKind::Integral) | Some(TextureKind::Unsigned) if filter != gl::NEAREST =>
+            Err(BlitError::LinearFilterOnIntegralFormat),
+        _ => Ok(()),
+    }
+}
+
 pub fn blit(context: &Context, source: Option<&ValidatedAttachments<'_>>,
-            target: Option<&ValidatedAttachments<'_>>, mask: gl::types::GLbitfield,
+            target: Option<&ValidatedAttachments<'_>>, mask: gl::types::GLbitfield, srgb: Option<bool>,
             src_rect: &Rect, target_rect: &BlitTarget, filter: gl::types::GLenum)
 {
     assert!(
@@ -32,6 +86,23 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments<'_>>,
             ctxt.state.enabled_scissor_test = false;
         }
 
+        // `GL_FRAMEBUFFER_SRGB` controls the conversion of the colors during the blit
+        if let Some(srgb) = srgb {
+            if mask & gl::COLOR_BUFFER_BIT != 0 && ctxt.state.enabled_framebuffer_srgb != srgb &&
+               (ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                ctxt.extensions.gl_arb_framebuffer_srgb ||
+                ctxt.extensions.gl_ext_framebuffer_srgb ||
+                ctxt.extensions.gl_ext_srgb_write_control)
+            {
+                if srgb {
+                    ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
+                } else {
+                    ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
+                }
+                ctxt.state.enabled_framebuffer_srgb = srgb;
+            }
+        }
+
         // trying to do a named blit if possible
         if ctxt.version >= &Version(Api::Gl, 4, 5) {
             ctxt.gl.BlitNamedFramebuffer(source, target,
@@ -43,6 +114,7 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments<'_>>,
                 (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
                 (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
 
+            ctxt.log_errors("blit");
             return;
         }
 
@@ -89,5 +161,7 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments<'_>>,
                 (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
                 (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
         }
+
+        ctxt.log_errors("blit");
     }
 }
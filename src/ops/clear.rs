@@ -1,3 +1,6 @@
+use std::fmt;
+use std::error::Error;
+
 use crate::fbo::{self, ValidatedAttachments};
 
 use crate::context::Context;
@@ -11,10 +14,30 @@ use crate::Api;
 use crate::version::Version;
 use crate::gl;
 
+/// Error that can happen while clearing a surface.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearError {
+    /// Some color components were masked, but the surface doesn't support clearing with a
+    /// color mask.
+    ColorMaskNotSupported,
+}
+
+impl fmt::Display for ClearError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ClearError::*;
+        let desc = match *self {
+            ColorMaskNotSupported =>
+                "This surface doesn't support clearing with a color mask",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for ClearError {}
 
 pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
              rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
-             depth: Option<f32>, stencil: Option<i32>)
+             color_mask: (bool, bool, bool, bool), depth: Option<f32>, stencil: Option<i32>)
 {
     unsafe {
         let mut ctxt = context.make_current();
@@ -27,9 +50,15 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
             ctxt.state.enabled_rasterizer_discard = false;
         }
 
-        if ctxt.state.color_mask != (1, 1, 1, 1) {
-            ctxt.state.color_mask = (1, 1, 1, 1);
-            ctxt.gl.ColorMask(1, 1, 1, 1);
+        let color_mask = (
+            if color_mask.0 { 1 } else { 0 },
+            if color_mask.1 { 1 } else { 0 },
+            if color_mask.2 { 1 } else { 0 },
+            if color_mask.3 { 1 } else { 0 },
+        );
+        if ctxt.state.color_mask != color_mask {
+            ctxt.state.color_mask = color_mask;
+            ctxt.gl.ColorMask(color_mask.0, color_mask.1, color_mask.2, color_mask.3);
         }
 
         if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
@@ -115,5 +144,6 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
         }
 
         ctxt.gl.Clear(flags);
+        ctxt.log_errors("clear");
     }
 }
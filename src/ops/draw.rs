@@ -1,3 +1,4 @@
+use std::borrow::Borrow;
 use std::ptr;
 
 use crate::BufferExt;
@@ -6,6 +7,7 @@ use crate::ProgramExt;
 use crate::DrawError;
 use crate::UniformsExt;
 
+use crate::buffer::Inserter;
 use crate::context::Context;
 use crate::ContextExt;
 use crate::TransformFeedbackSessionExt;
@@ -14,13 +16,13 @@ use crate::fbo::{self, ValidatedAttachments};
 
 use crate::uniforms::Uniforms;
 use crate::{Program, ToGlEnum};
-use crate::index::{self, IndicesSource};
-use crate::vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
-use crate::vertex_array_object::VertexAttributesSystem;
+use crate::index::{self, IndexType, IndicesSource};
+use crate::vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession, VertexFormat};
+use crate::vertex_array_object::{Binder, VertexAttributesSystem};
 
 use crate::draw_parameters::DrawParameters;
 
-use crate::{gl, context, draw_parameters};
+use crate::{gl, context, debug, draw_parameters};
 use crate::version::Version;
 use crate::version::Api;
 
@@ -31,14 +33,111 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                       dimensions: (u32, u32)) -> Result<(), DrawError>
                       where U: Uniforms, V: MultiVerticesSource<'a>
 {
-    // this contains the list of fences that will need to be fulfilled after the draw command
-    // has started
+    draw_all(context, framebuffer, Some((vertex_buffers.iter(), indices)), program, uniforms,
+             draw_parameters, dimensions)
+}
+
+/// Draws the same vertices once for each source of indices, with the type of primitives that
+/// accompanies it.
+///
+/// The framebuffer, the program and the uniforms are only bound before the first draw command,
+/// and the vertex array object is only changed if the index buffer changes. Stops at the first
+/// error.
+pub fn draw_multi<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                            vertex_buffers: V, indices: &[(IndicesSource<'_>, index::PrimitiveType)],
+                            program: &Program, uniforms: &U,
+                            draw_parameters: &DrawParameters<'_>, dimensions: (u32, u32))
+                            -> Result<(), DrawError>
+                            where U: Uniforms, V: MultiVerticesSource<'a> + Copy
+{
+    let draws = indices.iter().map(|(indices, primitives)| {
+        (vertex_buffers.iter(), indices.clone().with_primitives_type(*primitives))
+    });
+
+    draw_all(context, framebuffer, draws, program, uniforms, draw_parameters, dimensions)
+}
+
+fn draw_all<'v, 'i, U, D, S>(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                             draws: D, program: &Program, uniforms: &U,
+                             draw_parameters: &DrawParameters<'_>, dimensions: (u32, u32))
+                             -> Result<(), DrawError>
+                             where U: Uniforms, D: IntoIterator<Item = (S, IndicesSource<'i>)>,
+                                   S: Iterator<Item = VerticesSource<'v>>
+{
+    if let Some(tf) = draw_parameters.transform_feedback {
+        tf.check_program(program)?;
+    }
+
+    if context.unset_uniforms_warnings_enabled() {
+        report_unset_uniforms(context, program, uniforms);
+    }
+
+    if let Some(back) = draw_parameters.back_polygon_mode {
+        if back != draw_parameters.polygon_mode &&
+           !draw_parameters::is_per_face_polygon_mode_supported(context)
+        {
+            context.report_debug_message(debug::MessageType::Portability, debug::Severity::Low,
+                                         "A different polygon mode for back faces was requested \
+                                          but is not supported by this context, the front mode \
+                                          is used for both faces");
+        }
+    }
+
+    // starting the state changes
+    let mut ctxt = context.make_current();
+
+    // this contains the list of fences that will need to be fulfilled after the draw commands
+    // have started
     let mut fences = Vec::with_capacity(0);
 
+    // `None` until the framebuffer, the program and the uniforms are bound
+    let mut srgb_ignored = None;
+
+    let mut result = Ok(());
+    for (vertex_buffers, indices) in draws {
+        result = draw_indices(&mut ctxt, framebuffer, vertex_buffers, &indices, program, uniforms,
+                              draw_parameters, dimensions, &mut fences, &mut srgb_ignored);
+        if result.is_err() {
+            break;
+        }
+    }
+
+    ctxt.log_errors("draw");
+
+    // fulfilling the fences
+    for fence in fences.into_iter() {
+        fence.insert(&mut ctxt);
+    }
+
+    drop(ctxt);
+
+    if srgb_ignored == Some(true) {
+        context.report_debug_message(debug::MessageType::Other, debug::Severity::Low,
+                                     "sRGB conversion was requested but the color attachment \
+                                      doesn't have an sRGB format");
+    }
+
+    result
+}
+
+/// Binds the vertices and the state required to draw with `indices`, then issues the draw
+/// command.
+///
+/// The framebuffer, the program and the uniforms are only bound if `srgb_ignored` is `None`,
+/// in which case it is then set.
+fn draw_indices<'a, 'v: 'a, U, S>(ctxt: &mut context::CommandContext<'_>,
+                                  framebuffer: Option<&ValidatedAttachments<'_>>,
+                                  vertex_buffers: S, indices: &IndicesSource<'a>,
+                                  program: &'a Program, uniforms: &'a U,
+                                  draw_parameters: &DrawParameters<'_>, dimensions: (u32, u32),
+                                  fences: &mut Vec<Inserter<'a>>,
+                                  srgb_ignored: &mut Option<bool>) -> Result<(), DrawError>
+                                  where U: Uniforms, S: Iterator<Item = VerticesSource<'v>>
+{
     // handling tessellation
     let vertices_per_patch = match indices.get_primitives_type() {
         index::PrimitiveType::Patches { vertices_per_patch } => {
-            if let Some(max) = context.capabilities().max_patch_vertices {
+            if let Some(max) = ctxt.capabilities.max_patch_vertices {
                 if vertices_per_patch == 0 || vertices_per_patch as gl::types::GLint > max {
                     return Err(DrawError::UnsupportedVerticesPerPatch);
                 }
@@ -65,38 +164,51 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         },
     };
 
-    // starting the state changes
-    let mut ctxt = context.make_current();
+    // checking that adjacency primitives receive a valid number of indices
+    if let IndicesSource::IndexBuffer { ref buffer, primitives, .. } = *indices {
+        if !primitives.is_valid_count(buffer.get_elements_count()) {
+            return Err(DrawError::InvalidAdjacencyIndicesCount);
+        }
+    }
 
-    // handling vertices source
-    let (vertices_count, instances_count, base_vertex) = {
-        let index_buffer = match indices {
-            IndicesSource::IndexBuffer { buffer, .. } => Some(buffer),
-            IndicesSource::MultidrawArray { .. } => None,
-            IndicesSource::MultidrawElement { indices, .. } => Some(indices),
-            IndicesSource::NoIndices { .. } => None,
-        };
+    // checking that the geometry shader takes this type of primitives as input
+    if !program.accepts_input_primitives(indices.get_primitives_type()) {
+        return Err(DrawError::GeometryShaderInputMismatch);
+    }
 
-        // determining whether we can use the `base_vertex` variants for drawing
-        let use_base_vertex = match indices {
-            IndicesSource::MultidrawArray { .. } => false,
-            IndicesSource::MultidrawElement { .. } => false,
-            IndicesSource::NoIndices { .. } => true,
-            _ => ctxt.version >= &Version(Api::Gl, 3, 2) ||
-                 ctxt.version >= &Version(Api::GlEs, 3, 2) ||
-                 ctxt.extensions.gl_arb_draw_elements_base_vertex ||
-                 ctxt.extensions.gl_oes_draw_elements_base_vertex
+    // checking that the custom primitive restart index fits in the indices
+    if let (Some(index), IndicesSource::IndexBuffer { data_type, .. }) =
+        (draw_parameters.custom_primitive_restart_index, indices)
+    {
+        let max = match data_type {
+            IndexType::U8 => u8::MAX as u32,
+            IndexType::U16 => u16::MAX as u32,
+            IndexType::U32 => u32::MAX,
         };
 
+        if index > max {
+            return Err(DrawError::PrimitiveRestartIndexOutOfRange);
+        }
+    }
+
+    if let IndicesSource::TransformFeedback { .. } = *indices {
+        if !(ctxt.version >= &Version(Api::Gl, 4, 0) ||
+             ctxt.extensions.gl_arb_transform_feedback2)
+        {
+            return Err(DrawError::DrawTransformFeedbackNotSupported);
+        }
+    }
+
+    // handling vertices source
+    let (vertices_count, instances_count, base_vertex) = {
         // object that is used to build the bindings
-        let mut binder = VertexAttributesSystem::start(&mut ctxt, program, index_buffer,
-                                                       use_base_vertex);
+        let mut binder = start_binding(ctxt, program, indices);
         // number of vertices in the vertices sources, or `None` if there is a mismatch
         let mut vertices_count: Option<usize> = None;
         // number of instances to draw
         let mut instances_count: Option<usize> = None;
 
-        for src in vertex_buffers.iter() {
+        for src in vertex_buffers {
             // Allow single match for consistency with the match below.
             // Integrating the two matches wouldn't improve the code either.
             #[allow(clippy::single_match)]
@@ -156,37 +268,45 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
             }
         }
 
-        (vertices_count, instances_count, binder.bind().unwrap_or(0))
-    };
-
-    // binding the FBO to draw upon
-    {
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        unsafe { fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false) };
+        (vertices_count, instances_count, binder.bind()?.unwrap_or(0))
     };
 
-    // binding the program and uniforms
-    program.use_program(&mut ctxt);
-    uniforms.bind_uniforms(&mut ctxt, program, &mut fences)?;
+    let bind_state = srgb_ignored.is_none();
+    if bind_state {
+        // binding the FBO to draw upon
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(ctxt, framebuffer);
+        unsafe { fbo::bind_framebuffer(ctxt, fbo_id, true, false) };
+
+        // querying the attachment requires two round-trips to the driver, so this is only
+        // checked in debug builds
+        let srgb = cfg!(debug_assertions) && draw_parameters.srgb == Some(true) &&
+                   unsafe { !is_draw_attachment_srgb(ctxt, fbo_id) };
+
+        // binding the program and uniforms
+        program.use_program(ctxt);
+        uniforms.bind_uniforms(ctxt, program, fences)?;
+        *srgb_ignored = Some(srgb);
+    }
 
     // sync-ing draw_parameters
     unsafe {
-        draw_parameters::sync(&mut ctxt, draw_parameters, dimensions, indices.get_primitives_type())?;
-        sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
+        draw_parameters::sync(ctxt, draw_parameters, dimensions, indices.get_primitives_type())?;
+        sync_vertices_per_patch(ctxt, vertices_per_patch);
 
-        // TODO: make sure that the program is the right one
         // TODO: changing the current transform feedback requires pausing/unbinding before changing the program
-        if let Some(ref tf) = draw_parameters.transform_feedback {
-            tf.bind(&mut ctxt, indices.get_primitives_type());
-        } else {
-            TransformFeedbackSession::unbind(&mut ctxt);
+        if bind_state {
+            if let Some(ref tf) = draw_parameters.transform_feedback {
+                tf.bind(ctxt, indices.get_primitives_type());
+            } else {
+                TransformFeedbackSession::unbind(ctxt);
+            }
         }
     }
 
     // drawing
     // TODO: make this code more readable
     {
-        match &indices {
+        match indices {
             IndicesSource::IndexBuffer { ref buffer, data_type, primitives } => {
                 let ptr: *const u8 = ptr::null_mut();
                 let ptr = unsafe { ptr.add(buffer.get_offset_bytes()) };
@@ -276,7 +396,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
 
                 unsafe {
-                    buffer.prepare_and_bind_for_draw_indirect(&mut ctxt);
+                    buffer.prepare_and_bind_for_draw_indirect(ctxt);
                     ctxt.gl.MultiDrawArraysIndirect(primitives.to_glenum(), ptr as *const _,
                                                     buffer.get_elements_count() as gl::types::GLsizei,
                                                     0);
@@ -296,7 +416,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
 
                 unsafe {
-                    commands.prepare_and_bind_for_draw_indirect(&mut ctxt);
+                    commands.prepare_and_bind_for_draw_indirect(ctxt);
                     debug_assert_eq!(base_vertex, 0);       // enforced earlier in this function
                     ctxt.gl.MultiDrawElementsIndirect(primitives.to_glenum(), data_type.to_glenum(),
                                                       cmd_ptr as *const _,
@@ -311,6 +431,10 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     None => return Err(DrawError::VerticesSourcesLengthMismatch)
                 };
 
+                if !primitives.is_valid_count(vertices_count) {
+                    return Err(DrawError::InvalidAdjacencyIndicesCount);
+                }
+
                 unsafe {
                     if let Some(instances_count) = instances_count {
                         ctxt.gl.DrawArraysInstanced(primitives.to_glenum(), base_vertex,
@@ -322,19 +446,197 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     }
                 }
             },
+
+            IndicesSource::TransformFeedback { primitives } => {
+                debug_assert_eq!(base_vertex, 0);       // enforced earlier in this function
+
+                // glium only uses the default transform feedback object, which holds the
+                // number of vertices written by the last session
+                unsafe {
+                    if let Some(instances_count) = instances_count {
+                        if ctxt.version >= &Version(Api::Gl, 4, 2) ||
+                           ctxt.extensions.gl_arb_transform_feedback_instanced
+                        {
+                            ctxt.gl.DrawTransformFeedbackInstanced(primitives.to_glenum(), 0,
+                                                                   instances_count as
+                                                                   gl::types::GLsizei);
+                        } else {
+                            return Err(DrawError::DrawTransformFeedbackNotSupported);
+                        }
+                    } else {
+                        ctxt.gl.DrawTransformFeedback(primitives.to_glenum(), 0);
+                    }
+                }
+            },
         };
     };
 
     ctxt.state.next_draw_call_id += 1;
+    Ok(())
+}
 
-    // fulfilling the fences
-    for fence in fences.into_iter() {
-        fence.insert(&mut ctxt);
+/// Returns true if the first color attachment of the framebuffer bound for drawing has an sRGB
+/// encoding. Also returns true if this can't be determined.
+unsafe fn is_draw_attachment_srgb(ctxt: &mut context::CommandContext<'_>,
+                                  fbo_id: gl::types::GLuint) -> bool
+{
+    if !(ctxt.version >= &Version(Api::Gl, 3, 0)) && !(ctxt.version >= &Version(Api::GlEs, 3, 0)) {
+        return true;
+    }
+
+    let attachment = if fbo_id != 0 {
+        gl::COLOR_ATTACHMENT0
+    } else if ctxt.version.0 == Api::GlEs {
+        gl::BACK
+    } else {
+        gl::BACK_LEFT
+    };
+
+    let mut object_type = 0;
+    ctxt.gl.GetFramebufferAttachmentParameteriv(gl::DRAW_FRAMEBUFFER, attachment,
+                                                gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE,
+                                                &mut object_type);
+    if object_type as gl::types::GLenum == gl::NONE {
+        return false;
     }
 
+    let mut encoding = 0;
+    ctxt.gl.GetFramebufferAttachmentParameteriv(gl::DRAW_FRAMEBUFFER, attachment,
+                                                gl::FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING,
+                                                &mut encoding);
+    encoding as gl::types::GLenum == gl::SRGB
+}
+
+/// Builds the vertex array object that `draw` would use for these sources, so that the first
+/// draw doesn't have to.
+pub fn warm_vao_cache<'a, V>(context: &Context, vertex_buffers: V, indices: IndicesSource<'_>,
+                             program: &Program) -> Result<(), DrawError>
+                             where V: MultiVerticesSource<'a>
+{
+    let mut ctxt = context.make_current();
+
+    // without vertex array objects, there is nothing to cache
+    if !(ctxt.version >= &Version(Api::Gl, 3, 0)) && !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+       !ctxt.extensions.gl_arb_vertex_array_object && !ctxt.extensions.gl_oes_vertex_array_object &&
+       !ctxt.extensions.gl_apple_vertex_array_object
+    {
+        return Ok(());
+    }
+
+    let buffers = vertex_buffers.iter().filter_map(|src| match src {
+        VerticesSource::VertexBuffer(buffer, format, per_instance) =>
+            Some((buffer, format, per_instance)),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    // building the vertex array object panics if the buffers don't match the program
+    for &(_, format, _) in buffers.iter() {
+        check_attributes_types(program, format)?;
+    }
+
+    let is_provided = |name: &str, location| buffers.iter().any(|&(_, format, _)| {
+        format.iter().any(|&(ref n, _, l, _, _)| (l != -1 && l == location) || n == name)
+    });
+    if program.attributes().any(|(name, a)| !is_provided(name, a.location)) {
+        return Err(DrawError::AttributeMissing);
+    }
+
+    let mut binder = start_binding(&mut ctxt, program, &indices);
+    for (buffer, format, per_instance) in buffers {
+        binder = binder.add(&buffer, format, if per_instance { Some(1) } else { None });
+    }
+
+    binder.bind()?;
     Ok(())
 }
 
+/// Checks that the attributes of `format` that are used by the program have the type that the
+/// program expects, and that `format` doesn't use the same location twice.
+fn check_attributes_types(program: &Program, format: VertexFormat) -> Result<(), DrawError> {
+    for (index, &(ref name, _, location, ty, _)) in format.iter().enumerate() {
+        if location != -1 && format.iter().skip(index + 1).any(|b| b.2 == location) {
+            return Err(DrawError::VertexAttributeCollision { name: name.to_string() });
+        }
+
+        let attribute = match location {
+            -1 => program.get_attribute(Borrow::<str>::borrow(name)),
+            location => program.attributes().map(|(_, a)| a).find(|a| a.location == location),
+        };
+
+        // see the same check in `vertex_array_object`
+        #[cfg(not(target_os = "vita"))]
+        {
+            if let Some(attribute) = attribute {
+                if ty.get_num_components() != attribute.ty.get_num_components() ||
+                   attribute.size != 1
+                {
+                    return Err(DrawError::AttributeTypeMismatch);
+                }
+            }
+        }
+        #[cfg(target_os = "vita")]
+        let _ = (attribute, ty);
+    }
+
+    Ok(())
+}
+
+/// Reports through the debug callback the active uniforms of the program that aren't provided
+/// by `uniforms`.
+fn report_unset_uniforms<U>(context: &Context, program: &Program, uniforms: &U)
+                            where U: Uniforms
+{
+    let mut names = Vec::new();
+    uniforms.visit_values(|name, _| names.push(name.to_owned()));
+
+    let mut locations = Vec::new();
+    uniforms.visit_located_values(|location, _| locations.push(location));
+
+    let mut missing = program.uniforms()
+        .filter(|&(name, uniform)| {
+            !name.starts_with("gl_") && !names.iter().any(|n| n == name) &&
+            !locations.contains(&uniform.location)
+        })
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    missing.sort_unstable();
+    let message = format!("The following uniforms of the program were not provided in this \
+                           draw call: {}", missing.join(", "));
+    context.report_debug_message(debug::MessageType::Other, debug::Severity::Low, &message);
+}
+
+/// Starts binding the vertex attributes for a draw command with the given indices.
+fn start_binding<'a, 'b, 'c: 'b>(ctxt: &'b mut context::CommandContext<'c>, program: &'a Program,
+                                 indices: &IndicesSource<'a>) -> Binder<'a, 'b, 'c>
+{
+    let index_buffer = match *indices {
+        IndicesSource::IndexBuffer { buffer, .. } => Some(buffer),
+        IndicesSource::MultidrawArray { .. } => None,
+        IndicesSource::MultidrawElement { indices, .. } => Some(indices),
+        IndicesSource::NoIndices { .. } => None,
+        IndicesSource::TransformFeedback { .. } => None,
+    };
+
+    // determining whether we can use the `base_vertex` variants for drawing
+    let use_base_vertex = match *indices {
+        IndicesSource::MultidrawArray { .. } => false,
+        IndicesSource::MultidrawElement { .. } => false,
+        IndicesSource::NoIndices { .. } => true,
+        IndicesSource::TransformFeedback { .. } => false,
+        _ => ctxt.version >= &Version(Api::Gl, 3, 2) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+             ctxt.extensions.gl_arb_draw_elements_base_vertex ||
+             ctxt.extensions.gl_oes_draw_elements_base_vertex
+    };
+
+    VertexAttributesSystem::start(ctxt, program, index_buffer, use_base_vertex)
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext<'_>, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
@@ -0,0 +1,59 @@
+use crate::fbo::{self, ValidatedAttachments};
+
+use crate::context::Context;
+use crate::ContextExt;
+use crate::Rect;
+
+use crate::framebuffer::Attachment;
+
+use crate::Api;
+use crate::version::Version;
+use crate::gl;
+
+/// Invalidates the content of some attachments of a framebuffer, or of the default framebuffer
+/// if `framebuffer` is `None`.
+///
+/// Does nothing if invalidating framebuffers is not supported by the backend.
+pub fn invalidate(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                  attachments: &[Attachment], rect: Option<&Rect>)
+{
+    if attachments.is_empty() {
+        return;
+    }
+
+    let mut ctxt = context.make_current();
+
+    if !(ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+         ctxt.extensions.gl_arb_invalidate_subdata)
+    {
+        return;
+    }
+
+    let attachments = attachments.iter().map(|attachment| {
+        match (attachment, framebuffer.is_some()) {
+            (Attachment::Color, true) => gl::COLOR_ATTACHMENT0,
+            (Attachment::Depth, true) => gl::DEPTH_ATTACHMENT,
+            (Attachment::Stencil, true) => gl::STENCIL_ATTACHMENT,
+            (Attachment::Color, false) => gl::COLOR,
+            (Attachment::Depth, false) => gl::DEPTH,
+            (Attachment::Stencil, false) => gl::STENCIL,
+        }
+    }).collect::<Vec<_>>();
+
+    unsafe {
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+        if let Some(rect) = rect {
+            ctxt.gl.InvalidateSubFramebuffer(gl::FRAMEBUFFER, attachments.len() as gl::types::GLsizei,
+                                             attachments.as_ptr(),
+                                             rect.left as gl::types::GLint,
+                                             rect.bottom as gl::types::GLint,
+                                             rect.width as gl::types::GLsizei,
+                                             rect.height as gl::types::GLsizei);
+        } else {
+            ctxt.gl.InvalidateFramebuffer(gl::FRAMEBUFFER, attachments.len() as gl::types::GLsizei,
+                                          attachments.as_ptr());
+        }
+    }
+}
@@ -1,9 +1,11 @@
-pub use self::blit::blit;
-pub use self::clear::clear;
-pub use self::draw::draw;
-pub use self::read::{read, ReadError, Source, Destination};
+pub use self::blit::{blit, validate_blit, BlitError};
+pub use self::clear::{clear, ClearError};
+pub use self::draw::{draw, draw_multi, warm_vao_cache};
+pub use self::invalidate::invalidate;
+pub use self::read::{read, read_stencil, ReadError, Source, Destination};
 
 mod blit;
 mod clear;
 mod draw;
+mod invalidate;
 mod read;
@@ -1,3 +1,4 @@
+use std::mem;
 use std::ptr;
 use std::fmt;
 use std::error::Error;
@@ -8,12 +9,13 @@ use crate::texture::PixelValue;
 use crate::image_format::{TextureFormatRequest, TextureFormat};
 
 use crate::fbo;
-use crate::fbo::FramebuffersContainer;
+use crate::fbo::{FramebuffersContainer, ValidatedAttachments};
 
 use crate::buffer::BufferAny;
 use crate::BufferExt;
 use crate::Rect;
-use crate::context::CommandContext;
+use crate::context::{CommandContext, Context};
+use crate::ContextExt;
 use crate::gl;
 
 use crate::version::Version;
@@ -73,6 +75,13 @@ pub enum ReadError {
     /// Clamping the values is not supported by the implementation.
     ClampingNotSupported,
 
+    /// The requested output format is not compatible with the format of the source. Integral
+    /// and unsigned textures can only be read to integer formats that aren't packed.
+    IncompatibleFormat,
+
+    /// Trying to read the stencil buffer of a surface that doesn't have one.
+    NoStencilBuffer,
+
     // TODO: context lost
 }
 
@@ -86,6 +95,10 @@ impl fmt::Display for ReadError {
                 "The implementation doesn't support reading a depth, depth-stencil or stencil attachment",
             ClampingNotSupported =>
                 "Clamping the values is not supported by the implementation",
+            IncompatibleFormat =>
+                "The requested output format is not compatible with the format of the source",
+            NoStencilBuffer =>
+                "Trying to read the stencil buffer of a surface that doesn't have one",
         };
         fmt.write_str(desc)
     }
@@ -114,10 +127,19 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
     // checking that the output format is supported
     // OpenGL supported everything, while OpenGL ES only supports U8U8U8U8 plus an additional
     // implementation-defined format
-    if ctxt.version >= &Version(Api::GlEs, 2, 0) && output_pixel_format != ClientFormat::U8U8U8U8 {
+    if ctxt.version >= &Version(Api::GlEs, 2, 0) {
         // TODO: GLES is guaranteed to support GL_RGBA and an implementation-defined format
-        //       queried with GL_IMPLEMENTATION_COLOR_READ_FORMAT. We only handle GL_RGBA.
-        return Err(ReadError::OutputFormatNotSupported);
+        //       queried with GL_IMPLEMENTATION_COLOR_READ_FORMAT. We only handle GL_RGBA, plus
+        //       GL_BGRA when GL_EXT_read_format_bgra is available.
+        let supported = match output_pixel_format {
+            ClientFormat::U8U8U8U8 => true,
+            ClientFormat::U8U8U8U8Bgra => ctxt.extensions.gl_ext_read_format_bgra,
+            _ => false,
+        };
+
+        if !supported {
+            return Err(ReadError::OutputFormatNotSupported);
+        }
     }
 
     // handling clamping
@@ -193,6 +215,10 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
     // obtaining the client format and client type to be passed to `glReadPixels`
     let (format, gltype) = match read_src_type {
         ReadSourceType::Color => {
+            if integer && !is_unpacked_integer_format(&output_pixel_format) {
+                return Err(ReadError::IncompatibleFormat);
+            }
+
             client_format_to_gl_enum(&output_pixel_format, integer)
         },
         ReadSourceType::Depth => {
@@ -230,10 +256,42 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
                     ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
                 }
 
-                ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                                   rect.width as gl::types::GLsizei,
-                                   rect.height as gl::types::GLsizei, format, gltype,
-                                   buf.as_mut_ptr() as *mut _);
+                // when possible, we pass the size of the buffer to the implementation so that
+                // it never writes past the end of it
+                let buf_size = (buf.capacity() * mem::size_of::<T>()) as gl::types::GLsizei;
+
+                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                   ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                   (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_robustness)
+                {
+                    ctxt.gl.ReadnPixels(rect.left as gl::types::GLint,
+                                        rect.bottom as gl::types::GLint,
+                                        rect.width as gl::types::GLsizei,
+                                        rect.height as gl::types::GLsizei, format, gltype,
+                                        buf_size, buf.as_mut_ptr() as *mut _);
+
+                } else if ctxt.extensions.gl_arb_robustness {
+                    ctxt.gl.ReadnPixelsARB(rect.left as gl::types::GLint,
+                                           rect.bottom as gl::types::GLint,
+                                           rect.width as gl::types::GLsizei,
+                                           rect.height as gl::types::GLsizei, format, gltype,
+                                           buf_size, buf.as_mut_ptr() as *mut _);
+
+                } else if ctxt.extensions.gl_ext_robustness {
+                    ctxt.gl.ReadnPixelsEXT(rect.left as gl::types::GLint,
+                                           rect.bottom as gl::types::GLint,
+                                           rect.width as gl::types::GLsizei,
+                                           rect.height as gl::types::GLsizei, format, gltype,
+                                           buf_size, buf.as_mut_ptr() as *mut _);
+
+                } else {
+                    ctxt.gl.ReadPixels(rect.left as gl::types::GLint,
+                                       rect.bottom as gl::types::GLint,
+                                       rect.width as gl::types::GLsizei,
+                                       rect.height as gl::types::GLsizei, format, gltype,
+                                       buf.as_mut_ptr() as *mut _);
+                }
+
                 buf.set_len(pixels_to_read as usize);
 
                 *dest = buf;
@@ -253,9 +311,72 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
         }
     };
 
+    ctxt.log_errors("read");
     Ok(())
 }
 
+/// Reads the stencil buffer of a framebuffer, or of the default framebuffer if `None`.
+///
+/// The values are returned row by row, starting with the bottom row.
+pub fn read_stencil(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                    rect: &Rect) -> Result<Vec<u8>, ReadError>
+{
+    let stencil_bits = match framebuffer {
+        Some(framebuffer) => framebuffer.get_stencil_buffer_bits(),
+        None => context.capabilities().stencil_bits,
+    };
+
+    if stencil_bits.is_none() {
+        return Err(ReadError::NoStencilBuffer);
+    }
+
+    let mut ctxt = context.make_current();
+
+    // OpenGL ES doesn't support reading from stencil attachments by default
+    if ctxt.version >= &Version(Api::GlEs, 2, 0) && !ctxt.extensions.gl_nv_read_stencil {
+        return Err(ReadError::AttachmentTypeNotSupported);
+    }
+
+    unsafe {
+        let fbo_id = FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+        fbo::bind_framebuffer(&mut ctxt, fbo_id, false, true);
+
+        BufferAny::unbind_pixel_pack(&mut ctxt);
+
+        // rows of bytes are tightly packed
+        if ctxt.state.pixel_store_pack_alignment != 1 {
+            ctxt.state.pixel_store_pack_alignment = 1;
+            ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+        }
+
+        let pixels_to_read = (rect.width as usize).checked_mul(rect.height as usize)
+                                                  .expect("The rectangle is too large");
+        let mut buf: Vec<u8> = Vec::with_capacity(pixels_to_read);
+
+        ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                           rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei,
+                           gl::STENCIL_INDEX, gl::UNSIGNED_BYTE, buf.as_mut_ptr() as *mut _);
+
+        buf.set_len(pixels_to_read);
+
+        ctxt.log_errors("read_stencil");
+        Ok(buf)
+    }
+}
+
+/// Returns true if the format can be used with `GL_*_INTEGER` client formats.
+fn is_unpacked_integer_format(format: &ClientFormat) -> bool {
+    matches!(*format,
+             ClientFormat::U8 | ClientFormat::U8U8 | ClientFormat::U8U8U8 | ClientFormat::U8U8U8U8 |
+             ClientFormat::I8 | ClientFormat::I8I8 | ClientFormat::I8I8I8 | ClientFormat::I8I8I8I8 |
+             ClientFormat::U16 | ClientFormat::U16U16 | ClientFormat::U16U16U16 |
+             ClientFormat::U16U16U16U16 | ClientFormat::I16 | ClientFormat::I16I16 |
+             ClientFormat::I16I16I16 | ClientFormat::I16I16I16I16 | ClientFormat::U32 |
+             ClientFormat::U32U32 | ClientFormat::U32U32U32 | ClientFormat::U32U32U32U32 |
+             ClientFormat::I32 | ClientFormat::I32I32 | ClientFormat::I32I32I32 |
+             ClientFormat::I32I32I32I32 | ClientFormat::U8U8U8Bgr | ClientFormat::U8U8U8U8Bgra)
+}
+
 fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
                             -> (gl::types::GLenum, gl::types::GLenum)
 {
@@ -298,6 +419,8 @@ fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
         ClientFormat::F32F32 => (gl::RG, gl::FLOAT),
         ClientFormat::F32F32F32 => (gl::RGB, gl::FLOAT),
         ClientFormat::F32F32F32F32 => (gl::RGBA, gl::FLOAT),
+        ClientFormat::U8U8U8Bgr => (gl::BGR, gl::UNSIGNED_BYTE),
+        ClientFormat::U8U8U8U8Bgra => (gl::BGRA, gl::UNSIGNED_BYTE),
     };
 
     let format = if integer {
@@ -306,6 +429,8 @@ fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
             gl::RG => gl::RG_INTEGER,
             gl::RGB => gl::RGB_INTEGER,
             gl::RGBA => gl::RGBA_INTEGER,
+            gl::BGR => gl::BGR_INTEGER,
+            gl::BGRA => gl::BGRA_INTEGER,
             _ => unreachable!()
         }
     } else {
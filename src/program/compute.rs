@@ -18,7 +18,7 @@ use crate::RawUniformValue;
 
 use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary, GetBinaryError, SpirvEntryPoint};
 
-use crate::program::reflection::{Uniform, UniformBlock};
+use crate::program::reflection::{Uniform, UniformBlock, BlockMember};
 use crate::program::reflection::{ShaderStage, SubroutineData};
 use crate::program::shader::{build_shader, build_spirv_shader, check_shader_type_compatibility};
 
@@ -40,10 +40,16 @@ impl ComputeShader {
     }
 
     /// Builds a new compute shader from some source code.
+    ///
+    /// Returns `ComputeShaderNotSupported` if the backend doesn't support compute shaders.
     #[inline]
     pub fn from_source<F: ?Sized>(facade: &F, src: &str) -> Result<ComputeShader, ProgramCreationError>
                           where F: Facade
     {
+        if !ComputeShader::is_supported(facade) {
+            return Err(ProgramCreationError::ComputeShaderNotSupported);
+        }
+
         let _lock = COMPILER_GLOBAL_LOCK.lock();
 
         let shader = build_shader(facade, gl::COMPUTE_SHADER, src)?;
@@ -54,10 +60,16 @@ impl ComputeShader {
     }
 
     /// Builds a new compute shader from SPIR-V module.
+    ///
+    /// Returns `ComputeShaderNotSupported` if the backend doesn't support compute shaders.
     #[inline]
     pub fn from_spirv<F: ?Sized>(facade: &F, spirv: &SpirvEntryPoint) -> Result<ComputeShader, ProgramCreationError>
                           where F: Facade
     {
+        if !ComputeShader::is_supported(facade) {
+            return Err(ProgramCreationError::ComputeShaderNotSupported);
+        }
+
         let _lock = COMPILER_GLOBAL_LOCK.lock();
 
         let shader = build_spirv_shader(facade, gl::COMPUTE_SHADER, spirv)?;
@@ -163,6 +175,15 @@ impl ComputeShader {
             -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
         self.raw.get_shader_storage_blocks()
     }
+
+    /// Returns the members of the uniform block or shader storage block with the given name,
+    /// with their offsets and strides as the implementation actually laid them out.
+    ///
+    /// Uniform blocks are searched first. Returns `None` if there is no such block.
+    #[inline]
+    pub fn get_block_members(&self, block_name: &str) -> Option<&[BlockMember]> {
+        self.raw.get_block_members(block_name)
+    }
 }
 
 impl fmt::Debug for ComputeShader {
@@ -11,7 +11,7 @@ use crate::version::Version;
 
 pub use self::compute::{ComputeShader, ComputeCommand};
 pub use self::program::Program;
-pub use self::reflection::{Uniform, UniformBlock, BlockLayout, OutputPrimitives};
+pub use self::reflection::{Uniform, UniformBlock, BlockLayout, BlockMember, OutputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 pub use self::reflection::{ShaderStage, SubroutineData, SubroutineUniform};
 
@@ -135,6 +135,19 @@ pub enum ProgramCreationError {
 
     /// The glium-specific binary header was not found or is corrupt.
     BinaryHeaderError,
+
+    /// Compute shaders are not supported by the backend. They require OpenGL 4.3,
+    /// OpenGL ES 3.1 or `GL_ARB_compute_shader`.
+    ComputeShaderNotSupported,
+
+    /// The geometry shader declares a `max_vertices` value greater than the
+    /// `GL_MAX_GEOMETRY_OUTPUT_VERTICES` limit of the backend.
+    GeometryMaxVerticesTooLarge {
+        /// Value of `max_vertices` declared in the geometry shader.
+        declared: u32,
+        /// Maximum value supported by the backend.
+        max: u32,
+    },
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -163,12 +176,18 @@ impl fmt::Display for ProgramCreationError {
                 "Point size is not supported by the backend.",
             BinaryHeaderError =>
                 "The glium-specific binary header was not found or is corrupt.",
+            ComputeShaderNotSupported =>
+                "Compute shaders are not supported by the backend",
+            GeometryMaxVerticesTooLarge { .. } =>
+                "The `max_vertices` of the geometry shader is greater than what the backend supports",
         };
         match *self {
             CompilationError(ref s, _) =>
                 write!(fmt, "{}: {}", desc, s),
             LinkingError(ref s) =>
                 write!(fmt, "{}: {}", desc, s),
+            GeometryMaxVerticesTooLarge { declared, max } =>
+                write!(fmt, "{}: declared {}, maximum is {}", desc, declared, max),
             _ =>
                 write!(fmt, "{}", desc),
         }
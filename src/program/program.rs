@@ -13,6 +13,7 @@ use std::hash::BuildHasherDefault;
 
 use fnv::FnvHasher;
 
+use crate::ContextExt;
 use crate::GlObject;
 use crate::ProgramExt;
 use crate::Handle;
@@ -21,7 +22,7 @@ use crate::RawUniformValue;
 use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationInput, ProgramCreationError, ShaderType, Binary, SpirvProgram};
 use crate::program::GetBinaryError;
 
-use crate::program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use crate::program::reflection::{Uniform, UniformBlock, BlockMember, OutputPrimitives};
 use crate::program::reflection::{Attribute, TransformFeedbackBuffer};
 use crate::program::reflection::{SubroutineData, ShaderStage, SubroutineUniform};
 use crate::program::shader::{build_shader, build_spirv_shader};
@@ -29,6 +30,7 @@ use crate::program::shader::{build_shader, build_spirv_shader};
 use crate::program::raw::RawProgram;
 
 use crate::vertex::VertexFormat;
+use crate::index::PrimitiveType;
 
 /// A combination of shaders linked together.
 pub struct Program {
@@ -74,6 +76,11 @@ impl Program {
                     has_tessellation_evaluation_shader = true;
                 }
 
+                // exceeding the limit would otherwise produce an obscure linking error
+                if let Some(gs) = geometry_shader {
+                    check_geometry_max_vertices(facade, gs)?;
+                }
+
                 // TODO: move somewhere else
                 if transform_feedback_varyings.is_some() &&
                     !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
@@ -290,6 +297,15 @@ impl Program {
         self.raw.transform_feedback_matches(format, stride)
     }
 
+    /// Same as `transform_feedback_matches`, but for the transform feedback buffer number
+    /// `index` of the program. Returns `false` if the buffer doesn't exist.
+    #[inline]
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        self.raw.transform_feedback_buffer_matches(index, format, stride)
+    }
+
     /// Returns the type of geometry that transform feedback would generate, or `None` if it
     /// depends on the vertex/index data passed when drawing.
     ///
@@ -300,6 +316,13 @@ impl Program {
         self.raw.get_output_primitives()
     }
 
+    /// Returns true if the geometry shader of the program, if there is one, takes `primitives`
+    /// as input.
+    #[inline]
+    pub fn accepts_input_primitives(&self, primitives: PrimitiveType) -> bool {
+        self.raw.accepts_input_primitives(primitives)
+    }
+
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
@@ -369,6 +392,26 @@ impl Program {
         self.raw.get_shader_storage_blocks()
     }
 
+    /// Returns the members of the uniform block or shader storage block with the given name,
+    /// with their offsets and strides as the implementation actually laid them out.
+    ///
+    /// Uniform blocks are searched first. Returns `None` if there is no such block.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # fn example(program: glium::Program) {
+    /// for member in program.get_block_members("MyBlock").unwrap() {
+    ///     println!("{} at offset {}, array stride {}", member.name, member.offset,
+    ///              member.array_stride);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn get_block_members(&self, block_name: &str) -> Option<&[BlockMember]> {
+        self.raw.get_block_members(block_name)
+    }
+
     /// Returns the list of shader storage blocks.
     ///
     /// ## Example
@@ -405,6 +448,25 @@ impl Program {
         &self.raw.get_subroutine_data().subroutine_uniforms
     }
 
+    /// Asks the driver to finish preparing the program for drawing.
+    ///
+    /// Glium compiles and links programs when they are created. However some drivers defer a
+    /// part of this work until the program is first used, which can cause a hitch in the middle
+    /// of a frame. This function binds the program and validates it, which usually triggers
+    /// this work. Whether this has any effect depends on the driver. To prepare the vertex
+    /// attributes as well, see `Context::warm_vao_cache`.
+    pub fn warm_up(&self) {
+        let mut ctxt = self.raw.get_context().make_current();
+        self.use_program(&mut ctxt);
+
+        unsafe {
+            match self.get_id() {
+                Handle::Id(id) => ctxt.gl.ValidateProgram(id),
+                Handle::Handle(id) => ctxt.gl.ValidateProgramARB(id),
+            }
+        }
+    }
+
     /// Returns true if the program has been configured to use the `gl_PointSize` variable.
     ///
     /// If the program uses `gl_PointSize` without having been configured appropriately, then
@@ -513,3 +575,88 @@ impl ProgramExt for Program {
         self.raw.get_subroutine_data()
     }
 }
+
+/// Checks the `max_vertices` declared in the source code of a geometry shader against the limit
+/// of the backend.
+fn check_geometry_max_vertices<F: ?Sized>(facade: &F, source: &str)
+                                          -> Result<(), ProgramCreationError> where F: Facade
+{
+    let max = match facade.get_context().get_capabilities().max_geometry_output_vertices {
+        Some(max) => max.max(0) as u32,
+        None => return Ok(()),
+    };
+
+    let declared = match parse_max_vertices(source) {
+        Some(declared) => declared,
+        None => return Ok(()),
+    };
+
+    if declared > max {
+        return Err(ProgramCreationError::GeometryMaxVerticesTooLarge { declared, max });
+    }
+
+    Ok(())
+}
+
+/// Finds the value of `max_vertices` in the layout qualifiers of a geometry shader's source.
+fn parse_max_vertices(source: &str) -> Option<u32> {
+    const KEYWORD: &str = "max_vertices";
+
+    let source = strip_comments(source);
+    let mut rest = &source[..];
+    while let Some(pos) = rest.find(KEYWORD) {
+        // ignoring identifiers that merely contain the keyword
+        let preceded_by_identifier = rest[.. pos].chars().next_back()
+                                                 .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        rest = &rest[pos + KEYWORD.len() ..];
+
+        if preceded_by_identifier {
+            continue;
+        }
+
+        let after = rest.trim_start();
+        let after = match after.strip_prefix('=') {
+            Some(after) => after.trim_start(),
+            None => continue,
+        };
+
+        let digits = after.bytes().take_while(|c| c.is_ascii_digit()).count();
+        if let Ok(value) = after[.. digits].parse() {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Replaces the `//` and `/* */` comments of GLSL source code with spaces.
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+
+    loop {
+        let line = rest.find("//");
+        let block = rest.find("/*");
+
+        let (start, end_marker) = match (line, block) {
+            (Some(l), Some(b)) if l < b => (l, "\n"),
+            (Some(l), None) => (l, "\n"),
+            (_, Some(b)) => (b, "*/"),
+            (None, None) => break,
+        };
+
+        result.push_str(&rest[.. start]);
+        result.push(' ');
+        rest = &rest[start + 2 ..];
+
+        match rest.find(end_marker) {
+            // the newline that ends a line comment is kept
+            Some(end) if end_marker == "\n" => rest = &rest[end ..],
+            Some(end) => rest = &rest[end + end_marker.len() ..],
+            None => return result,
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
@@ -35,19 +35,20 @@ use crate::program::{ProgramCreationError, Binary, GetBinaryError};
 use crate::program::uniforms_storage::UniformsStorage;
 
 use crate::program::compute::ComputeCommand;
-use crate::program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use crate::program::reflection::{Uniform, UniformBlock, BlockMember, OutputPrimitives};
 use crate::program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuffer};
 use crate::program::reflection::{SubroutineData, ShaderStage};
 use crate::program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use crate::program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
 use crate::program::reflection::{reflect_tess_eval_output_type, reflect_shader_storage_blocks};
-use crate::program::reflection::{reflect_subroutine_data};
+use crate::program::reflection::{reflect_subroutine_data, reflect_geometry_input_type};
 use crate::program::shader::Shader;
 use crate::program::binary_header::{attach_glium_header, process_glium_header};
 
 use crate::uniforms::Uniforms;
 
 use crate::vertex::VertexFormat;
+use crate::index::PrimitiveType;
 use crate::vertex_array_object::VertexAttributesSystem;
 
 /// A combination of shaders linked together.
@@ -57,13 +58,16 @@ pub struct RawProgram {
     uniform_values: UniformsStorage,
     uniforms: HashMap<String, Uniform, BuildHasherDefault<FnvHasher>>,
     uniform_blocks: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+    uniform_blocks_members: HashMap<String, Vec<BlockMember>, BuildHasherDefault<FnvHasher>>,
     subroutine_data: SubroutineData,
     attributes: HashMap<String, Attribute, BuildHasherDefault<FnvHasher>>,
     frag_data_locations: RefCell<HashMap<String, Option<u32>, BuildHasherDefault<FnvHasher>>>,
     tf_buffers: Vec<TransformFeedbackBuffer>,
     ssbos: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+    ssbos_members: HashMap<String, Vec<BlockMember>, BuildHasherDefault<FnvHasher>>,
     atomic_counters: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
     output_primitives: Option<OutputPrimitives>,
+    geometry_input_type: Option<gl::types::GLenum>,
     has_geometry_shader: bool,
     has_tessellation_control_shader: bool,
     has_tessellation_evaluation_shader: bool,
@@ -166,9 +170,9 @@ impl RawProgram {
 
         let (uniforms, atomic_counters) = unsafe { reflect_uniforms(&mut ctxt, id) };
         let attributes = unsafe { reflect_attributes(&mut ctxt, id) };
-        let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
+        let (blocks, blocks_members) = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
         let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
-        let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
+        let (ssbos, ssbos_members) = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
         let subroutine_data = unsafe {
             reflect_subroutine_data(&mut ctxt, id, has_geometry_shader,
                                     has_tessellation_control_shader,
@@ -183,19 +187,28 @@ impl RawProgram {
             None
         };
 
+        let geometry_input_type = if has_geometry_shader {
+            Some(unsafe { reflect_geometry_input_type(&mut ctxt, id) })
+        } else {
+            None
+        };
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id,
             uniforms,
             uniform_values: UniformsStorage::new(),
             uniform_blocks: blocks,
+            uniform_blocks_members: blocks_members,
             subroutine_data,
             attributes,
             frag_data_locations: RefCell::new(HashMap::with_hasher(Default::default())),
             tf_buffers,
             ssbos,
+            ssbos_members,
             atomic_counters,
             output_primitives,
+            geometry_input_type,
             has_geometry_shader,
             has_tessellation_control_shader,
             has_tessellation_evaluation_shader,
@@ -234,7 +247,8 @@ impl RawProgram {
             id
         };
 
-        let ((uniforms, atomic_counters), attributes, blocks, tf_buffers, ssbos, subroutine_data) = unsafe {
+        let ((uniforms, atomic_counters), attributes, (blocks, blocks_members), tf_buffers,
+             (ssbos, ssbos_members), subroutine_data) = unsafe {
             (
                 reflect_uniforms(&mut ctxt, id),
                 reflect_attributes(&mut ctxt, id),
@@ -255,25 +269,40 @@ impl RawProgram {
             None
         };
 
+        let geometry_input_type = if has_geometry_shader {
+            Some(unsafe { reflect_geometry_input_type(&mut ctxt, id) })
+        } else {
+            None
+        };
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id,
             uniforms,
             uniform_values: UniformsStorage::new(),
             uniform_blocks: blocks,
+            uniform_blocks_members: blocks_members,
             subroutine_data,
             attributes,
             frag_data_locations: RefCell::new(HashMap::with_hasher(Default::default())),
             tf_buffers,
             ssbos,
+            ssbos_members,
             atomic_counters,
             output_primitives,
+            geometry_input_type,
             has_geometry_shader,
             has_tessellation_control_shader,
             has_tessellation_evaluation_shader,
         })
     }
 
+    /// Returns the context this program belongs to.
+    #[inline]
+    pub fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -415,13 +444,22 @@ impl RawProgram {
     ///
     /// The correctness of semantic meaning(i.e. naming) between vertex attributes is the responsibility of user.
     pub fn transform_feedback_matches(&self, format: &VertexFormat, stride: usize) -> bool {
-        // TODO: doesn't support multiple buffers
-
-        if self.get_transform_feedback_buffers().len() != 1 {
-            return false;
-        }
+        self.get_transform_feedback_buffers().len() == 1 &&
+            self.transform_feedback_buffer_matches(0, format, stride)
+    }
 
-        let buf = &self.get_transform_feedback_buffers()[0];
+    /// Same as `transform_feedback_matches`, but for the transform feedback buffer number
+    /// `index` of the program. Returns `false` if the buffer doesn't exist.
+    ///
+    /// Programs whose varyings are captured with `TransformFeedbackMode::Separate` have one
+    /// buffer per varying.
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        let buf = match self.get_transform_feedback_buffers().get(index) {
+            Some(buf) => buf,
+            None => return false,
+        };
 
         if buf.stride != stride {
             return false;
@@ -453,6 +491,33 @@ impl RawProgram {
         self.output_primitives
     }
 
+    /// Returns true if the geometry shader of the program, if there is one, takes `primitives`
+    /// as input.
+    ///
+    /// The input layout of a geometry shader must match the type of primitives of the draw
+    /// command, unless a tessellation evaluation shader is in between.
+    pub fn accepts_input_primitives(&self, primitives: PrimitiveType) -> bool {
+        if self.has_tessellation_evaluation_shader {
+            return true;
+        }
+
+        match (self.geometry_input_type, primitives) {
+            (None, _) => true,
+            (Some(gl::POINTS), PrimitiveType::Points) => true,
+            (Some(gl::LINES), PrimitiveType::LinesList) |
+            (Some(gl::LINES), PrimitiveType::LineStrip) |
+            (Some(gl::LINES), PrimitiveType::LineLoop) => true,
+            (Some(gl::LINES_ADJACENCY), PrimitiveType::LinesListAdjacency) |
+            (Some(gl::LINES_ADJACENCY), PrimitiveType::LineStripAdjacency) => true,
+            (Some(gl::TRIANGLES), PrimitiveType::TrianglesList) |
+            (Some(gl::TRIANGLES), PrimitiveType::TriangleStrip) |
+            (Some(gl::TRIANGLES), PrimitiveType::TriangleFan) => true,
+            (Some(gl::TRIANGLES_ADJACENCY), PrimitiveType::TrianglesListAdjacency) |
+            (Some(gl::TRIANGLES_ADJACENCY), PrimitiveType::TriangleStripAdjacency) => true,
+            _ => false,
+        }
+    }
+
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
@@ -516,6 +581,16 @@ impl RawProgram {
         &self.ssbos
     }
 
+    /// Returns the members of the uniform block or shader storage block with the given name.
+    ///
+    /// Uniform blocks are searched first.
+    #[inline]
+    pub fn get_block_members(&self, block_name: &str) -> Option<&[BlockMember]> {
+        self.uniform_blocks_members.get(block_name)
+            .or_else(|| self.ssbos_members.get(block_name))
+            .map(|members| &members[..])
+    }
+
     /// Returns the list of atomic_counters
     ///
     /// ## Example
@@ -54,6 +54,35 @@ pub struct UniformBlock {
     pub layout: BlockLayout,
 }
 
+/// Layout of a basic member of a uniform block or shader storage block, as reported by the
+/// implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMember {
+    /// Full name of the member, for example `lights[0].position`.
+    pub name: String,
+
+    /// Offset in bytes of the member from the start of the buffer.
+    pub offset: usize,
+
+    /// Type of the member.
+    pub ty: UniformType,
+
+    /// Number of elements if the member is an array, or 1 otherwise. 0 for an array whose size
+    /// isn't known at compile-time.
+    pub array_size: usize,
+
+    /// Number of bytes between two consecutive elements of the array, or 0 if the member isn't
+    /// an array.
+    pub array_stride: usize,
+
+    /// Number of bytes between two consecutive columns of a column-major matrix or two
+    /// consecutive rows of a row-major matrix, or 0 if the member isn't a matrix.
+    pub matrix_stride: usize,
+
+    /// True if the member is a matrix stored in row-major order.
+    pub row_major: bool,
+}
+
 /// Layout of a shader storage buffer or a uniform buffer.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BlockLayout {
@@ -426,11 +455,13 @@ pub unsafe fn reflect_attributes(ctxt: &mut CommandContext<'_>, program: Handle)
 }
 
 pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Handle)
-                                     -> HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>
+                                     -> (HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+                                         HashMap<String, Vec<BlockMember>, BuildHasherDefault<FnvHasher>>)
 {
     // uniform blocks are not supported, so there's none
     if !(ctxt.version >= &Version(Api::Gl, 3, 1) || ctxt.version >= &Version(Api::GlEs, 3, 0)) {
-        return HashMap::with_hasher(Default::default());
+        return (HashMap::with_hasher(Default::default()),
+                HashMap::with_hasher(Default::default()));
     }
 
     let program = match program {
@@ -445,7 +476,8 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Han
     //              even though they report OpenGL ES 3.1. So we return early on if possible.
     // TODO: find a better work-around ^
     if active_blocks == 0 {
-        return HashMap::with_hasher(Default::default());
+        return (HashMap::with_hasher(Default::default()),
+                HashMap::with_hasher(Default::default()));
     }
 
     let mut active_blocks_max_name_len: gl::types::GLint = 0;
@@ -454,6 +486,8 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Han
 
     let mut blocks = HashMap::with_hasher(Default::default());
     blocks.reserve(active_blocks as usize);
+    let mut blocks_members = HashMap::with_hasher(Default::default());
+    blocks_members.reserve(active_blocks as usize);
 
     for block_id in 0 .. active_blocks {
         // getting the name of the block
@@ -515,6 +549,22 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Han
         ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
                                     gl::UNIFORM_NAME_LENGTH, member_name_len.as_mut_ptr());
 
+        // getting the strides and the matrix order of the members
+        let mut member_array_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                            .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_ARRAY_STRIDE, member_array_stride.as_mut_ptr());
+
+        let mut member_matrix_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                             .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_MATRIX_STRIDE, member_matrix_stride.as_mut_ptr());
+
+        let mut member_row_major = ::std::iter::repeat(0).take(num_members as usize)
+                                                         .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_IS_ROW_MAJOR, member_row_major.as_mut_ptr());
+
         // getting the names of the members
         let member_names = member_name_len.iter().zip(members_indices.iter())
                                           .map(|(&name_len, &index)|
@@ -530,21 +580,32 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Han
 
         // now computing the list of members
         let members = member_names.enumerate().map(|(index, name)| {
-            (name, member_offsets[index] as usize,
-             glenum_to_uniform_type(member_types[index] as gl::types::GLenum),
-             member_size[index] as usize, None)
-        });
+            BlockMember {
+                name,
+                offset: member_offsets[index] as usize,
+                ty: glenum_to_uniform_type(member_types[index] as gl::types::GLenum),
+                array_size: member_size[index] as usize,
+                array_stride: member_array_stride[index] as usize,
+                matrix_stride: member_matrix_stride[index] as usize,
+                row_major: member_row_major[index] != 0,
+            }
+        }).collect::<Vec<_>>();
+
+        let layout = introspection_output_to_layout(members.iter().map(|m| {
+            (m.name.clone(), m.offset, m.ty, m.array_size, None)
+        }));
 
         // finally inserting into the blocks list
+        blocks_members.insert(name.clone(), members);
         blocks.insert(name, UniformBlock {
             id: block_id as i32,
             initial_binding: binding as i32,
             size: block_size as usize,
-            layout: introspection_output_to_layout(members),
+            layout,
         });
     }
 
-    blocks
+    (blocks, blocks_members)
 }
 
 pub unsafe fn reflect_transform_feedback(ctxt: &mut CommandContext<'_>, program: Handle)
@@ -708,6 +769,32 @@ pub unsafe fn reflect_geometry_output_type(ctxt: &mut CommandContext<'_>, progra
     }
 }
 
+/// Obtains the type of primitives that the geometry shader stage takes as input.
+///
+/// # Unsafety
+///
+/// - `program` must be a valid handle to a program.
+/// - The program **must** contain a geometry shader.
+pub unsafe fn reflect_geometry_input_type(ctxt: &mut CommandContext<'_>, program: Handle)
+                                          -> gl::types::GLenum
+{
+    let mut value = 0;
+
+    match program {
+        Handle::Id(program) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(program, gl::GEOMETRY_INPUT_TYPE, &mut value);
+        },
+        Handle::Handle(program) => {
+            assert!(ctxt.extensions.gl_arb_vertex_shader);
+            ctxt.gl.GetObjectParameterivARB(program, gl::GEOMETRY_INPUT_TYPE, &mut value);
+        }
+    };
+
+    value as gl::types::GLenum
+}
+
 /// Obtains the type of data that the tessellation evaluation shader stage outputs.
 ///
 /// # Unsafety
@@ -741,18 +828,21 @@ pub unsafe fn reflect_tess_eval_output_type(ctxt: &mut CommandContext<'_>, progr
 
 /// Returns the list of shader storage blocks of a program.
 pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext<'_>, program: Handle)
-    -> HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>
+    -> (HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
+        HashMap<String, Vec<BlockMember>, BuildHasherDefault<FnvHasher>>)
 {
     if !(ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
          (ctxt.extensions.gl_arb_program_interface_query && ctxt.extensions.gl_arb_shader_storage_buffer_object))
     {
         // not supported
-        return HashMap::with_hasher(Default::default());
+        return (HashMap::with_hasher(Default::default()),
+                HashMap::with_hasher(Default::default()));
     }
 
     let program = match program {
         Handle::Id(program) => program,
-        Handle::Handle(program) => return HashMap::with_hasher(Default::default())
+        Handle::Handle(program) => return (HashMap::with_hasher(Default::default()),
+                                           HashMap::with_hasher(Default::default()))
     };
 
     // number of active SSBOs
@@ -766,6 +856,8 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext<'_>, progr
     // the result of this function
     let mut blocks = HashMap::with_hasher(Default::default());
     blocks.reserve(active_blocks as usize);
+    let mut blocks_members = HashMap::with_hasher(Default::default());
+    blocks_members.reserve(active_blocks as usize);
 
     for block_id in 0 .. active_blocks {
         // getting basic infos
@@ -803,17 +895,15 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext<'_>, progr
 
         // iterator over variables
         let members = active_variables.into_iter().map(|variable| {
-            let (ty, array_size, offset, _array_stride, name_len, top_level_array_size) = {
-                let mut output: [gl::types::GLint; 6] = [0; 6];
-                ctxt.gl.GetProgramResourceiv(program, gl::BUFFER_VARIABLE,
-                                             variable as gl::types::GLuint, 6,
-                                             [gl::TYPE, gl::ARRAY_SIZE, gl::OFFSET,
-                                              gl::ARRAY_STRIDE, gl::NAME_LENGTH,
-                                              gl::TOP_LEVEL_ARRAY_SIZE].as_ptr(), 6,
-                                             ptr::null_mut(), output.as_mut_ptr() as *mut _);
-                (glenum_to_uniform_type(output[0] as gl::types::GLenum), output[1] as usize,
-                 output[2] as usize, output[3] as usize, output[4] as usize, output[5] as usize)
-            };
+            let mut output: [gl::types::GLint; 8] = [0; 8];
+            ctxt.gl.GetProgramResourceiv(program, gl::BUFFER_VARIABLE,
+                                         variable as gl::types::GLuint, 8,
+                                         [gl::TYPE, gl::ARRAY_SIZE, gl::OFFSET,
+                                          gl::ARRAY_STRIDE, gl::NAME_LENGTH,
+                                          gl::TOP_LEVEL_ARRAY_SIZE, gl::MATRIX_STRIDE,
+                                          gl::IS_ROW_MAJOR].as_ptr(), 8,
+                                         ptr::null_mut(), output.as_mut_ptr() as *mut _);
+            let name_len = output[4] as usize;
 
             let name = {
                 let mut name_tmp: Vec<u8> = Vec::with_capacity(1 + name_len);
@@ -827,19 +917,34 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext<'_>, progr
                 String::from_utf8(name_tmp).unwrap()
             };
 
-            (name, offset, ty, array_size, Some(top_level_array_size))
-        });
+            let member = BlockMember {
+                name,
+                offset: output[2] as usize,
+                ty: glenum_to_uniform_type(output[0] as gl::types::GLenum),
+                array_size: output[1] as usize,
+                array_stride: output[3] as usize,
+                matrix_stride: output[6] as usize,
+                row_major: output[7] != 0,
+            };
+
+            (member, output[5] as usize)
+        }).collect::<Vec<_>>();
+
+        let layout = introspection_output_to_layout(members.iter().map(|(m, top_level_array_size)| {
+            (m.name.clone(), m.offset, m.ty, m.array_size, Some(*top_level_array_size))
+        }));
 
         // finally inserting into the blocks list
+        blocks_members.insert(name.clone(), members.into_iter().map(|(m, _)| m).collect());
         blocks.insert(name, UniformBlock {
             id: block_id as i32,
             initial_binding: binding as i32,
             size: total_size,
-            layout: introspection_output_to_layout(members),
+            layout,
         });
     }
 
-    blocks
+    (blocks, blocks_members)
 }
 
 /// Takes a list of elements produced by OpenGL's introspection API and turns them into
@@ -92,13 +92,20 @@ impl Drop for SamplerObject {
     }
 }
 
+/// Returns true if the backend supports sampler objects.
+#[inline]
+pub fn is_sampler_objects_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_sampler_objects
+}
+
 /// Returns the sampler corresponding to the given behavior, or a draw error if
 /// samplers are not supported.
 pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
                    -> Result<gl::types::GLuint, DrawError>
 {
     // checking for compatibility
-    if ctxt.version < &Version(Api::Gl, 3, 2) && !ctxt.extensions.gl_arb_sampler_objects {
+    if !is_sampler_objects_supported(ctxt) {
         return Err(DrawError::SamplersNotSupported);
     }
 
@@ -113,3 +120,53 @@ pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
     ctxt.samplers.insert(*behavior, sampler);
     Ok(id)
 }
+
+/// Sets the sampling parameters of the texture bound to `bind_point` on the active texture unit.
+///
+/// This is used instead of sampler objects when the backend doesn't support them. The parameters
+/// are stored in the texture itself and stay in place until they are changed again.
+pub fn set_texture_parameters(ctxt: &mut CommandContext<'_>, bind_point: gl::types::GLenum,
+                              behavior: &SamplerBehavior)
+{
+    unsafe {
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_S,
+                              behavior.wrap_function.0.to_glenum() as gl::types::GLint);
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_T,
+                              behavior.wrap_function.1.to_glenum() as gl::types::GLint);
+
+        // OpenGL ES 2.0 only knows about the R coordinate for 3D textures
+        if ctxt.version >= &Version(Api::Gl, 1, 2) || bind_point == gl::TEXTURE_3D {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_R,
+                                  behavior.wrap_function.2.to_glenum() as gl::types::GLint);
+        }
+
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
+                              behavior.minify_filter.to_glenum() as gl::types::GLint);
+        ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER,
+                              behavior.magnify_filter.to_glenum() as gl::types::GLint);
+
+        if let Some(dtc) = behavior.depth_texture_comparison {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_COMPARE_MODE,
+                                  gl::COMPARE_R_TO_TEXTURE as gl::types::GLint);
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_COMPARE_FUNC,
+                                  dtc.to_glenum() as gl::types::GLint);
+
+        } else if ctxt.version >= &Version(Api::Gl, 1, 4) ||
+                  ctxt.version >= &Version(Api::GlEs, 3, 0)
+        {
+            // the texture may have been used with a comparison before
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_COMPARE_MODE,
+                                  gl::NONE as gl::types::GLint);
+        }
+
+        if let Some(max_value) = ctxt.capabilities.max_texture_max_anisotropy {
+            let value = if behavior.max_anisotropy as f32 > max_value {
+                max_value
+            } else {
+                behavior.max_anisotropy as f32
+            };
+
+            ctxt.gl.TexParameterf(bind_point, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
+        }
+    }
+}
@@ -13,11 +13,13 @@ use crate::TextureExt;
 use crate::TextureMipmapExt;
 use crate::version::Api;
 use crate::Rect;
+use crate::ToGlEnum;
 
 use crate::image_format::{self, TextureFormatRequest, ClientFormatAny};
 use crate::texture::Texture2dDataSink;
 use crate::texture::TextureKind;
 use crate::texture::{MipmapsOption, TextureFormat, TextureCreationError, CubeLayer};
+use crate::texture::{Swizzle, SwizzleNotSupportedError, TextureViewError};
 use crate::texture::{get_format, InternalFormat, GetFormatError};
 use crate::texture::pixel::PixelValue;
 use crate::texture::pixel_buffer::PixelBuffer;
@@ -129,6 +131,37 @@ unsafe fn generate_mipmaps(ctxt: &CommandContext<'_>,
     }
 }
 
+/// Returns true if `glTexStorage1D/2D/3D` can be used to allocate immutable storage.
+#[inline]
+fn is_texture_storage_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+    ctxt.extensions.gl_arb_texture_storage
+}
+
+/// Returns the view compatibility class of a sized internal format, as defined by
+/// `GL_ARB_texture_view`. Formats without a class are only compatible with themselves.
+fn view_class(format: gl::types::GLenum) -> Option<u8> {
+    match format {
+        gl::RGBA32F | gl::RGBA32UI | gl::RGBA32I => Some(0),
+        gl::RGB32F | gl::RGB32UI | gl::RGB32I => Some(1),
+        gl::RGBA16F | gl::RG32F | gl::RGBA16UI | gl::RG32UI | gl::RGBA16I | gl::RG32I |
+        gl::RGBA16 | gl::RGBA16_SNORM => Some(2),
+        gl::RGB16 | gl::RGB16_SNORM | gl::RGB16F | gl::RGB16UI | gl::RGB16I => Some(3),
+        gl::RG16F | gl::R11F_G11F_B10F | gl::R32F | gl::RGB10_A2UI | gl::RGBA8UI | gl::RG16UI |
+        gl::R32UI | gl::RGBA8I | gl::RG16I | gl::R32I | gl::RGB10_A2 | gl::RGBA8 | gl::RG16 |
+        gl::RGBA8_SNORM | gl::RG16_SNORM | gl::SRGB8_ALPHA8 | gl::RGB9_E5 => Some(4),
+        gl::RGB8 | gl::RGB8_SNORM | gl::SRGB8 | gl::RGB8UI | gl::RGB8I => Some(5),
+        gl::R16F | gl::RG8UI | gl::R16UI | gl::RG8I | gl::R16I | gl::RG8 | gl::R16 |
+        gl::RG8_SNORM | gl::R16_SNORM => Some(6),
+        gl::R8UI | gl::R8I | gl::R8 | gl::R8_SNORM => Some(7),
+        gl::COMPRESSED_RED_RGTC1 | gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(8),
+        gl::COMPRESSED_RG_RGTC2 | gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(9),
+        gl::COMPRESSED_RGBA_BPTC_UNORM | gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => Some(10),
+        gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT | gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => Some(11),
+        _ => None,
+    }
+}
+
 /// Builds a new texture.
 ///
 /// # Panic
@@ -170,9 +203,33 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         return Err(TextureCreationError::DimensionsNotSupported);
     }
 
+    // checking the number of samples against what the implementation reports for this format
+    if let Some(samples) = samples {
+        if samples == 0 {
+            return Err(TextureCreationError::SamplesNotSupported);
+        }
+
+        if let TextureFormatRequest::Specific(format) = format {
+            let supported = facade.get_context().get_capabilities().internal_formats_textures
+                                  .get(&format).and_then(|infos| infos.multisamples.as_ref());
+
+            if let Some(supported) = supported {
+                // the implementation is free to use more samples than requested
+                if !supported.iter().any(|&s| s as u32 >= samples) {
+                    return Err(TextureCreationError::SamplesNotSupported);
+                }
+            }
+        }
+    }
+
     let should_generate_mipmaps = mipmaps.should_generate();
     let texture_levels = mipmaps.num_levels(width, height, depth) as gl::types::GLsizei;
 
+    // multisample textures only have a main level
+    if samples.is_some() && texture_levels > 1 {
+        return Err(TextureCreationError::MipmapsNotSupported);
+    }
+
     let teximg_internal_format = image_format::format_request_to_glenum(facade.get_context(), format, image_format::RequestType::TexImage(data.as_ref().map(|&(c, _)| c)))?;
     let storage_internal_format = image_format::format_request_to_glenum(facade.get_context(), format, image_format::RequestType::TexStorage).ok();
 
@@ -283,7 +340,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if storage_internal_format.is_some() && is_texture_storage_supported(&ctxt) {
                 ctxt.gl.TexStorage3D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width, height, depth);
@@ -323,7 +380,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if storage_internal_format.is_some() && is_texture_storage_supported(&ctxt) {
                 ctxt.gl.TexStorage2D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width, height);
@@ -415,7 +472,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if storage_internal_format.is_some() && is_texture_storage_supported(&ctxt) {
                 ctxt.gl.TexStorage1D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width);
@@ -451,6 +508,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         id
     };
 
+    ctxt.log_errors("texture creation");
     Ok(TextureAny {
         context: facade.get_context().clone(),
         id,
@@ -506,7 +564,6 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
                                               ty: Dimensions,
                                               params: super::ImportParameters,
                                               fd: std::fs::File) -> Result<TextureAny,super::TextureImportError> {
-    use crate::ToGlEnum;
     let memory = MemoryObject::new_from_fd(facade, params.dedicated_memory, fd, params.size)?;
     
     let (width, height, depth, array_size, samples) = extract_dimensions(ty);
@@ -611,7 +668,8 @@ pub unsafe fn new_from_fd<F: Facade + ?Sized>(facade: &F,
 
         id
     };
-    
+
+    ctxt.log_errors("texture creation");
     Ok(TextureAny {
         context: facade.get_context().clone(),
         id,
@@ -821,6 +879,58 @@ impl TextureAny {
         })
     }
 
+    /// Changes the source of the red, green, blue and alpha components returned when sampling
+    /// this texture.
+    ///
+    /// For example, a single-channel texture returns `(r, 0, 0, 1)` by default. Passing
+    /// `[Swizzle::Red, Swizzle::Red, Swizzle::Red, Swizzle::One]` makes it return
+    /// `(r, r, r, 1)` instead, which is what you want for grayscale images.
+    pub fn set_swizzle(&self, swizzle: [Swizzle; 4]) -> Result<(), SwizzleNotSupportedError> {
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 3)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+           !ctxt.extensions.gl_arb_texture_swizzle && !ctxt.extensions.gl_ext_texture_swizzle
+        {
+            return Err(SwizzleNotSupportedError);
+        }
+
+        let bind_point = self.bind_to_current(&mut ctxt);
+        let names = [gl::TEXTURE_SWIZZLE_R, gl::TEXTURE_SWIZZLE_G, gl::TEXTURE_SWIZZLE_B,
+                     gl::TEXTURE_SWIZZLE_A];
+
+        for (&name, component) in names.iter().zip(swizzle.iter()) {
+            unsafe {
+                ctxt.gl.TexParameteri(bind_point, name,
+                                      component.to_glenum() as gl::types::GLint);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the storage of this texture is immutable, in other words if it was
+    /// allocated with `glTexStorage*`.
+    ///
+    /// Textures created by glium use immutable storage whenever the backend supports
+    /// OpenGL 4.2, OpenGL ES 3.0 or `GL_ARB_texture_storage`, except for multisample textures
+    /// which also require `glTexStorage*Multisample`.
+    pub fn has_immutable_storage(&self) -> bool {
+        let mut ctxt = self.context.make_current();
+
+        if !is_texture_storage_supported(&ctxt) {
+            return false;
+        }
+
+        let bind_point = self.bind_to_current(&mut ctxt);
+
+        unsafe {
+            let mut immutable = 0;
+            ctxt.gl.GetTexParameteriv(bind_point, gl::TEXTURE_IMMUTABLE_FORMAT, &mut immutable);
+            immutable != 0
+        }
+    }
+
     /// Binds this texture and generates mipmaps.
     #[inline]
     pub unsafe fn generate_mipmaps(&self) {
@@ -828,6 +938,146 @@ impl TextureAny {
         self.bind_to_current(&mut ctxt);
         generate_mipmaps(&ctxt, self.get_bind_point());
     }
+
+    /// Builds a view of this texture that interprets its content with another format.
+    ///
+    /// The view covers the mipmap levels `min_level .. min_level + num_levels` and the layers
+    /// `min_layer .. min_layer + num_layers` of this texture, and has the same kind of
+    /// dimensions. For textures that aren't arrays, the layers must be `0 .. 1`. For cubemaps
+    /// the layers are faces and must be `0 .. 6`, and for cubemap arrays the number of layers
+    /// must be a multiple of 6.
+    ///
+    /// No data is copied. The view and this texture share the same storage, which is kept
+    /// alive by OpenGL as long as either of them exists.
+    ///
+    /// The texture must have been created with immutable storage, and `format` must belong to
+    /// the same compatibility class as the format of the texture. For example a `U8U8U8U8`
+    /// texture can be viewed as `UncompressedUintFormat::U8U8U8U8` or as `SrgbFormat::U8U8U8U8`.
+    ///
+    /// ## Features
+    ///
+    /// Only available if the backend supports OpenGL 4.3 or `GL_ARB_texture_view`.
+    pub fn create_view(&self, format: TextureFormat, min_level: u32, num_levels: u32,
+                       min_layer: u32, num_layers: u32) -> Result<TextureAny, TextureViewError>
+    {
+        match min_level.checked_add(num_levels) {
+            Some(end) if num_levels >= 1 && end <= self.levels => (),
+            _ => return Err(TextureViewError::InvalidLevels),
+        };
+
+        let layers_end = match min_layer.checked_add(num_layers) {
+            Some(end) if num_layers >= 1 => end,
+            _ => return Err(TextureViewError::InvalidLayers),
+        };
+
+        let shrink = |size: u32| cmp::max(1, size >> min_level);
+        let single_layer = min_layer == 0 && num_layers == 1;
+
+        let ty = match self.ty {
+            Dimensions::Texture1d { width } if single_layer => {
+                Dimensions::Texture1d { width: shrink(width) }
+            },
+            Dimensions::Texture1dArray { width, array_size } if layers_end <= array_size => {
+                Dimensions::Texture1dArray { width: shrink(width), array_size: num_layers }
+            },
+            Dimensions::Texture2d { width, height } if single_layer => {
+                Dimensions::Texture2d { width: shrink(width), height: shrink(height) }
+            },
+            Dimensions::Texture2dArray { width, height, array_size } if layers_end <= array_size => {
+                Dimensions::Texture2dArray { width: shrink(width), height: shrink(height),
+                                             array_size: num_layers }
+            },
+            Dimensions::Texture2dMultisample { width, height, samples } if single_layer => {
+                Dimensions::Texture2dMultisample { width, height, samples }
+            },
+            Dimensions::Texture2dMultisampleArray { width, height, array_size, samples }
+                if layers_end <= array_size =>
+            {
+                Dimensions::Texture2dMultisampleArray { width, height, array_size: num_layers,
+                                                        samples }
+            },
+            Dimensions::Texture3d { width, height, depth } if single_layer => {
+                Dimensions::Texture3d { width: shrink(width), height: shrink(height),
+                                        depth: shrink(depth) }
+            },
+            Dimensions::Cubemap { dimension } if min_layer == 0 && num_layers == 6 => {
+                Dimensions::Cubemap { dimension: shrink(dimension) }
+            },
+            Dimensions::CubemapArray { dimension, array_size }
+                if num_layers.is_multiple_of(6) && layers_end <= array_size * 6 =>
+            {
+                Dimensions::CubemapArray { dimension: shrink(dimension),
+                                           array_size: num_layers / 6 }
+            },
+            _ => return Err(TextureViewError::InvalidLayers),
+        };
+
+        if !(self.context.get_version() >= &Version(Api::Gl, 4, 3)) &&
+           !self.context.get_extensions().gl_arb_texture_view
+        {
+            return Err(TextureViewError::NotSupported);
+        }
+
+        let parent_format = image_format::format_request_to_glenum(&self.context,
+                                                                   self.requested_format,
+                                                                   image_format::RequestType::TexStorage);
+        let view_format = format.to_glenum();
+        match parent_format {
+            Ok(f) if f == view_format => (),
+            Ok(f) if view_class(f).is_some() && view_class(f) == view_class(view_format) => (),
+            _ => return Err(TextureViewError::IncompatibleFormat),
+        };
+
+        if !self.has_immutable_storage() {
+            return Err(TextureViewError::MutableStorage);
+        }
+
+        let mut ctxt = self.context.make_current();
+        let bind_point = self.bind_to_current(&mut ctxt);
+
+        let id = unsafe {
+            let mut id: gl::types::GLuint = 0;
+            ctxt.gl.GenTextures(1, &mut id);
+            ctxt.gl.TextureView(id, bind_point, self.id, view_format, min_level, num_levels,
+                                min_layer, num_layers);
+
+            ctxt.gl.BindTexture(bind_point, id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+
+            let is_multisampled = matches!(ty, Dimensions::Texture2dMultisample {..}
+                | Dimensions::Texture2dMultisampleArray {..});
+
+            if !is_multisampled {
+                let (filtering, mipmap_filtering) = match format {
+                    TextureFormat::UncompressedIntegral(_) |
+                    TextureFormat::UncompressedUnsigned(_) |
+                    TextureFormat::StencilFormat(_) => (gl::NEAREST, gl::NEAREST_MIPMAP_NEAREST),
+                    _ => (gl::LINEAR, gl::LINEAR_MIPMAP_LINEAR),
+                };
+
+                let min_filtering = if num_levels > 1 { mipmap_filtering } else { filtering };
+                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
+                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER, min_filtering as i32);
+            }
+
+            id
+        };
+
+        ctxt.log_errors("texture view creation");
+        Ok(TextureAny {
+            context: self.context.clone(),
+            id,
+            requested_format: TextureFormatRequest::Specific(format),
+            actual_format: Cell::new(None),
+            ty,
+            levels: num_levels,
+            generate_mipmaps: false,
+            owned: true,
+            memory: None,
+            latest_shader_write: Cell::new(self.latest_shader_write.get()),
+        })
+    }
 }
 
 impl TextureExt for TextureAny {
@@ -1594,16 +1844,27 @@ impl<'a> TextureAnyImage<'a> {
     /// - Panics if it fails to read the texture.
     ///
     pub fn raw_read<T, P>(&self, rect: &Rect) -> T where T: Texture2dDataSink<P>, P: PixelValue {
+        self.try_raw_read(rect).unwrap()
+    }
+
+    /// Reads the content of the image, or returns an error if the pixel format can't be read.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the rect is out of range.
+    ///
+    pub fn try_raw_read<T, P>(&self, rect: &Rect) -> Result<T, ops::ReadError>
+                              where T: Texture2dDataSink<P>, P: PixelValue
+    {
         assert!(rect.left + rect.width <= self.width);
         assert!(rect.bottom + rect.height <= self.height.unwrap_or(1));
 
         let mut ctxt = self.texture.context.make_current();
 
         let mut data = Vec::new();
-        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, &mut data, false)
-            .unwrap();
+        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, &mut data, false)?;
 
-        T::from_raw(Cow::Owned(data), self.width, self.height.unwrap_or(1))
+        Ok(T::from_raw(Cow::Owned(data), self.width, self.height.unwrap_or(1)))
     }
 
     /// Reads the content of the image to a pixel buffer.
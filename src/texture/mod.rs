@@ -116,6 +116,8 @@ use std::borrow::Cow;
 use std::fmt;
 use std::error::Error;
 
+use crate::gl;
+use crate::ToGlEnum;
 use crate::image_format::FormatNotSupportedError;
 
 pub use crate::image_format::{ClientFormat, TextureFormat};
@@ -126,7 +128,7 @@ pub use self::any::{TextureAny, TextureAnyMipmap, TextureAnyLayer, TextureAnyLay
 pub use self::any::{TextureAnyImage, Dimensions};
 pub use self::bindless::{ResidentTexture, TextureHandle, BindlessTexturesNotSupportedError};
 pub use self::get_format::{InternalFormat, InternalFormatType, GetFormatError};
-pub use self::pixel::PixelValue;
+pub use self::pixel::{PixelValue, Bgr, Bgra};
 pub use self::ty_support::{is_texture_1d_supported, is_texture_2d_supported};
 pub use self::ty_support::{is_texture_3d_supported, is_texture_1d_array_supported};
 pub use self::ty_support::{is_texture_2d_array_supported, is_texture_2d_multisample_supported};
@@ -195,6 +197,53 @@ pub enum TextureKind {
     DepthStencil,
 }
 
+/// Source of a component of the color returned when sampling a texture.
+///
+/// See `TextureAny::set_swizzle`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Swizzle {
+    /// The red component of the texture.
+    Red,
+    /// The green component of the texture.
+    Green,
+    /// The blue component of the texture.
+    Blue,
+    /// The alpha component of the texture.
+    Alpha,
+    /// The constant `0`.
+    Zero,
+    /// The constant `1`.
+    One,
+}
+
+impl ToGlEnum for Swizzle {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            Swizzle::Red => gl::RED,
+            Swizzle::Green => gl::GREEN,
+            Swizzle::Blue => gl::BLUE,
+            Swizzle::Alpha => gl::ALPHA,
+            Swizzle::Zero => gl::ZERO,
+            Swizzle::One => gl::ONE,
+        }
+    }
+}
+
+/// Texture swizzling is not supported by the backend.
+///
+/// It requires OpenGL 3.3, OpenGL ES 3.0, `GL_ARB_texture_swizzle` or `GL_EXT_texture_swizzle`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SwizzleNotSupportedError;
+
+impl fmt::Display for SwizzleNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Texture swizzling is not supported by the backend")
+    }
+}
+
+impl Error for SwizzleNotSupportedError {}
+
 /// Describes what to do about mipmaps during texture creation.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum MipmapsOption {
@@ -687,6 +736,72 @@ impl_2d_sink_for_raw_image!((f32, f32));
 impl_2d_sink_for_raw_image!((f32, f32, f32));
 impl_2d_sink_for_raw_image!((f32, f32, f32, f32));
 
+#[cfg(feature = "image")]
+impl Texture2dDataSink<(u8, u8, u8, u8)> for image::RgbaImage {
+    fn from_raw(data: Cow<'_, [(u8, u8, u8, u8)]>, width: u32, height: u32) -> Self {
+        // the first row of OpenGL data is the bottom of the image, while the first row of an
+        // `ImageBuffer` is the top of the image
+        let mut buffer = Vec::with_capacity(data.len() * 4);
+        if width != 0 {
+            for row in data.chunks(width as usize).rev() {
+                for &(r, g, b, a) in row {
+                    buffer.extend_from_slice(&[r, g, b, a]);
+                }
+            }
+        }
+
+        image::RgbaImage::from_raw(width, height, buffer).unwrap()
+    }
+}
+
+#[cfg(feature = "image")]
+impl Texture2d {
+    /// Reads the content of the texture into an `image::RgbaImage`.
+    ///
+    /// The rows are flipped so that the first row of the image is the top of the texture, which
+    /// means that the result can be saved directly.
+    ///
+    /// Only available if the `image` feature is enabled.
+    #[inline]
+    pub fn read_to_image(&self) -> image::RgbaImage {
+        self.read()
+    }
+
+    /// Builds a new texture by uploading an image decoded by the `image` crate.
+    ///
+    /// The rows are flipped so that the top of the image is at the top of the texture. RGB and
+    /// RGBA images are uploaded as they are, luminance images are expanded to gray RGB, and
+    /// all the other kinds of images are converted to RGBA first. All the mipmaps of the
+    /// texture are generated automatically.
+    ///
+    /// Only available if the `image` feature is enabled.
+    pub fn from_image<F: ?Sized>(facade: &F, image: &image::DynamicImage)
+                                 -> Result<Texture2d, TextureCreationError>
+                                 where F: crate::backend::Facade
+    {
+        match *image {
+            image::DynamicImage::ImageRgb8(ref image) => {
+                let raw = RawImage2d::from_raw_rgb_reversed(image.as_raw(), image.dimensions());
+                Texture2d::new(facade, raw)
+            },
+            image::DynamicImage::ImageRgba8(ref image) => {
+                let raw = RawImage2d::from_raw_rgba_reversed(image.as_raw(), image.dimensions());
+                Texture2d::new(facade, raw)
+            },
+            image::DynamicImage::ImageLuma8(_) => {
+                let image = image.to_rgb8();
+                let raw = RawImage2d::from_raw_rgb_reversed(image.as_raw(), image.dimensions());
+                Texture2d::new(facade, raw)
+            },
+            _ => {
+                let image = image.to_rgba8();
+                let raw = RawImage2d::from_raw_rgba_reversed(image.as_raw(), image.dimensions());
+                Texture2d::new(facade, raw)
+            },
+        }
+    }
+}
+
 /// Trait that describes data for a two-dimensional texture.
 pub trait Texture3dDataSource<'a> {
     /// The type of each pixel.
@@ -805,6 +920,17 @@ pub enum TextureCreationError {
 
     /// The texture format is not supported by the backend.
     TypeNotSupported,
+
+    /// The requested number of samples is not supported for this format.
+    SamplesNotSupported,
+
+    /// Mipmaps were requested for a texture that can't have any, like a multisample texture.
+    MipmapsNotSupported,
+
+    /// The images that were passed to build a texture array don't all have the same dimensions
+    /// and format, or the levels that were passed to build a mipmap chain don't have the
+    /// dimensions of a mipmap chain.
+    InconsistentImages,
 }
 
 impl fmt::Display for TextureCreationError {
@@ -817,6 +943,12 @@ impl fmt::Display for TextureCreationError {
                 "The requested texture dimensions are not supported",
             TypeNotSupported =>
                 "The texture format is not supported by the backend",
+            SamplesNotSupported =>
+                "The requested number of samples is not supported for this format",
+            MipmapsNotSupported =>
+                "This kind of texture can't have mipmaps",
+            InconsistentImages =>
+                "The images of the texture array or the mipmap levels have inconsistent dimensions or formats",
         };
         fmt.write_str(desc)
     }
@@ -824,6 +956,53 @@ impl fmt::Display for TextureCreationError {
 
 impl Error for TextureCreationError {}
 
+/// Error that can happen when creating a view of a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureViewError {
+    /// Texture views are not supported by the backend.
+    NotSupported,
+
+    /// The texture doesn't have immutable storage and thus can't be viewed.
+    MutableStorage,
+
+    /// The format of the view isn't in the same compatibility class as the format of the
+    /// texture.
+    IncompatibleFormat,
+
+    /// The requested range of mipmap levels is empty or out of range.
+    InvalidLevels,
+
+    /// The requested range of layers is empty, out of range, or doesn't match the kind of
+    /// texture.
+    InvalidLayers,
+
+    /// The texture doesn't have the dimensions expected by the type of the view.
+    IncompatibleDimensions,
+}
+
+impl fmt::Display for TextureViewError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::TextureViewError::*;
+        let desc = match *self {
+            NotSupported =>
+                "Texture views are not supported by the backend",
+            MutableStorage =>
+                "The texture doesn't have immutable storage",
+            IncompatibleFormat =>
+                "The format of the view isn't compatible with the format of the texture",
+            InvalidLevels =>
+                "The requested range of mipmap levels is invalid",
+            InvalidLayers =>
+                "The requested range of layers is invalid",
+            IncompatibleDimensions =>
+                "The texture doesn't have the dimensions expected by the view",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for TextureViewError {}
+
 impl From<FormatNotSupportedError> for TextureCreationError {
     #[inline]
     fn from(_: FormatNotSupportedError) -> TextureCreationError {
@@ -203,6 +203,37 @@ unsafe impl PixelValue for (f32, f32, f32, f32) {
     }
 }
 
+/// A pixel made of three unsigned bytes stored in the blue, green, red order.
+///
+/// Reading or uploading this type of pixel uses the `GL_BGR` client format, which is not
+/// supported by OpenGL ES.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[repr(C)]
+pub struct Bgr(pub u8, pub u8, pub u8);
+
+unsafe impl PixelValue for Bgr {
+    #[inline]
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U8U8U8Bgr
+    }
+}
+
+/// A pixel made of four unsigned bytes stored in the blue, green, red, alpha order.
+///
+/// Reading or uploading this type of pixel uses the `GL_BGRA` client format. This is often the
+/// native format of window systems and video encoders, which avoids swizzling the channels on
+/// the CPU. Uploading it is not supported by OpenGL ES.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[repr(C)]
+pub struct Bgra(pub u8, pub u8, pub u8, pub u8);
+
+unsafe impl PixelValue for Bgra {
+    #[inline]
+    fn get_format() -> super::ClientFormat {
+        super::ClientFormat::U8U8U8U8Bgra
+    }
+}
+
 #[cfg(feature = "image")]
 unsafe impl PixelValue for image::Rgb<u8> {
     #[inline]
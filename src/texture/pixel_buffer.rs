@@ -3,18 +3,28 @@ Pixel buffers are buffers that contain two-dimensional texture data.
 
 Contrary to textures, pixel buffers are stored in a client-defined format. They are used
 to transfer data to or from the video memory, before or after being turned into a texture.
+
+Reading a pixel buffer right after filling it forces the CPU to wait until the GPU has
+finished. To capture images continuously, a `PixelBufferPool` alternates between two pixel
+buffers: while the GPU fills one of them, the content of the other one is read.
 */
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
 
 use crate::backend::Facade;
+use crate::context::Context;
 
 use crate::GlObject;
+use crate::Rect;
+use crate::SyncFence;
 use crate::buffer::{ReadError, Buffer, BufferType, BufferMode};
 use crate::gl;
 
 use crate::texture::PixelValue;
+use crate::texture::Texture2d;
+use crate::texture::TextureAny;
 use crate::texture::Texture2dDataSink;
 
 /// Buffer that stores the content of a texture.
@@ -71,6 +81,104 @@ impl<T> GlObject for PixelBuffer<T> where T: PixelValue {
     }
 }
 
+/// Two pixel buffers that are used alternately to read textures without waiting for the GPU.
+///
+/// Each call to `read` starts copying a texture to one of the buffers, then returns the
+/// texture that was passed to the previous call. By the time the previous copy is read, the
+/// GPU has usually finished it, and the CPU doesn't have to wait.
+///
+/// ```no_run
+/// # fn example(display: glium::Display<glium::glutin::surface::WindowSurface>,
+/// #            texture: glium::Texture2d) {
+/// use glium::texture::pixel_buffer::PixelBufferPool;
+///
+/// let mut pool = PixelBufferPool::new(&display, 1024 * 768);
+///
+/// loop {
+///     // ... draw to `texture` ...
+///     let previous_frame: Option<Vec<Vec<(u8, u8, u8, u8)>>> = pool.read(&texture).unwrap();
+/// }
+/// # }
+/// ```
+pub struct PixelBufferPool<T> where T: PixelValue {
+    context: Rc<Context>,
+    buffers: [PixelBuffer<T>; 2],
+    // index of the buffer that was filled last and hasn't been read yet, and the fence that
+    // is signaled when the copy is finished
+    pending: Option<(usize, Option<SyncFence>)>,
+}
+
+impl<T> PixelBufferPool<T> where T: PixelValue {
+    /// Builds a new pool of two buffers that can each hold `capacity` pixels.
+    pub fn new<F: ?Sized>(facade: &F, capacity: usize) -> PixelBufferPool<T> where F: Facade {
+        PixelBufferPool {
+            context: facade.get_context().clone(),
+            buffers: [PixelBuffer::new_empty(facade, capacity),
+                      PixelBuffer::new_empty(facade, capacity)],
+            pending: None,
+        }
+    }
+
+    /// Returns the number of pixels that each buffer can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffers[0].len()
+    }
+
+    /// Returns the content of the buffer that was filled last, if it hasn't been read yet.
+    ///
+    /// Blocks until the copy is finished. Call this function once the capture is over to
+    /// retrieve the last image.
+    pub fn flush<S>(&mut self) -> Result<Option<S>, ReadError> where S: Texture2dDataSink<T> {
+        let (index, fence) = match self.pending.take() {
+            Some(pending) => pending,
+            None => return Ok(None),
+        };
+
+        if let Some(fence) = fence {
+            fence.wait();
+        }
+
+        self.buffers[index].read_as_texture_2d().map(Some)
+    }
+}
+
+impl PixelBufferPool<(u8, u8, u8, u8)> {
+    /// Starts copying the content of `texture` to one of the buffers, and returns the content
+    /// of the texture that was passed to the previous call.
+    ///
+    /// Returns `Ok(None)` if there is no previous call, or if `flush` has been called since.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the texture has more pixels than the capacity of the buffers.
+    pub fn read<S>(&mut self, texture: &Texture2d) -> Result<Option<S>, ReadError>
+        where S: Texture2dDataSink<(u8, u8, u8, u8)>
+    {
+        let current = match self.pending {
+            Some((index, _)) => 1 - index,
+            None => 0,
+        };
+
+        let texture: &TextureAny = texture;
+        let rect = Rect {
+            left: 0,
+            bottom: 0,
+            width: texture.get_width(),
+            height: texture.get_height().unwrap_or(1),
+        };
+
+        texture.main_level().first_layer().into_image(None).unwrap()
+               .raw_read_to_pixel_buffer(&rect, &self.buffers[current]);
+        let fence = SyncFence::new(&self.context).ok();
+
+        // the new copy must stay pending even if reading the previous one fails
+        let previous = self.flush();
+        self.pending = Some((current, fence));
+        previous
+    }
+}
+
 // TODO: remove this hack
 #[doc(hidden)]
 #[inline]
@@ -0,0 +1,219 @@
+/*!
+Rendering an image by tiles.
+
+Some images are too large to be rendered in one go, for example because the framebuffer would
+exceed `GL_MAX_RENDERBUFFER_SIZE` or because it wouldn't fit in memory. A `TiledRenderer` splits
+the image in tiles and calls a closure for each of them.
+
+There are two ways to render a tile:
+
+- Draw to a surface that has the dimensions of the whole image, with the draw parameters
+  returned by `Tile::draw_parameters`. They restrict the drawing to the tile with the scissor
+  box, which is useful to spread a heavy frame over multiple submissions.
+- Draw to a surface that is at least as large as a tile, and multiply the projection matrix
+  with `Tile::projection`. The tile is then stretched to fill the viewport returned by
+  `Tile::viewport`, and the content of the surface can be copied at the position of
+  `Tile::rect` in the final image.
+
+```no_run
+# use glium::Surface;
+# use glium::tiled_renderer::TiledRenderer;
+# fn example(display: glium::Display<glium::glutin::surface::WindowSurface>,
+#            vb: glium::VertexBuffer<[f32; 2]>, ib: glium::IndexBuffer<u16>,
+#            program: glium::Program) {
+let renderer = TiledRenderer::new((16384, 16384), (2048, 2048));
+let tile_texture = glium::Texture2d::empty(&display, 2048, 2048).unwrap();
+
+renderer.render(|tile| {
+    let params = glium::DrawParameters {
+        viewport: Some(tile.viewport()),
+        .. Default::default()
+    };
+
+    let mut surface = tile_texture.as_surface();
+    surface.clear_color(0.0, 0.0, 0.0, 1.0);
+    surface.draw(&vb, &ib, &program, &glium::uniform! { tile: tile.projection() }, &params)?;
+
+    // copy the content of `tile_texture` at the position of `tile.rect`
+    Ok::<_, glium::DrawError>(())
+}).unwrap();
+# }
+```
+
+The vertex shader must then apply the matrix after the projection:
+`gl_Position = tile * projection * view * model * position`.
+
+*/
+use crate::draw_parameters::DrawParameters;
+use crate::Rect;
+
+/// Splits an image in tiles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TiledRenderer {
+    dimensions: (u32, u32),
+    tile_size: (u32, u32),
+}
+
+impl TiledRenderer {
+    /// Builds a new `TiledRenderer` for an image of the given dimensions.
+    ///
+    /// The tiles at the right and top edges of the image are smaller if the dimensions of the
+    /// image are not multiples of `tile_size`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if one of the dimensions of `tile_size` is zero.
+    #[inline]
+    pub fn new(dimensions: (u32, u32), tile_size: (u32, u32)) -> TiledRenderer {
+        assert!(tile_size.0 != 0 && tile_size.1 != 0, "The size of the tiles must not be zero");
+
+        TiledRenderer {
+            dimensions,
+            tile_size,
+        }
+    }
+
+    /// Returns the dimensions of the whole image.
+    #[inline]
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    /// Returns the maximum dimensions of a tile.
+    #[inline]
+    pub fn get_tile_size(&self) -> (u32, u32) {
+        self.tile_size
+    }
+
+    /// Returns the number of tiles horizontally and vertically.
+    #[inline]
+    pub fn get_tiles_count(&self) -> (u32, u32) {
+        (self.dimensions.0.div_ceil(self.tile_size.0),
+         self.dimensions.1.div_ceil(self.tile_size.1))
+    }
+
+    /// Returns an iterator to the tiles of the image.
+    ///
+    /// The tiles are returned row by row, starting from the bottom-left corner.
+    #[inline]
+    pub fn tiles(&self) -> Tiles {
+        Tiles {
+            renderer: *self,
+            next: (0, 0),
+        }
+    }
+
+    /// Calls `f` for each tile of the image, in the same order as `tiles`.
+    ///
+    /// Stops and returns the error if the closure returns an error.
+    pub fn render<F, E>(&self, mut f: F) -> Result<(), E> where F: FnMut(&Tile) -> Result<(), E> {
+        for tile in self.tiles() {
+            f(&tile)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator to the tiles of a `TiledRenderer`.
+#[derive(Debug, Clone)]
+pub struct Tiles {
+    renderer: TiledRenderer,
+    next: (u32, u32),
+}
+
+impl Iterator for Tiles {
+    type Item = Tile;
+
+    fn next(&mut self) -> Option<Tile> {
+        let (width, height) = self.renderer.dimensions;
+        let (tile_width, tile_height) = self.renderer.tile_size;
+        let (left, bottom) = self.next;
+
+        if left >= width || bottom >= height {
+            return None;
+        }
+
+        self.next = if width - left > tile_width {
+            (left + tile_width, bottom)
+        } else {
+            (0, bottom.saturating_add(tile_height))
+        };
+
+        Some(Tile {
+            rect: Rect {
+                left,
+                bottom,
+                width: tile_width.min(width - left),
+                height: tile_height.min(height - bottom),
+            },
+            dimensions: self.renderer.dimensions,
+        })
+    }
+}
+
+/// A tile of an image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tile {
+    /// The region of the whole image covered by this tile.
+    pub rect: Rect,
+
+    dimensions: (u32, u32),
+}
+
+impl Tile {
+    /// Returns a copy of `params` that draws this tile to a surface that has the dimensions of
+    /// the whole image.
+    ///
+    /// The viewport covers the whole image, and the scissor box is set to the tile.
+    pub fn draw_parameters<'a>(&self, params: &DrawParameters<'a>) -> DrawParameters<'a> {
+        DrawParameters {
+            viewport: Some(Rect {
+                left: 0,
+                bottom: 0,
+                width: self.dimensions.0,
+                height: self.dimensions.1,
+            }),
+            scissor: Some(self.rect),
+            .. params.clone()
+        }
+    }
+
+    /// Returns the viewport to use with `projection` when drawing to a surface that is larger
+    /// than the tile.
+    ///
+    /// This happens when the same surface is reused for all the tiles, since the tiles at the
+    /// right and top edges of the image can be smaller than the others.
+    #[inline]
+    pub fn viewport(&self) -> Rect {
+        Rect {
+            left: 0,
+            bottom: 0,
+            width: self.rect.width,
+            height: self.rect.height,
+        }
+    }
+
+    /// Returns a matrix that stretches this tile over a surface that has the dimensions of the
+    /// tile.
+    ///
+    /// This matrix must be applied to the clip-space coordinates, in other words multiplied on
+    /// the left of the projection matrix.
+    pub fn projection(&self) -> [[f32; 4]; 4] {
+        let (width, height) = (self.dimensions.0 as f32, self.dimensions.1 as f32);
+        let (left, bottom) = (self.rect.left as f32, self.rect.bottom as f32);
+        let (tile_width, tile_height) = (self.rect.width as f32, self.rect.height as f32);
+
+        [
+            [width / tile_width, 0.0, 0.0, 0.0],
+            [0.0, height / tile_height, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                (width - 2.0 * left - tile_width) / tile_width,
+                (height - 2.0 * bottom - tile_height) / tile_height,
+                0.0,
+                1.0,
+            ],
+        ]
+    }
+}
@@ -122,6 +122,11 @@ impl<U> UniformsExt for U where U: Uniforms {
                     fences.push(fence);
                 }
             } else if let UniformValue::Subroutine(stage, sr_name) = value {
+                if !program::is_subroutine_supported(&*ctxt) {
+                    visiting_result = Err(DrawError::SubroutinesNotSupported);
+                    return;
+                }
+
                 if let Some(subroutine_uniform) = program.get_subroutine_data().subroutine_uniforms.get(&(name.into(), stage)) {
                     subroutine_bindings.entry(stage).or_insert(Vec::new());
                     let vec = subroutine_bindings.get_mut(&stage).unwrap();
@@ -130,6 +135,38 @@ impl<U> UniformsExt for U where U: Uniforms {
             }
         });
 
+        // Uniforms with an explicit location are set directly, without looking at the reflection
+        // of the program.
+        self.visit_located_values(|location, value| {
+            if visiting_result.is_err() { return; }
+
+            // like with `glUniform`, a location of -1 is silently ignored
+            if location == -1 { return; }
+
+            // see the comment in `bind_uniform`
+            #[cfg(not(target_os = "vita"))]
+            {
+                if location < 0 {
+                    visiting_result = Err(DrawError::InvalidUniformLocation { location });
+                    return;
+                }
+            }
+
+            // the name is only used for error messages
+            let name = match value {
+                UniformValue::Block(_, _) | UniformValue::Subroutine(_, _) =>
+                    format!("location {}", location),
+                _ => String::new(),
+            };
+
+            if let Err(e) = bind_uniform(&mut ctxt, &value, program, location,
+                                         &mut texture_bind_points, &mut image_unit_bind_points,
+                                         &name)
+            {
+                visiting_result = Err(e);
+            }
+        });
+
         // Process all subroutine uniforms in one batch.
         if !subroutine_bindings.is_empty() {
             match bind_subroutine_uniforms(&mut ctxt, program, &subroutine_bindings) {
@@ -239,7 +276,24 @@ fn bind_shared_storage_block<'a, P>(ctxt: &mut context::CommandContext<'_>, valu
                 }
             }
 
-            let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
+            // using the binding declared in the shader, unless another block already uses it
+            let declared = block.initial_binding;
+            let bind_point = if declared >= 0 &&
+                                declared < ctxt.capabilities.max_indexed_shader_storage_buffer &&
+                                !buffer_bind_points.is_used(declared as u16)
+            {
+                declared as u16
+            } else {
+                match buffer_bind_points.get_unused() {
+                    Some(b) => b,
+                    None => return Err(DrawError::InsufficientShaderStorageBufferBindings),
+                }
+            };
+
+            if bind_point as gl::types::GLint >= ctxt.capabilities.max_indexed_shader_storage_buffer {
+                return Err(DrawError::InsufficientShaderStorageBufferBindings);
+            }
+
             buffer_bind_points.set_used(bind_point);
 
             assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
@@ -499,18 +553,23 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::Texture2dMultisample(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::SrgbTexture2dMultisample(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::IntegralTexture2dMultisample(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::UnsignedTexture2dMultisample(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::DepthTexture2dMultisample(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::Texture3d(texture, sampler) => {
@@ -577,18 +636,23 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::Texture2dMultisampleArray(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::SrgbTexture2dMultisampleArray(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::IntegralTexture2dMultisampleArray(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::UnsignedTexture2dMultisampleArray(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::DepthTexture2dMultisampleArray(texture, sampler) => {
+            check_multisample_sampler(sampler, name)?;
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
         UniformValue::Cubemap(texture, sampler) => {
@@ -702,6 +766,17 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
     }
 }
 
+/// Multisample textures don't have any sampling state, so they can't be bound with a sampler.
+fn check_multisample_sampler(sampler: Option<SamplerBehavior>, name: &str)
+                             -> Result<(), DrawError>
+{
+    if sampler.is_some() {
+        return Err(DrawError::SamplerWithMultisampleTexture { name: name.to_owned() });
+    }
+
+    Ok(())
+}
+
 fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
                               texture: &T, sampler: Option<SamplerBehavior>,
                               location: gl::types::GLint, program: &P,
@@ -710,14 +785,15 @@ fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
 {
     texture.prepare_for_access(ctxt, crate::TextureAccess::TextureFetch);
     
-    let sampler = if let Some(sampler) = sampler {
-        Some(crate::sampler_object::get_sampler(ctxt, &sampler)?)
-    } else {
-        None
+    // without sampler objects, the sampling parameters are set on the texture itself
+    let (sampler, texture_parameters) = match sampler {
+        Some(behavior) if !crate::sampler_object::is_sampler_objects_supported(ctxt) => {
+            (0, Some(behavior))
+        },
+        Some(behavior) => (crate::sampler_object::get_sampler(ctxt, &behavior)?, None),
+        None => (0, None),
     };
 
-    let sampler = sampler.unwrap_or(0);
-
     // finding an appropriate texture unit
     let texture_unit =
         ctxt.state.texture_units
@@ -776,6 +852,15 @@ fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
         }
     }
 
+    if let Some(behavior) = texture_parameters {
+        if ctxt.state.active_texture != texture_unit as gl::types::GLenum {
+            unsafe { ctxt.gl.ActiveTexture(texture_unit as gl::types::GLenum + gl::TEXTURE0) };
+            ctxt.state.active_texture = texture_unit as gl::types::GLenum;
+        }
+
+        crate::sampler_object::set_texture_parameters(ctxt, texture.get_bind_point(), &behavior);
+    }
+
     Ok(())
 }
 
@@ -6,6 +6,7 @@ use crate::program;
 use crate::gl;
 use crate::GlObject;
 
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
 use crate::backend::Facade;
@@ -24,9 +25,58 @@ use crate::backend::Facade;
 ///     uniform! {
 ///         MyBlock: &buffer,
 ///     }
+pub type UniformBuffer<T> = BlockBuffer<T, UniformBlockKind>;
+
+/// Buffer that contains a shader storage block.
+///
+/// Contrary to uniform blocks, shader storage blocks can be written by the shaders, and their
+/// last member can be an array whose size is determined by the size of the buffer. For example,
+/// to use a `ShaderStorageBuffer<[f32]>`, you must declare it in GLSL as
+///```ignore
+///     layout(std430) buffer MyBlock {
+///         float values[];
+///     };
+///```
+///
+/// and pass it to `uniform!` like this:
+///     # use glium::uniform;
+///     uniform! {
+///         MyBlock: &buffer,
+///     }
+///
+/// The buffer is bound to the binding point declared with `layout(binding = N)` in the shader
+/// if it is free. The content of the buffer can then be read back with `read()`.
+pub type ShaderStorageBuffer<T> = BlockBuffer<T, ShaderStorageBlockKind>;
+
+/// Buffer that contains a block of a program. The kind of block is determined by `K`.
+///
+/// This type is used through the `UniformBuffer` and `ShaderStorageBuffer` aliases.
 #[derive(Debug)]
-pub struct UniformBuffer<T: ?Sized> where T: Content {
+pub struct BlockBuffer<T: ?Sized, K> where T: Content, K: BlockKind {
     buffer: Buffer<T>,
+    marker: PhantomData<K>,
+}
+
+/// Kind of block stored in a `BlockBuffer`.
+pub trait BlockKind {
+    /// Type of the buffer that is created.
+    const BUFFER_TYPE: BufferType;
+}
+
+/// Marker for the buffers that contain a uniform block.
+#[derive(Debug, Copy, Clone)]
+pub enum UniformBlockKind {}
+
+impl BlockKind for UniformBlockKind {
+    const BUFFER_TYPE: BufferType = BufferType::UniformBuffer;
+}
+
+/// Marker for the buffers that contain a shader storage block.
+#[derive(Debug, Copy, Clone)]
+pub enum ShaderStorageBlockKind {}
+
+impl BlockKind for ShaderStorageBlockKind {
+    const BUFFER_TYPE: BufferType = BufferType::ShaderStorageBuffer;
 }
 
 /// Same as `UniformBuffer` but doesn't contain any information about the type.
@@ -35,7 +85,7 @@ pub struct TypelessUniformBuffer {
     buffer: BufferAny,
 }
 
-impl<T: ?Sized + Content> GlObject for UniformBuffer<T> {
+impl<T: ?Sized + Content, K: BlockKind> GlObject for BlockBuffer<T, K> {
     type Id = gl::types::GLuint;
 
     #[inline]
@@ -44,94 +94,96 @@ impl<T: ?Sized + Content> GlObject for UniformBuffer<T> {
     }
 }
 
-impl<T> UniformBuffer<T> where T: Copy {
-    /// Uploads data in the uniforms buffer.
+impl<T, K> BlockBuffer<T, K> where T: Copy, K: BlockKind {
+    /// Uploads data in the buffer.
     #[inline]
-    pub fn new<F: ?Sized>(facade: &F, data: T) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn new<F: ?Sized>(facade: &F, data: T) -> Result<BlockBuffer<T, K>, BufferCreationError>
                   where F: Facade
     {
-        UniformBuffer::new_impl(facade, data, BufferMode::Default)
+        BlockBuffer::new_impl(facade, data, BufferMode::Default)
     }
 
-    /// Uploads data in the uniforms buffer.
+    /// Uploads data in the buffer.
     #[inline]
-    pub fn dynamic<F: ?Sized>(facade: &F, data: T) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn dynamic<F: ?Sized>(facade: &F, data: T) -> Result<BlockBuffer<T, K>, BufferCreationError>
                       where F: Facade
     {
-        UniformBuffer::new_impl(facade, data, BufferMode::Dynamic)
+        BlockBuffer::new_impl(facade, data, BufferMode::Dynamic)
     }
 
-    /// Uploads data in the uniforms buffer.
+    /// Uploads data in the buffer.
     #[inline]
-    pub fn persistent<F: ?Sized>(facade: &F, data: T) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn persistent<F: ?Sized>(facade: &F, data: T) -> Result<BlockBuffer<T, K>, BufferCreationError>
                   where F: Facade
     {
-        UniformBuffer::new_impl(facade, data, BufferMode::Persistent)
+        BlockBuffer::new_impl(facade, data, BufferMode::Persistent)
     }
 
-    /// Uploads data in the uniforms buffer.
+    /// Uploads data in the buffer.
     #[inline]
-    pub fn immutable<F: ?Sized>(facade: &F, data: T) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn immutable<F: ?Sized>(facade: &F, data: T) -> Result<BlockBuffer<T, K>, BufferCreationError>
                         where F: Facade
     {
-        UniformBuffer::new_impl(facade, data, BufferMode::Immutable)
+        BlockBuffer::new_impl(facade, data, BufferMode::Immutable)
     }
 
     #[inline]
     fn new_impl<F: ?Sized>(facade: &F, data: T, mode: BufferMode)
-                   -> Result<UniformBuffer<T>, BufferCreationError>
+                   -> Result<BlockBuffer<T, K>, BufferCreationError>
                    where F: Facade
     {
-        let buffer = Buffer::new(facade, &data, BufferType::UniformBuffer, mode)?;
+        let buffer = Buffer::new(facade, &data, K::BUFFER_TYPE, mode)?;
 
-        Ok(UniformBuffer {
+        Ok(BlockBuffer {
             buffer,
+            marker: PhantomData,
         })
     }
 
     /// Creates an empty buffer.
     #[inline]
-    pub fn empty<F: ?Sized>(facade: &F) -> Result<UniformBuffer<T>, BufferCreationError> where F: Facade {
-        UniformBuffer::empty_impl(facade, BufferMode::Default)
+    pub fn empty<F: ?Sized>(facade: &F) -> Result<BlockBuffer<T, K>, BufferCreationError> where F: Facade {
+        BlockBuffer::empty_impl(facade, BufferMode::Default)
     }
 
     /// Creates an empty buffer.
     #[inline]
-    pub fn empty_dynamic<F: ?Sized>(facade: &F) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn empty_dynamic<F: ?Sized>(facade: &F) -> Result<BlockBuffer<T, K>, BufferCreationError>
                             where F: Facade
     {
-        UniformBuffer::empty_impl(facade, BufferMode::Dynamic)
+        BlockBuffer::empty_impl(facade, BufferMode::Dynamic)
     }
 
     /// Creates an empty buffer.
     #[inline]
-    pub fn empty_persistent<F: ?Sized>(facade: &F) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn empty_persistent<F: ?Sized>(facade: &F) -> Result<BlockBuffer<T, K>, BufferCreationError>
                                where F: Facade
     {
-        UniformBuffer::empty_impl(facade, BufferMode::Persistent)
+        BlockBuffer::empty_impl(facade, BufferMode::Persistent)
     }
 
     /// Creates an empty buffer.
     #[inline]
-    pub fn empty_immutable<F: ?Sized>(facade: &F) -> Result<UniformBuffer<T>, BufferCreationError>
+    pub fn empty_immutable<F: ?Sized>(facade: &F) -> Result<BlockBuffer<T, K>, BufferCreationError>
                               where F: Facade
     {
-        UniformBuffer::empty_impl(facade, BufferMode::Immutable)
+        BlockBuffer::empty_impl(facade, BufferMode::Immutable)
     }
 
     #[inline]
-    fn empty_impl<F: ?Sized>(facade: &F, mode: BufferMode) -> Result<UniformBuffer<T>, BufferCreationError>
+    fn empty_impl<F: ?Sized>(facade: &F, mode: BufferMode) -> Result<BlockBuffer<T, K>, BufferCreationError>
                      where F: Facade
     {
-        let buffer = Buffer::empty(facade, BufferType::UniformBuffer, mode)?;
+        let buffer = Buffer::empty(facade, K::BUFFER_TYPE, mode)?;
 
-        Ok(UniformBuffer {
+        Ok(BlockBuffer {
             buffer,
+            marker: PhantomData,
         })
     }
 }
 
-impl<T: ?Sized> UniformBuffer<T> where T: Content {
+impl<T: ?Sized, K> BlockBuffer<T, K> where T: Content, K: BlockKind {
     /// Creates an empty buffer.
     ///
     /// # Panic
@@ -140,10 +192,10 @@ impl<T: ?Sized> UniformBuffer<T> where T: Content {
     ///
     #[inline]
     pub fn empty_unsized<F: ?Sized>(facade: &F, size: usize)
-                            -> Result<UniformBuffer<T>, BufferCreationError>
+                            -> Result<BlockBuffer<T, K>, BufferCreationError>
                             where F: Facade
     {
-        UniformBuffer::empty_unsized_impl(facade, size, BufferMode::Default)
+        BlockBuffer::empty_unsized_impl(facade, size, BufferMode::Default)
     }
 
     /// Creates an empty buffer.
@@ -154,10 +206,10 @@ impl<T: ?Sized> UniformBuffer<T> where T: Content {
     ///
     #[inline]
     pub fn empty_unsized_dynamic<F: ?Sized>(facade: &F, size: usize)
-                                    -> Result<UniformBuffer<T>, BufferCreationError>
+                                    -> Result<BlockBuffer<T, K>, BufferCreationError>
                                     where F: Facade
     {
-        UniformBuffer::empty_unsized_impl(facade, size, BufferMode::Dynamic)
+        BlockBuffer::empty_unsized_impl(facade, size, BufferMode::Dynamic)
     }
 
     /// Creates an empty buffer.
@@ -168,10 +220,10 @@ impl<T: ?Sized> UniformBuffer<T> where T: Content {
     ///
     #[inline]
     pub fn empty_unsized_persistent<F: ?Sized>(facade: &F, size: usize)
-                                       -> Result<UniformBuffer<T>, BufferCreationError>
+                                       -> Result<BlockBuffer<T, K>, BufferCreationError>
                                        where F: Facade
     {
-        UniformBuffer::empty_unsized_impl(facade, size, BufferMode::Persistent)
+        BlockBuffer::empty_unsized_impl(facade, size, BufferMode::Persistent)
     }
 
     /// Creates an empty buffer.
@@ -182,26 +234,27 @@ impl<T: ?Sized> UniformBuffer<T> where T: Content {
     ///
     #[inline]
     pub fn empty_unsized_immutable<F: ?Sized>(facade: &F, size: usize)
-                                      -> Result<UniformBuffer<T>, BufferCreationError>
+                                      -> Result<BlockBuffer<T, K>, BufferCreationError>
                                       where F: Facade
     {
-        UniformBuffer::empty_unsized_impl(facade, size, BufferMode::Immutable)
+        BlockBuffer::empty_unsized_impl(facade, size, BufferMode::Immutable)
     }
 
     #[inline]
     fn empty_unsized_impl<F: ?Sized>(facade: &F, size: usize, mode: BufferMode)
-                             -> Result<UniformBuffer<T>, BufferCreationError>
+                             -> Result<BlockBuffer<T, K>, BufferCreationError>
                              where F: Facade
     {
-        let buffer = Buffer::empty_unsized(facade, BufferType::UniformBuffer, size, mode)?;
+        let buffer = Buffer::empty_unsized(facade, K::BUFFER_TYPE, size, mode)?;
 
-        Ok(UniformBuffer {
+        Ok(BlockBuffer {
             buffer,
+            marker: PhantomData,
         })
     }
 }
 
-impl<T: ?Sized> Deref for UniformBuffer<T> where T: Content {
+impl<T: ?Sized, K> Deref for BlockBuffer<T, K> where T: Content, K: BlockKind {
     type Target = Buffer<T>;
 
     #[inline]
@@ -210,28 +263,34 @@ impl<T: ?Sized> Deref for UniformBuffer<T> where T: Content {
     }
 }
 
-impl<T: ?Sized> DerefMut for UniformBuffer<T> where T: Content {
+impl<T: ?Sized, K> DerefMut for BlockBuffer<T, K> where T: Content, K: BlockKind {
     #[inline]
     fn deref_mut(&mut self) -> &mut Buffer<T> {
         &mut self.buffer
     }
 }
 
-impl<'a, T: ?Sized> From<&'a UniformBuffer<T>> for BufferSlice<'a, T> where T: Content {
+impl<'a, T: ?Sized, K> From<&'a BlockBuffer<T, K>> for BufferSlice<'a, T>
+    where T: Content, K: BlockKind
+{
     #[inline]
-    fn from(b: &'a UniformBuffer<T>) -> BufferSlice<'a, T> {
+    fn from(b: &'a BlockBuffer<T, K>) -> BufferSlice<'a, T> {
         b.buffer.as_slice()
     }
 }
 
-impl<'a, T: ?Sized> From<&'a mut UniformBuffer<T>> for BufferMutSlice<'a, T> where T: Content {
+impl<'a, T: ?Sized, K> From<&'a mut BlockBuffer<T, K>> for BufferMutSlice<'a, T>
+    where T: Content, K: BlockKind
+{
     #[inline]
-    fn from(b: &'a mut UniformBuffer<T>) -> BufferMutSlice<'a, T> {
+    fn from(b: &'a mut BlockBuffer<T, K>) -> BufferMutSlice<'a, T> {
         b.buffer.as_mut_slice()
     }
 }
 
-impl<'a, T: ?Sized> AsUniformValue for &'a UniformBuffer<T> where T: UniformBlock + Content {
+impl<'a, T: ?Sized, K> AsUniformValue for &'a BlockBuffer<T, K>
+    where T: UniformBlock + Content, K: BlockKind
+{
     #[inline]
     fn as_uniform_value(&self) -> UniformValue<'_> {
         #[inline]
@@ -74,6 +74,10 @@ let uniforms = uniform! {
 # }
 ```
 
+Shader storage blocks, declared with the `buffer` keyword, work the same way with a
+`ShaderStorageBuffer`. If the block is declared with `layout(binding = N)`, the buffer is bound
+to this binding point.
+
 ## Subroutines
 OpenGL allows the use of subroutines, which are like function pointers. Subroutines can be used
 to change the functionality of a shader program at runtime. This method is usually a lot faster
@@ -121,11 +125,32 @@ let program = glium::Program::from_source(&display,
     };
 # }
 ```
+
+## Explicit locations
+
+Uniforms that are declared with an explicit location in the shader, like
+`layout(location = 2) uniform vec3 color;`, can be set by location instead of by name. This
+skips looking up the name in the program's reflection data, and also works for uniforms
+whose name has been optimized out by the driver.
+
+```no_run
+# use glium::uniform;
+# fn example(matrix: [[f32; 4]; 4]) {
+let uniforms = uniform! {
+    matrix: matrix,
+}.add_at_location(2, [1.0f32, 0.0, 0.0]);
+# }
+```
+
+The type of a uniform set by location isn't checked against the shader.
+A location of -1 is ignored, like with `glUniform`, while other negative locations return
+an error when drawing.
 */
-pub use self::buffer::UniformBuffer;
+pub use self::buffer::{UniformBuffer, ShaderStorageBuffer};
+pub use self::buffer::{BlockBuffer, BlockKind, UniformBlockKind, ShaderStorageBlockKind};
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter, DepthTextureComparison};
 pub use self::sampler::{Sampler, SamplerBehavior};
-pub use self::uniforms::{EmptyUniforms, UniformsStorage, DynamicUniforms};
+pub use self::uniforms::{EmptyUniforms, UniformsStorage, LocatedUniformsStorage, DynamicUniforms};
 pub use self::image_unit::{ImageUnitAccess, ImageUnitFormat, ImageUnitError};
 pub use self::image_unit::{ImageUnit, ImageUnitBehavior};
 pub use self::value::{UniformValue, UniformType};
@@ -151,6 +176,14 @@ mod value;
 pub trait Uniforms {
     /// Calls the parameter once with the name and value of each uniform.
     fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, _: F);
+
+    /// Calls the parameter once with the explicit location and value of each uniform that
+    /// must be set by location instead of by name.
+    ///
+    /// The default implementation doesn't call the parameter.
+    #[inline]
+    fn visit_located_values<'a, F: FnMut(i32, UniformValue<'a>)>(&'a self, _: F) {
+    }
 }
 
 /// Error about a block layout mismatch.
@@ -210,10 +210,11 @@ pub struct SamplerBehavior {
     /// ## Compatibility
     ///
     /// This parameter is always available. However it is ignored on hardware that does
-    /// not support anisotropic filtering.
+    /// not support anisotropic filtering, which requires OpenGL 4.6,
+    /// `GL_ARB_texture_filter_anisotropic` or `GL_EXT_texture_filter_anisotropic`.
     ///
     /// If you set the value to a value higher than what the hardware supports, it will
-    /// be clamped.
+    /// be clamped. The maximum value is returned by `Context::get_max_anisotropy_support`.
     pub max_anisotropy: u16,
 }
 
@@ -46,6 +46,19 @@ impl<'n, T, R> UniformsStorage<'n, T, R> where T: AsUniformValue, R: Uniforms {
             rest: self,
         }
     }
+
+    /// Adds a value that is set at an explicit location of the program.
+    #[inline]
+    pub fn add_at_location<U>(self, location: i32, value: U)
+                              -> LocatedUniformsStorage<U, UniformsStorage<'n, T, R>>
+                              where U: AsUniformValue
+    {
+        LocatedUniformsStorage {
+            location,
+            value,
+            rest: self,
+        }
+    }
 }
 
 impl<'n, T, R> Uniforms for UniformsStorage<'n, T, R> where T: AsUniformValue, R: Uniforms {
@@ -54,19 +67,78 @@ impl<'n, T, R> Uniforms for UniformsStorage<'n, T, R> where T: AsUniformValue, R
         output(self.name, self.value.as_uniform_value());
         self.rest.visit_values(output);
     }
+
+    #[inline]
+    fn visit_located_values<'a, F: FnMut(i32, UniformValue<'a>)>(&'a self, output: F) {
+        self.rest.visit_located_values(output);
+    }
+}
+
+/// Stores uniforms that are set at an explicit location, as declared in the shader with
+/// `layout(location = N)`.
+///
+/// Setting a uniform by location skips looking up its name in the program, but its type isn't
+/// checked against the shader.
+#[derive(Copy, Clone)]
+pub struct LocatedUniformsStorage<T, R> where T: AsUniformValue, R: Uniforms {
+    location: i32,
+    value: T,
+    rest: R,
+}
+
+impl<T> LocatedUniformsStorage<T, EmptyUniforms> where T: AsUniformValue {
+    /// Builds a new storage with a value.
+    #[inline]
+    pub fn new(location: i32, value: T) -> LocatedUniformsStorage<T, EmptyUniforms> {
+        LocatedUniformsStorage {
+            location,
+            value,
+            rest: EmptyUniforms,
+        }
+    }
+}
+
+impl<T, R> LocatedUniformsStorage<T, R> where T: AsUniformValue, R: Uniforms {
+    /// Adds a value to the storage.
+    #[inline]
+    pub fn add_at_location<U>(self, location: i32, value: U)
+                              -> LocatedUniformsStorage<U, LocatedUniformsStorage<T, R>>
+                              where U: AsUniformValue
+    {
+        LocatedUniformsStorage {
+            location,
+            value,
+            rest: self,
+        }
+    }
+}
+
+impl<T, R> Uniforms for LocatedUniformsStorage<T, R> where T: AsUniformValue, R: Uniforms {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, output: F) {
+        self.rest.visit_values(output);
+    }
+
+    #[inline]
+    fn visit_located_values<'a, F: FnMut(i32, UniformValue<'a>)>(&'a self, mut output: F) {
+        output(self.location, self.value.as_uniform_value());
+        self.rest.visit_located_values(output);
+    }
 }
 
 /// Stores Uniforms dynamicly in a HashMap.
 #[derive(Clone)]
 pub struct DynamicUniforms<'a, 's>{
     map: HashMap<&'s str, UniformValue<'a>>,
+    located: HashMap<i32, UniformValue<'a>>,
 }
 
 impl<'a, 's> DynamicUniforms<'a, 's>{
     /// Creates new DynamicUniforms
     pub fn new() -> Self{
         Self{
-            map: HashMap::new()
+            map: HashMap::new(),
+            located: HashMap::new(),
         }
     }
 
@@ -75,6 +147,12 @@ impl<'a, 's> DynamicUniforms<'a, 's>{
     pub fn add(&mut self, key: &'s str, value: &'a dyn AsUniformValue){
         self.map.insert(key, value.as_uniform_value());
     }
+
+    /// Add a value to the DynamicUniforms that is set at an explicit location of the program
+    #[inline]
+    pub fn add_at_location(&mut self, location: i32, value: &'a dyn AsUniformValue){
+        self.located.insert(location, value.as_uniform_value());
+    }
 }
 
 impl Uniforms for DynamicUniforms<'_, '_>{
@@ -83,4 +161,10 @@ impl Uniforms for DynamicUniforms<'_, '_>{
             output(key, *value);
         }
     }
+
+    fn visit_located_values<'a, F: FnMut(i32, UniformValue<'a>)>(&'a self, mut output: F) {
+        for (location, value) in self.located.iter(){
+            output(*location, *value);
+        }
+    }
 }
@@ -111,9 +111,28 @@ pub fn get_supported_glsl_version(gl_version: &Version) -> Version {
     }
 }
 
+/// Parses the string returned by `glGetString(GL_SHADING_LANGUAGE_VERSION)`.
+///
+/// The minor version is the first digit after the dot, so that `4.60` gives `Version(Gl, 4, 6)`
+/// like the other GLSL versions used by glium. Returns `None` if the string is malformed.
+pub fn parse_glsl_version(version: &str) -> Option<Version> {
+    let (version, api) = if let Some(pos) = version.find("GLSL ES ") {
+        (&version[pos + 8 ..], Api::GlEs)
+    } else {
+        (version, Api::Gl)
+    };
+
+    let version = version.split(' ').next()?;
+    let mut iter = version.split('.');
+    let major = iter.next()?.parse().ok()?;
+    let minor = iter.next()?.chars().next()?.to_digit(10)?;
+
+    Some(Version(api, major, minor as u8))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Version, Api, get_supported_glsl_version};
+    use super::{Version, Api, get_supported_glsl_version, parse_glsl_version};
 
     macro_rules! assert_versions {
         ( $api:path, $gl_major:expr, $gl_minor:expr => $glsl_major:expr, $glsl_minor:expr) => {
@@ -149,6 +168,19 @@ mod tests {
         assert_versions!(Api::GlEs, 3, 1 => 3, 1);
     }
 
+    #[test]
+    fn glsl_version_strings() {
+        assert_eq!(parse_glsl_version("4.60 NVIDIA"), Some(Version(Api::Gl, 4, 6)));
+        assert_eq!(parse_glsl_version("1.10"), Some(Version(Api::Gl, 1, 1)));
+        assert_eq!(parse_glsl_version("OpenGL ES GLSL ES 3.20"), Some(Version(Api::GlEs, 3, 2)));
+        assert_eq!(parse_glsl_version("OpenGL ES GLSL ES 1.00 build 1.2"),
+                   Some(Version(Api::GlEs, 1, 0)));
+        assert_eq!(parse_glsl_version("WebGL GLSL ES 1.0 (OpenGL ES GLSL ES 1.0 Chromium)"),
+                   Some(Version(Api::GlEs, 1, 0)));
+        assert_eq!(parse_glsl_version(""), None);
+        assert_eq!(parse_glsl_version("invalid"), None);
+    }
+
     #[test]
     #[should_panic]
     fn invalid_gl_version() {
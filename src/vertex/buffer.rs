@@ -15,7 +15,34 @@ use crate::version::{Api, Version};
 use crate::CapabilitiesSource;
 
 /// Error that can happen when creating a vertex buffer.
-#[derive(Copy, Clone, Debug)]
+///
+/// The error can be matched in order to try again with other parameters instead of panicking:
+///
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate glium;
+/// # fn main() {
+/// use glium::buffer::BufferCreationError;
+/// use glium::vertex::BufferCreationError as CreationError;
+///
+/// # #[derive(Copy, Clone)]
+/// # struct Vertex { position: [f32; 3] }
+/// # implement_vertex!(Vertex, position);
+/// # fn example(display: glium::Display<glium::glutin::surface::WindowSurface>,
+/// #            data: Vec<Vertex>) {
+/// let vertex_buffer = match glium::VertexBuffer::dynamic(&display, &data) {
+///     Ok(vb) => vb,
+///     Err(CreationError::BufferCreationError(BufferCreationError::OutOfMemory)) => {
+///         // not enough video memory, trying with half of the vertices
+///         glium::VertexBuffer::dynamic(&display, &data[.. data.len() / 2]).unwrap()
+///     },
+///     Err(CreationError::FormatNotSupported) => panic!("the vertex format is not supported"),
+///     Err(err) => panic!("failed to create the vertex buffer: {}", err),
+/// };
+/// # }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CreationError {
     /// The vertex format is not supported by the backend.
     ///
@@ -54,6 +81,45 @@ impl Error for CreationError {
     }
 }
 
+/// Error that can happen when creating a vertex buffer with `VertexBuffer::from_iter`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromIterError {
+    /// Error while creating the vertex buffer.
+    CreationError(CreationError),
+
+    /// The iterator didn't produce the number of elements given by its `len()`.
+    LengthMismatch,
+}
+
+impl From<CreationError> for FromIterError {
+    #[inline]
+    fn from(err: CreationError) -> FromIterError {
+        FromIterError::CreationError(err)
+    }
+}
+
+impl fmt::Display for FromIterError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::FromIterError::*;
+        let desc = match self {
+            CreationError(_) => "Error while creating the vertex buffer",
+            LengthMismatch =>
+                "The iterator didn't produce the number of elements given by its length",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for FromIterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::FromIterError::*;
+        match *self {
+            CreationError(ref error) => Some(error),
+            LengthMismatch => None,
+        }
+    }
+}
+
 impl<T: Copy> GlObject for VertexBuffer<T> {
     type Id = gl::types::GLuint;
 
@@ -214,6 +280,43 @@ impl<T> VertexBuffer<T> where T: Vertex {
         VertexBuffer::empty_impl(facade, elements, BufferMode::Immutable)
     }
 
+    /// Builds a new vertex buffer from an iterator of vertices.
+    ///
+    /// The vertices are written directly to the buffer without being collected in a `Vec` first.
+    /// Returns `FromIterError::LengthMismatch` if the iterator doesn't produce the number of
+    /// elements given by its `len()`.
+    pub fn from_iter<F: ?Sized, I>(facade: &F, iter: I) -> Result<VertexBuffer<T>, FromIterError>
+                                   where F: Facade, I: IntoIterator<Item = T>,
+                                         I::IntoIter: ExactSizeIterator
+    {
+        let iter = iter.into_iter();
+        let len = iter.len();
+
+        let mut buffer = VertexBuffer::empty_impl(facade, len, BufferMode::Default)?;
+
+        // mapping an empty range is invalid
+        if len == 0 {
+            return Ok(buffer);
+        }
+
+        {
+            let mut mapping = buffer.buffer.map_write();
+            let mut written = 0;
+            for vertex in iter {
+                if written == len {
+                    return Err(FromIterError::LengthMismatch);
+                }
+                mapping.set(written, vertex);
+                written += 1;
+            }
+            if written != len {
+                return Err(FromIterError::LengthMismatch);
+            }
+        }
+
+        Ok(buffer)
+    }
+
     #[inline]
     fn empty_impl<F: ?Sized>(facade: &F, elements: usize, mode: BufferMode)
                      -> Result<VertexBuffer<T>, CreationError>
@@ -306,6 +409,24 @@ impl<T> VertexBuffer<T> where T: Copy {
         })
     }
 
+    /// Replaces the vertices starting at `offset` with the content of `data`.
+    ///
+    /// The rest of the buffer is left untouched, which is useful to update only a part of
+    /// a mesh.
+    ///
+    /// # Implementation
+    ///
+    /// Same as `Buffer::write`. Calls `glBufferSubData` for regular buffers.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `offset + data.len()` is superior to the length of the buffer.
+    #[inline]
+    pub fn write_at(&self, offset: usize, data: &[T]) {
+        let end = offset.checked_add(data.len()).expect("Vertex buffer write out of range");
+        self.buffer.slice(offset .. end).expect("Vertex buffer write out of range").write(data);
+    }
+
     /// Returns the associated `VertexFormat`.
     #[inline]
     pub fn get_bindings(&self) -> &VertexFormat {
@@ -10,6 +10,8 @@ use crate::CapabilitiesSource;
 use cgmath;
 #[cfg(feature = "nalgebra")]
 use nalgebra;
+#[cfg(feature = "half")]
+use half::f16;
 
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -636,6 +638,56 @@ unsafe impl Attribute for [u64; 4] {
     const TYPE: AttributeType = AttributeType::U64U64U64U64;
 }
 
+#[cfg(feature="half")]
+unsafe impl Attribute for f16 {
+    const TYPE: AttributeType = AttributeType::F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for (f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [f16; 2] {
+    const TYPE: AttributeType = AttributeType::F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for (f16, f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [f16; 3] {
+    const TYPE: AttributeType = AttributeType::F16F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for (f16, f16, f16, f16) {
+    const TYPE: AttributeType = AttributeType::F16F16F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [f16; 4] {
+    const TYPE: AttributeType = AttributeType::F16F16F16F16;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [[f16; 2]; 2] {
+    const TYPE: AttributeType = AttributeType::F16x2x2;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [[f16; 3]; 3] {
+    const TYPE: AttributeType = AttributeType::F16x3x3;
+}
+
+#[cfg(feature="half")]
+unsafe impl Attribute for [[f16; 4]; 4] {
+    const TYPE: AttributeType = AttributeType::F16x4x4;
+}
+
 unsafe impl Attribute for f32 {
     const TYPE: AttributeType = AttributeType::F32;
 }
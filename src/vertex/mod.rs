@@ -26,6 +26,9 @@ implement_vertex!(MyVertex, position, texcoords);
 # }
 ```
 
+If the `half` feature is enabled, the members can also be made of `half::f16` values. They are
+bound as `GL_HALF_FLOAT` attributes, which halves the size of the vertex data compared to `f32`.
+
 ## Vertex buffer
 
 Once you have a struct that implements the `Vertex` trait, you can build an array of vertices and
@@ -133,7 +136,9 @@ use std::option::IntoIter;
 pub use self::buffer::{VertexBuffer, VertexBufferAny};
 pub use self::buffer::VertexBufferSlice;
 pub use self::buffer::CreationError as BufferCreationError;
+pub use self::buffer::FromIterError;
 pub use self::format::{AttributeType, VertexFormat};
+pub use self::streaming::StreamingVertexBuffer;
 pub use self::transform_feedback::{is_transform_feedback_supported, TransformFeedbackSession};
 
 use crate::buffer::BufferAnySlice;
@@ -141,6 +146,7 @@ use crate::CapabilitiesSource;
 
 mod buffer;
 mod format;
+mod streaming;
 mod transform_feedback;
 
 /// Describes the source to use for the vertices when drawing.
@@ -0,0 +1,118 @@
+use crate::backend::Facade;
+use crate::vertex::{Vertex, VertexBuffer, VertexBufferSlice};
+use crate::vertex::buffer::CreationError;
+
+/// A vertex buffer whose content is replaced at each frame.
+///
+/// The buffer is split in several regions of the same length, three by default. Each call to
+/// `next_region` returns the region that follows the one returned by the previous call, which
+/// means that the CPU writes to a region while the GPU may still be reading the previous ones.
+/// This avoids waiting for the GPU when the vertices change every frame, for example with
+/// particle systems.
+///
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # #[derive(Copy, Clone)]
+/// # struct Vertex { position: [f32; 2] }
+/// # implement_vertex!(Vertex, position);
+/// # fn example(display: glium::Display, program: glium::Program,
+/// #            particles: Vec<Vertex>) {
+/// let mut buffer = glium::vertex::StreamingVertexBuffer::new(&display, particles.len()).unwrap();
+///
+/// loop {
+///     let region = buffer.next_region();
+///     region.write(&particles);
+///
+///     let mut frame = display.draw();
+///     frame.draw(region, glium::index::NoIndices(glium::index::PrimitiveType::Points),
+///                &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+///     frame.finish().unwrap();
+/// }
+/// # }
+/// # }
+/// ```
+///
+/// # Implementation
+///
+/// The buffer is created with `BufferMode::Persistent`, so its content is written through a
+/// persistent mapping when `glBufferStorage` is supported. Glium's fences make sure that a
+/// region isn't modified while the GPU is still reading it.
+///
+/// Otherwise, the whole buffer is invalidated each time the first region is returned again,
+/// which lets the driver allocate new storage instead of waiting for the GPU.
+pub struct StreamingVertexBuffer<T> where T: Copy {
+    buffer: VertexBuffer<T>,
+    region_len: usize,
+    regions: usize,
+    next_region: usize,
+}
+
+impl<T> StreamingVertexBuffer<T> where T: Vertex {
+    /// Builds a new buffer made of three regions of `len` vertices each.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F, len: usize)
+                          -> Result<StreamingVertexBuffer<T>, CreationError>
+                          where F: Facade
+    {
+        StreamingVertexBuffer::with_regions(facade, len, 3)
+    }
+
+    /// Builds a new buffer made of `regions` regions of `len` vertices each.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `regions` is 0.
+    pub fn with_regions<F: ?Sized>(facade: &F, len: usize, regions: usize)
+                                   -> Result<StreamingVertexBuffer<T>, CreationError>
+                                   where F: Facade
+    {
+        assert!(regions >= 1);
+
+        let buffer = VertexBuffer::empty_persistent(facade, len * regions)?;
+
+        Ok(StreamingVertexBuffer {
+            buffer,
+            region_len: len,
+            regions,
+            next_region: 0,
+        })
+    }
+}
+
+impl<T> StreamingVertexBuffer<T> where T: Copy {
+    /// Returns the number of vertices of each region.
+    #[inline]
+    pub fn region_len(&self) -> usize {
+        self.region_len
+    }
+
+    /// Returns the number of regions.
+    #[inline]
+    pub fn regions(&self) -> usize {
+        self.regions
+    }
+
+    /// Returns the buffer that contains all the regions.
+    #[inline]
+    pub fn get_buffer(&self) -> &VertexBuffer<T> {
+        &self.buffer
+    }
+
+    /// Returns the next region of the buffer.
+    ///
+    /// The content of the region is undefined. You should write to it with `write` before
+    /// drawing.
+    pub fn next_region(&mut self) -> VertexBufferSlice<'_, T> {
+        if self.next_region == 0 && !self.buffer.is_persistent() {
+            self.buffer.invalidate();
+        }
+
+        let start = self.next_region * self.region_len;
+        self.next_region = (self.next_region + 1) % self.regions;
+
+        self.buffer.slice(start .. start + self.region_len).unwrap()
+    }
+}
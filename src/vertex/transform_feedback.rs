@@ -1,4 +1,4 @@
-use std::{ mem, fmt };
+use std::{ mem, fmt, ptr };
 use std::error::Error;
 
 use crate::version::Api;
@@ -9,8 +9,10 @@ use crate::BufferExt;
 use crate::GlObject;
 use crate::ContextExt;
 use crate::CapabilitiesSource;
+use crate::DrawError;
 use crate::TransformFeedbackSessionExt;
 use crate::buffer::{Buffer, BufferAnySlice};
+use crate::draw_parameters::TransformFeedbackPrimitivesWrittenQuery;
 use crate::index::PrimitiveType;
 use crate::program::OutputPrimitives;
 use crate::program::Program;
@@ -41,9 +43,9 @@ use crate::gl;
 ///   program than the one you created the session with.
 ///
 /// - The transform feedback process doesn't necessarily fill the whole buffer. To retrieve the
-///   number of vertices that are written to the buffer, use a query object (see the
-///   `draw_parameters` module). It is however usually easy to determine in advance the number of
-///   vertices that will be written based on the input data.
+///   number of primitives that are written to the buffer, use `primitives_written` or a query
+///   object (see the `draw_parameters` module). To draw exactly the vertices that were written
+///   without reading this number, use `Surface::draw_transform_feedback`.
 ///
 /// - The buffer will obtain either a list of points, a list of lines (two vertices), or a list of
 ///   triangles (three vertices). If you draw a triangle strip or a triangle fan for example,
@@ -54,6 +56,10 @@ use crate::gl;
 ///   to be pushed in the buffer after the existing data. However you must always use the same type
 ///   of primitives and the same program.
 ///
+/// - If the varyings of the program are captured with `TransformFeedbackMode::Separate`, each
+///   varying is written to its own buffer. The buffer passed to `new` receives the first one,
+///   and the others must be passed in order with `add_buffer`.
+///
 /// # Example
 ///
 /// ```no_run
@@ -88,8 +94,9 @@ use crate::gl;
 /// ```
 #[derive(Debug)]
 pub struct TransformFeedbackSession<'a> {
-    buffer: BufferAnySlice<'a>,
+    buffers: Vec<BufferAnySlice<'a>>,
     program: &'a Program,
+    primitives_written: Option<TransformFeedbackPrimitivesWrittenQuery>,
 }
 
 /// Error that can happen when creating a `TransformFeedbackSession`.
@@ -130,8 +137,10 @@ pub fn is_transform_feedback_supported<F: ?Sized>(facade: &F) -> bool where F: F
 impl<'a> TransformFeedbackSession<'a> {
     /// Builds a new transform feedback session.
     ///
-    /// TODO: this constructor should ultimately support passing multiple buffers of different
-    ///       types
+    /// `buffer` receives the first transform feedback buffer of the program, which contains
+    /// all the varyings if they are captured with `TransformFeedbackMode::Interleaved`.
+    /// Returns `WrongVertexFormat` if the program wasn't linked with transform feedback
+    /// varyings that match `V`.
     pub fn new<F: ?Sized, V>(facade: &F, program: &'a Program, buffer: &'a mut Buffer<[V]>)
                      -> Result<TransformFeedbackSession<'a>, TransformFeedbackSessionCreationError>
                      where F: Facade, V: Vertex + Copy + Send + 'static
@@ -140,20 +149,85 @@ impl<'a> TransformFeedbackSession<'a> {
             return Err(TransformFeedbackSessionCreationError::NotSupported);
         }
 
-        if !program.transform_feedback_matches(&<V as Vertex>::build_bindings(),
-                                               mem::size_of::<V>())
+        if !program.transform_feedback_buffer_matches(0, &<V as Vertex>::build_bindings(),
+                                                      mem::size_of::<V>())
         {
             return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
         }
 
+        let buffers = vec![buffer.as_slice_any()];
+
         Ok(TransformFeedbackSession {
-            buffer: buffer.as_slice_any(),
+            buffers,
             program,
+            primitives_written: None,
         })
     }
+
+    /// Starts counting the primitives that are written by the draw commands that use this
+    /// session. The result can be retrieved with `primitives_written`.
+    ///
+    /// This creates a query object, which is why counting is disabled by default. It has no
+    /// effect if the query isn't supported by the backend.
+    pub fn count_primitives_written(mut self) -> TransformFeedbackSession<'a> {
+        if self.primitives_written.is_none() {
+            let context = self.buffers[0].get_context();
+            self.primitives_written = TransformFeedbackPrimitivesWrittenQuery::new(context).ok();
+        }
+
+        self
+    }
+
+    /// Adds the buffer that receives the next transform feedback buffer of the program.
+    ///
+    /// This is used with programs whose varyings are captured with
+    /// `TransformFeedbackMode::Separate`, in which case each varying has its own buffer. Drawing
+    /// with a session that doesn't have a buffer for each varying returns an error.
+    pub fn add_buffer<V>(mut self, buffer: &'a mut Buffer<[V]>)
+                         -> Result<TransformFeedbackSession<'a>, TransformFeedbackSessionCreationError>
+                         where V: Vertex + Copy + Send + 'static
+    {
+        if !self.program.transform_feedback_buffer_matches(self.buffers.len(),
+                                                           &<V as Vertex>::build_bindings(),
+                                                           mem::size_of::<V>())
+        {
+            return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
+        }
+
+        self.buffers.push(buffer.as_slice_any());
+        Ok(self)
+    }
+
+    /// Destroys the session and returns the number of primitives that have been written to
+    /// the buffer.
+    ///
+    /// Only the draw commands that didn't specify their own
+    /// `transform_feedback_primitives_written_query` are counted, and the counting stops as soon
+    /// as a draw command doesn't use this session. This function blocks until the result is
+    /// available. If you only need to draw the vertices that were written, use
+    /// `Surface::draw_transform_feedback` instead, which doesn't require the CPU to know this
+    /// number.
+    ///
+    /// Returns `None` if `count_primitives_written` hasn't been called or if the query isn't
+    /// supported by the backend.
+    pub fn primitives_written(mut self) -> Option<u32> {
+        self.primitives_written.take().map(|query| query.get())
+    }
 }
 
 impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
+    fn check_program(&self, program: &Program) -> Result<(), DrawError> {
+        if !ptr::eq(self.program, program) {
+            return Err(DrawError::TransformFeedbackProgramMismatch);
+        }
+
+        if self.buffers.len() != program.get_transform_feedback_buffers().len() {
+            return Err(DrawError::TransformFeedbackBuffersMismatch);
+        }
+
+        Ok(())
+    }
+
     fn bind(&self, ctxt: &mut CommandContext<'_>, draw_primitives: PrimitiveType) {
         // TODO: check that the state matches what is required
         if ctxt.state.transform_feedback_enabled.is_some() {
@@ -161,7 +235,9 @@ impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
         }
 
         // FIXME: use the memory barrier system
-        self.buffer.bind_to_transform_feedback(ctxt, 0);
+        for (index, buffer) in self.buffers.iter().enumerate() {
+            buffer.bind_to_transform_feedback(ctxt, index as gl::types::GLuint);
+        }
 
         unsafe {
             let primitives = match (self.program.get_output_primitives(), draw_primitives) {
@@ -219,6 +295,11 @@ impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
             TransformFeedbackSession::unbind(ctxt);
         }
     }
+
+    #[inline]
+    fn get_primitives_written_query(&self) -> Option<&TransformFeedbackPrimitivesWrittenQuery> {
+        self.primitives_written.as_ref()
+    }
 }
 
 impl<'a> Drop for TransformFeedbackSession<'a> {
@@ -227,7 +308,9 @@ impl<'a> Drop for TransformFeedbackSession<'a> {
         // Since the session can be mem::forget'ed, the code in buffer/alloc.rs ensures that the
         // buffer isn't used by transform feedback.
         // However we end the session now anyway.
-        let mut ctxt = self.buffer.get_context().make_current();
-        Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, self.buffer.get_id());
+        let mut ctxt = self.buffers[0].get_context().make_current();
+        for buffer in self.buffers.iter() {
+            Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, buffer.get_id());
+        }
     }
 }
@@ -12,6 +12,7 @@ use crate::vertex::AttributeType;
 use crate::vertex::VertexFormat;
 use crate::GlObject;
 use crate::BufferExt;
+use crate::DrawError;
 
 use crate::gl;
 use crate::context::CommandContext;
@@ -161,7 +162,7 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
     /// Finish binding the vertex attributes.
     ///
     /// If `base_vertex` was set to true, returns the base vertex to use when drawing.
-    pub fn bind(mut self) -> Option<gl::types::GLint> {
+    pub fn bind(mut self) -> Result<Option<gl::types::GLint>, DrawError> {
         let ctxt = self.context;
 
         if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
@@ -200,23 +201,26 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
                                      .get(&(buffers_list.clone(), program_id))
             {
                 value.bind(ctxt);
-                return base_vertex.map(|v| v as gl::types::GLint);
+                return Ok(base_vertex.map(|v| v as gl::types::GLint));
             }
 
             // if not found, building a new one
             let new_vao = unsafe {
                 VertexArrayObject::new(ctxt, &self.vertex_buffers,
-                                       self.element_array_buffer, self.program)
+                                       self.element_array_buffer, self.program)?
             };
 
             new_vao.bind(ctxt);
             ctxt.vertex_array_objects.vaos.borrow_mut().insert((buffers_list, program_id), new_vao);
 
-            base_vertex.map(|v| v as gl::types::GLint)
+            Ok(base_vertex.map(|v| v as gl::types::GLint))
 
         } else {
             // VAOs are not supported
 
+            // there is no cache, so this has to be checked every time
+            check_attributes_collision(self.program, &self.vertex_buffers)?;
+
             // just in case
             bind_vao(ctxt, 0);
 
@@ -234,9 +238,9 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
             // TODO: it is unlikely that a backend supports base vertex but not VAOs, so we just
             //       ignore this case ; however it would ideally be better to handle it
             if self.base_vertex {
-                Some(0)
+                Ok(Some(0))
             } else {
-                None
+                Ok(None)
             }
         }
     }
@@ -255,9 +259,13 @@ impl VertexArrayObject {
     ///
     /// The vertex buffer, index buffer and program must not outlive the
     /// VAO, and the VB & program attributes must not change.
+    ///
+    /// Returns `DrawError::VertexAttributeCollision` if several vertex buffers provide the same
+    /// attribute of the program.
     unsafe fn new(mut ctxt: &mut CommandContext<'_>,
                   vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)],
-                  index_buffer: Option<BufferAnySlice<'_>>, program: &Program) -> VertexArrayObject
+                  index_buffer: Option<BufferAnySlice<'_>>, program: &Program)
+                  -> Result<VertexArrayObject, DrawError>
     {
         // checking the attributes types
         for &(_, ref bindings, _, _, _) in vertex_buffers {
@@ -317,7 +325,7 @@ impl VertexArrayObject {
             }
         };
 
-        // TODO: check for collisions between the vertices sources
+        check_attributes_collision(program, vertex_buffers)?;
 
         // building the VAO
         let id = {
@@ -350,12 +358,12 @@ impl VertexArrayObject {
             bind_attribute(ctxt, program, vertex_buffer, bindings, offset, stride, divisor);
         }
 
-        VertexArrayObject {
+        Ok(VertexArrayObject {
             id,
             destroyed: false,
             element_array_buffer: index_buffer.map(|b| b.get_id()).unwrap_or(0),
             element_array_buffer_hijacked: Cell::new(false),
-        }
+        })
     }
 
     /// Sets this VAO as the current VAO.
@@ -422,6 +430,42 @@ impl GlObject for VertexArrayObject {
     }
 }
 
+/// Checks that the attributes that are used by the program aren't provided by several vertex
+/// buffers, for example by both a per-vertex buffer and a per-instance buffer.
+fn check_attributes_collision(program: &Program,
+                              vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize,
+                                                 Option<u32>)])
+                              -> Result<(), DrawError>
+{
+    // locations of the program attributes that have been provided, and index of the buffer
+    let mut provided_locations: SmallVec<[(i32, usize); 16]> = SmallVec::new();
+
+    for (buffer_index, (_, bindings, _, _, _)) in vertex_buffers.iter().enumerate() {
+        for &(ref name, _, location, _, _) in bindings.iter() {
+            let location = match location {
+                -1 => match program.get_attribute(Borrow::<str>::borrow(name)) {
+                    Some(attribute) => attribute.location,
+                    None => continue,
+                },
+                location => {
+                    if !program.attributes().any(|(_, a)| a.location == location) {
+                        continue;
+                    }
+                    location
+                },
+            };
+
+            if provided_locations.iter().any(|&(l, b)| l == location && b != buffer_index) {
+                return Err(DrawError::VertexAttributeCollision { name: name.to_string() });
+            }
+
+            provided_locations.push((location, buffer_index));
+        }
+    }
+
+    Ok(())
+}
+
 fn vertex_binding_type_to_gl(ty: AttributeType) -> (gl::types::GLenum, gl::types::GLint, gl::types::GLint) {
     match ty {
         AttributeType::I8 => (gl::BYTE, 1, 1),
@@ -0,0 +1,114 @@
+/*!
+Drawing a wireframe over a mesh, for debugging purposes.
+
+The `WireframeOverlay` trait adds a `draw_wireframe_overlay` method to all the surfaces. It
+draws a mesh twice with the same program: once filled, and once with `PolygonMode::Line`. The
+filled polygons are pushed back with a polygon offset so that the lines pass the depth test.
+
+The helper is provided by a separate trait so that it needs to be imported explicitly:
+
+```no_run
+# use glium::Surface;
+use glium::wireframe::WireframeOverlay;
+# fn example<S: Surface>(surface: &mut S, vb: glium::VertexBuffer<[f32; 3]>,
+#                        ib: glium::IndexBuffer<u16>, program: glium::Program) {
+surface.draw_wireframe_overlay(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                               [0.0, 1.0, 0.0, 1.0], &Default::default()).unwrap();
+# }
+```
+
+Since glium can't change the output of the program, the color of the lines is passed as a
+`vec4` uniform named `wireframe_color`. Its value is `(0.0, 0.0, 0.0, 0.0)` when drawing the
+filled polygons and the requested color when drawing the lines, so the fragment shader can
+simply end with:
+
+```glsl
+color = mix(color, vec4(wireframe_color.rgb, 1.0), wireframe_color.a);
+```
+
+Programs that don't declare this uniform draw the lines with their regular output.
+
+*/
+use crate::draw_parameters::{DrawParameters, PolygonMode, PolygonOffset};
+use crate::index::IndicesSource;
+use crate::uniforms::{Uniforms, UniformValue};
+use crate::vertex::MultiVerticesSource;
+
+use crate::DrawError;
+use crate::Program;
+use crate::Surface;
+
+/// Name of the uniform that contains the color of the lines.
+pub const WIREFRAME_COLOR_UNIFORM: &str = "wireframe_color";
+
+/// Adds a method that draws a mesh with its wireframe on top of it.
+///
+/// This trait is implemented on all the surfaces.
+pub trait WireframeOverlay: Surface {
+    /// Draws the mesh filled, then draws its edges with `line_color`.
+    ///
+    /// The same parameters as `Surface::draw` are used for both passes, except that:
+    ///
+    /// - The filled pass uses `PolygonMode::Fill` and a polygon offset that pushes the
+    ///   polygons back.
+    /// - The line pass uses `PolygonMode::Line`, doesn't write to the depth buffer and doesn't
+    ///   use a polygon offset.
+    ///
+    /// See the documentation of the module for how `line_color` reaches the program. Like
+    /// `PolygonMode::Line` itself, this function isn't supported with OpenGL ES.
+    fn draw_wireframe_overlay<'a, 'b, V, I, U>(&mut self, vertex_buffer: V, index_buffer: I,
+                                               program: &Program, uniforms: &U,
+                                               line_color: [f32; 4],
+                                               draw_parameters: &DrawParameters<'_>)
+                                               -> Result<(), DrawError>
+        where V: MultiVerticesSource<'b> + Copy, I: Into<IndicesSource<'a>> + Copy,
+              U: Uniforms
+    {
+        let fill_parameters = DrawParameters {
+            polygon_mode: PolygonMode::Fill,
+            back_polygon_mode: None,
+            polygon_offset: PolygonOffset {
+                factor: 1.0,
+                units: 1.0,
+                fill: true,
+                .. Default::default()
+            },
+            .. draw_parameters.clone()
+        };
+
+        let fill_uniforms = WithWireframeColor { uniforms, color: [0.0; 4] };
+        self.draw(vertex_buffer, index_buffer, program, &fill_uniforms, &fill_parameters)?;
+
+        let mut line_parameters = DrawParameters {
+            polygon_mode: PolygonMode::Line,
+            back_polygon_mode: None,
+            polygon_offset: Default::default(),
+            .. draw_parameters.clone()
+        };
+        line_parameters.depth.write = false;
+
+        let line_uniforms = WithWireframeColor { uniforms, color: line_color };
+        self.draw(vertex_buffer, index_buffer, program, &line_uniforms, &line_parameters)
+    }
+}
+
+impl<S: ?Sized> WireframeOverlay for S where S: Surface {}
+
+/// Adds the `wireframe_color` uniform to a list of uniforms.
+struct WithWireframeColor<'u, U> where U: Uniforms {
+    uniforms: &'u U,
+    color: [f32; 4],
+}
+
+impl<'u, U> Uniforms for WithWireframeColor<'u, U> where U: Uniforms {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        self.uniforms.visit_values(&mut output);
+        output(WIREFRAME_COLOR_UNIFORM, UniformValue::Vec4(self.color));
+    }
+
+    #[inline]
+    fn visit_located_values<'a, F: FnMut(i32, UniformValue<'a>)>(&'a self, output: F) {
+        self.uniforms.visit_located_values(output);
+    }
+}
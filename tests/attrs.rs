@@ -307,3 +307,93 @@ attribute_test!(attribute_vec3_i32, [i32; 3], "vec3", [0, 0, 0], "vec4(field1, 1
 attribute_test!(attribute_vec3_tuple_i32, (i32, i32, i32), "vec3", (0, 0, 0), "vec4(field1, 1.0)");
 attribute_test!(attribute_vec4_i32, [i32; 4], "vec4", [0, 0, 0, 0], "field1");
 attribute_test!(attribute_vec4_tuple_i32, (i32, i32, i32, i32), "vec4", (0, 0, 0, 0), "field1");
+
+#[test]
+#[cfg(feature = "half")]
+fn half_float_attributes() {
+    use half::f16;
+
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f16; 2],
+        color: [f16; 4],
+    }
+
+    implement_vertex!(Vertex, position, color);
+
+    let red = [f16::ONE, f16::ZERO, f16::ZERO, f16::ONE];
+    let vertices = [[-1.0f32, 1.0], [1.0, 1.0], [-1.0, -1.0], [1.0, -1.0]].iter().map(|p| {
+        Vertex { position: [f16::from_f32(p[0]), f16::from_f32(p[1])], color: red }
+    }).collect::<Vec<_>>();
+
+    let vertex_buffer = match glium::VertexBuffer::new(&display, &vertices) {
+        Err(glium::vertex::BufferCreationError::FormatNotSupported) => return,
+        vb => vb.unwrap()
+    };
+    let index_buffer = glium::index::NoIndices(PrimitiveType::TriangleStrip);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec4 color;
+                varying vec4 v_color;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_color = color;
+                }
+            ",
+            fragment: "
+                #version 110
+
+                varying vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color;
+                }
+            "
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute mediump vec2 position;
+                attribute mediump vec4 color;
+                varying mediump vec4 v_color;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_color = color;
+                }
+            ",
+            fragment: "
+                #version 100
+
+                varying mediump vec4 v_color;
+
+                void main() {
+                    gl_FragColor = v_color;
+                }
+            "
+        }
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                              &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
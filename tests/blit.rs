@@ -93,3 +93,103 @@ fn blit_color_and_depth_buffer() {
     // todo: how to check dst_tex_depth? There is no .read() on a DepthTexture2d...
     display.assert_no_error(None);
 }
+
+#[test]
+fn blit_color_srgb_between_linear_buffers() {
+    let display = support::build_display();
+
+    let src_tex_color = support::build_unicolor_texture2d(&display, 0.0, 0.5, 1.0);
+    let src_frame_buffer = SimpleFrameBuffer::new(&display, &src_tex_color).unwrap();
+
+    let dst_tex_color = support::build_unicolor_texture2d(&display, 0.0, 0.0, 0.0);
+    let dst_frame_buffer = SimpleFrameBuffer::new(&display, &dst_tex_color).unwrap();
+
+    // enabling the sRGB conversion doesn't change anything if both buffers are linear
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let dst_rect = BlitTarget { left: 0, bottom: 0, width: 2, height: 2 };
+    dst_frame_buffer.blit_buffers_from_simple_framebuffer_with_srgb(&src_frame_buffer, &src_rect,
+                                                                    &dst_rect,
+                                                                    MagnifySamplerFilter::Nearest,
+                                                                    BlitMask::color(), true);
+
+    let color_data: Vec<Vec<(u8, u8, u8, u8)>> = dst_tex_color.read();
+    assert_eq!(color_data[0][0], (0, 127, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn blit_color_with_filter_flipped() {
+    let display = support::build_display();
+
+    let src_texture = glium::Texture2d::new(&display, vec![
+        vec![(255u8, 0u8, 0u8, 255u8), (255, 0, 0, 255)],
+        vec![(0, 255, 0, 255), (0, 255, 0, 255)],
+    ]).unwrap();
+
+    let dst_texture = support::build_renderable_texture(&display);
+    dst_texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // a negative height flips the image vertically
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let dst_rect = BlitTarget { left: 0, bottom: 2, width: 2, height: -2 };
+    src_texture.as_surface().blit_color_with_filter(&src_rect, &dst_texture.as_surface(),
+                                                    &dst_rect, MagnifySamplerFilter::Linear)
+                                                    .unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = dst_texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+    assert_eq!(data[0][1], (0, 255, 0, 255));
+    assert_eq!(data[1][0], (255, 0, 0, 255));
+    assert_eq!(data[1][1], (255, 0, 0, 255));
+    assert_eq!(data[2][0], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn blit_color_with_linear_filter_from_integral_texture() {
+    let display = support::build_display();
+
+    let src_texture = match glium::texture::IntegralTexture2d::empty(&display, 2, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+    let src_frame_buffer = SimpleFrameBuffer::new(&display, &src_texture).unwrap();
+
+    let dst_texture = support::build_renderable_texture(&display);
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let dst_rect = BlitTarget { left: 0, bottom: 0, width: 4, height: 4 };
+    let result = src_frame_buffer.blit_color_with_filter(&src_rect, &dst_texture.as_surface(),
+                                                         &dst_rect, MagnifySamplerFilter::Linear);
+    assert_eq!(result, Err(glium::BlitError::LinearFilterOnIntegralFormat));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn blit_color_with_filter_from_multisampled_texture() {
+    let display = support::build_display();
+
+    let src_texture = match glium::texture::Texture2dMultisample::empty(&display, 2, 2, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+    let src_frame_buffer = SimpleFrameBuffer::new(&display, &src_texture).unwrap();
+
+    let dst_texture = support::build_renderable_texture(&display);
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let dst_rect = BlitTarget { left: 0, bottom: 0, width: 4, height: 4 };
+    let result = src_frame_buffer.blit_color_with_filter(&src_rect, &dst_texture.as_surface(),
+                                                         &dst_rect, MagnifySamplerFilter::Nearest);
+    assert_eq!(result, Err(glium::BlitError::MultisampledSourceRectMismatch));
+
+    // resolving into a rectangle of the same size is allowed
+    let dst_rect = BlitTarget { left: 0, bottom: 0, width: 2, height: 2 };
+    src_frame_buffer.blit_color_with_filter(&src_rect, &dst_texture.as_surface(), &dst_rect,
+                                            MagnifySamplerFilter::Nearest).unwrap();
+
+    display.assert_no_error(None);
+}
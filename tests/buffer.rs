@@ -89,6 +89,28 @@ fn buffer_dynamic_empty_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn buffer_unsupported_format() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [f64; 3],
+    }
+
+    implement_vertex!(Vertex, field1);
+
+    let supported = <Vertex as glium::vertex::Vertex>::is_supported(&display);
+
+    match glium::VertexBuffer::new(&display, &[Vertex { field1: [0.0; 3] }]) {
+        Ok(_) => assert!(supported),
+        Err(glium::vertex::BufferCreationError::FormatNotSupported) => assert!(!supported),
+        Err(err) => panic!("{}", err),
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_immutable_mapping_read() {
     let display = support::build_display();
@@ -295,6 +317,52 @@ fn buffer_dynamic_read() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn buffer_map_read_after_write() {
+    let display = support::build_display();
+
+    let mut buffer: glium::buffer::Buffer<[u32]> =
+        glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4][..],
+                                   glium::buffer::BufferType::ArrayBuffer,
+                                   BufferMode::Dynamic).unwrap();
+
+    buffer.write(&[5, 6, 7, 8]);
+
+    {
+        let mapping = buffer.map_read();
+        assert_eq!(&mapping[..], &[5, 6, 7, 8]);
+    }
+
+    // the buffer must have been unmapped when the mapping was dropped
+    buffer.write(&[9, 10, 11, 12]);
+    assert_eq!(&buffer.map_read()[..], &[9, 10, 11, 12]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_read_into() {
+    let display = support::build_display();
+
+    let buffer: glium::buffer::Buffer<[u32]> =
+        glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4][..],
+                                   glium::buffer::BufferType::ArrayBuffer,
+                                   BufferMode::Dynamic).unwrap();
+
+    let mut data = vec![9u32; 16];
+    match buffer.read_into(&mut data) {
+        Ok(()) => (),
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+    assert_eq!(data, [1, 2, 3, 4]);
+
+    buffer.slice(1 .. 3).unwrap().read_into(&mut data).unwrap();
+    assert_eq!(data, [2, 3]);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_immutable_read_slice() {
     let display = support::build_display();
@@ -455,6 +523,29 @@ fn buffer_dynamic_write() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn buffer_default_immutable_storage_write() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u16, 2, 3, 4],
+                                            glium::buffer::BufferType::ElementArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    assert_eq!(buffer.is_immutable_storage(), display.feature_report().buffer_storage);
+
+    buffer.write(&[5, 6, 7, 8]);
+
+    let data = match buffer.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, [5, 6, 7, 8]);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_immutable_write_slice() {
     let display = support::build_display();
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate glium;
 
-use glium::Surface;
+use glium::{CapabilitiesSource, Surface};
 
 mod support;
 
@@ -14,6 +14,15 @@ fn get_opengl_version() {
     assert!(version.1 >= 1);
 }
 
+#[test]
+fn get_glsl_version() {
+    let display = support::build_display();
+    let version = display.get_glsl_version();
+    display.assert_no_error(None);
+
+    assert!(version >= display.get_supported_glsl_version());
+}
+
 #[test]
 fn clear_color() {
     let display = support::build_display();
@@ -149,3 +158,336 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+#[test]
+fn check_reset_status() {
+    let display = support::build_display();
+    assert_eq!(display.check_reset_status(), glium::ResetStatus::NoError);
+    assert!(!display.is_context_lost());
+    display.assert_no_error(None);
+}
+
+#[test]
+fn has_extension() {
+    let display = support::build_display();
+
+    assert_eq!(display.has_extension("GL_ARB_debug_output"),
+               display.get_extensions().gl_arb_debug_output);
+    assert_eq!(display.has_extension("GL_KHR_debug"), display.get_extensions().gl_khr_debug);
+    assert!(!display.has_extension("gl_khr_debug"));
+    assert!(!display.has_extension(""));
+}
+
+#[test]
+fn get_integer_v() {
+    let display = support::build_display();
+
+    // GL_MAX_VIEWPORT_DIMS
+    let dims = display.get_integer_v(0x0D3A, 2);
+    assert_eq!((dims[0] as u32, dims[1] as u32), display.get_max_viewport_dimensions());
+    assert_eq!(display.get_integer(0x0D3A), dims[0]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn purge_internal_caches_leaves_no_objects() {
+    // GL_DRAW_FRAMEBUFFER_BINDING and GL_VERTEX_ARRAY_BINDING
+    const FRAMEBUFFER_BINDING: u32 = 0x8CA6;
+    const VERTEX_ARRAY_BINDING: u32 = 0x85B5;
+
+    let display = support::build_display();
+    let has_vaos = display.get_opengl_version() >= &glium::Version(glium::Api::Gl, 3, 0) ||
+                   display.get_opengl_version() >= &glium::Version(glium::Api::GlEs, 3, 0);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    for _ in 0 .. 4 {
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                  &Default::default()).unwrap();
+        assert!(display.get_integer(FRAMEBUFFER_BINDING) != 0);
+        if has_vaos {
+            assert!(display.get_integer(VERTEX_ARRAY_BINDING) != 0);
+        }
+
+        // deleting a bound object reverts its binding to 0
+        display.purge_internal_caches();
+        assert_eq!(display.get_integer(FRAMEBUFFER_BINDING), 0);
+        if has_vaos {
+            assert_eq!(display.get_integer(VERTEX_ARRAY_BINDING), 0);
+        }
+
+        // the purged objects are created again when needed
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                  &Default::default()).unwrap();
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        assert_eq!(data[0][0], (255, 0, 0, 255));
+        display.purge_internal_caches();
+
+        display.assert_no_error(None);
+    }
+}
+
+#[test]
+fn frame_set_draw_buffer() {
+    let display = support::build_display();
+
+    let mut target = display.draw();
+    match target.set_draw_buffer(glium::framebuffer::DefaultFramebufferAttachment::BackLeft) {
+        Ok(()) | Err(glium::framebuffer::DrawBufferError::NotSupported) => (),
+        Err(e) => panic!("{}", e),
+    }
+
+    if !display.get_capabilities().stereo {
+        assert_eq!(target.set_draw_buffer(glium::framebuffer::DefaultFramebufferAttachment::BackRight),
+                   Err(glium::framebuffer::DrawBufferError::StereoNotSupported));
+    }
+
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_without_implicit_swap() {
+    let display = support::build_display();
+    assert!(display.frame_swaps_buffers());
+
+    display.set_frame_swaps_buffers(false);
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.set_finish().unwrap();
+    assert!(target.set_finish().is_err());
+    drop(target);
+
+    // the application presents the frame itself
+    display.swap_buffers().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_draw_eye() {
+    let display = support::build_display();
+
+    let mut target = display.draw();
+    let result = target.draw_eye(glium::framebuffer::Eye::Right);
+
+    if display.is_stereo() {
+        result.unwrap();
+        target.draw_eye(glium::framebuffer::Eye::Left).unwrap();
+    } else {
+        assert_eq!(result, Err(glium::framebuffer::DrawBufferError::StereoNotSupported));
+    }
+
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_timer() {
+    let display = support::build_display();
+    let mut timer = glium::frame_timer::FrameTimer::new(&display);
+
+    assert!(timer.cpu_frame_time().is_none());
+
+    for _ in 0 .. 3 {
+        timer.begin_frame();
+        let mut target = display.draw();
+        target.clear_color(0.0, 0.0, 0.0, 0.0);
+        target.finish().unwrap();
+        timer.end_frame();
+    }
+
+    assert!(timer.cpu_frame_time().is_some());
+    if !timer.is_gpu_time_supported() {
+        assert!(timer.gpu_frame_time().is_none());
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn feature_report() {
+    let display = support::build_display();
+    let report = display.feature_report();
+
+    assert_eq!(report.stereo, display.is_stereo());
+    assert_eq!(report.compute_shaders, glium::program::ComputeShader::is_supported(&display));
+    assert_eq!(report.tessellation_shaders,
+               glium::program::is_tessellation_shader_supported(&display));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn save_and_restore_state() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let snapshot = display.save_state();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &glium::DrawParameters {
+                                  blend: glium::Blend::alpha_blending(),
+                                  scissor: Some(glium::Rect {
+                                      left: 1, bottom: 1, width: 2, height: 2
+                                  }),
+                                  .. Default::default()
+                              }).unwrap();
+
+    display.restore_state(&snapshot);
+    assert_eq!(display.save_state(), snapshot);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "debug_state_cache")]
+fn verify_state_cache() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &glium::DrawParameters {
+                                  blend: glium::Blend::alpha_blending(),
+                                  .. Default::default()
+                              }).unwrap();
+
+    assert_eq!(display.verify_state_cache(), Vec::new());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn warm_up() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    program.warm_up();
+    display.warm_vao_cache(&vb, &ib, &program).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data[512][512], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn error_log() {
+    let display = support::build_display_with_debug(glium::debug::DebugCallbackBehavior::Ignore);
+    assert!(display.take_errors().is_empty());
+
+    display.set_error_logging(true);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    // logging errors doesn't change the result of the operations
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(display.take_errors(), Vec::new());
+
+    // an invalid state name generates `GL_INVALID_ENUM` outside of glium's operations
+    display.get_integer(0xFFFF);
+    assert_eq!(display.take_errors(),
+               vec![(glium::GlError::InvalidEnum, "unknown operation")]);
+    assert!(display.take_errors().is_empty());
+
+    display.set_error_logging(false);
+    assert!(display.take_errors().is_empty());
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_with_size() {
+    let display = support::build_display();
+    let dimensions = display.get_framebuffer_dimensions();
+
+    let mut frame = display.draw_with_size(dimensions);
+    assert_eq!(frame.get_dimensions(), dimensions);
+    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+    frame.finish().unwrap();
+
+    let mut frame = display.draw_with_size((dimensions.0 / 2, dimensions.1 / 2));
+    assert_eq!(frame.get_dimensions(), (dimensions.0 / 2, dimensions.1 / 2));
+    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+    frame.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn display_swap_buffers() {
+    let display = support::build_display();
+
+    let mut frame = display.draw();
+    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+    frame.finish().unwrap();
+
+    display.swap_buffers().unwrap();
+    display.assert_no_error(None);
+}
+
+#[test]
+fn new_shared() {
+    let (display, other_display) = support::build_shared_displays();
+
+    // a texture created with one display can be used with the other
+    let texture = support::build_unicolor_texture2d(&display, 1.0, 0.0, 0.0);
+
+    let frame = other_display.try_draw().unwrap();
+    texture.as_surface().fill(&frame, glium::uniforms::MagnifySamplerFilter::Nearest);
+    frame.finish().unwrap();
+
+    let mut frame = display.try_draw().unwrap();
+    frame.clear_color(0.0, 1.0, 0.0, 1.0);
+    frame.finish().unwrap();
+
+    other_display.swap_buffers().unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+    other_display.assert_no_error(None);
+}
+
+#[test]
+fn warm_vao_cache_attribute_missing() {
+    let display = support::build_display();
+    let (_, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        color: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, color);
+
+    let vb = glium::VertexBuffer::new(&display, &[Vertex { color: [0.0, 0.0] }; 4]).unwrap();
+
+    match display.warm_vao_cache(&vb, &ib, &program) {
+        Ok(()) | Err(glium::DrawError::AttributeMissing) => (),
+        e => panic!("{:?}", e),
+    }
+
+    display.assert_no_error(None);
+}
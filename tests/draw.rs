@@ -0,0 +1,68 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+
+mod support;
+
+#[test]
+fn draw_multi_primitive_types() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let indices: [(glium::index::IndicesSource<'_>, glium::index::PrimitiveType); 3] = [
+        ((&ib).into(), glium::index::PrimitiveType::Points),
+        ((&ib).into(), glium::index::PrimitiveType::TriangleStrip),
+        (glium::index::NoIndices(glium::index::PrimitiveType::Points).into(),
+         glium::index::PrimitiveType::LineStrip),
+    ];
+
+    texture.as_surface().draw_multi(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                                    &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_multi_several_index_buffers() {
+    let display = support::build_display();
+
+    let (vb, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // each index buffer covers one half of the surface
+    let top = glium::IndexBuffer::new(&display, glium::index::PrimitiveType::TrianglesList,
+                                      &[0u16, 1, 2]).unwrap();
+    let bottom = glium::IndexBuffer::new(&display, glium::index::PrimitiveType::TrianglesList,
+                                         &[1u16, 2, 3]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let indices: [(glium::index::IndicesSource<'_>, glium::index::PrimitiveType); 2] = [
+        ((&top).into(), glium::index::PrimitiveType::TrianglesList),
+        ((&bottom).into(), glium::index::PrimitiveType::TrianglesList),
+    ];
+
+    texture.as_surface().draw_multi(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                                    &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,205 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::draw_batch::DrawBatch;
+
+mod support;
+
+/// Builds a program that fills the primitives with the `color` uniform.
+fn build_color_program<F: ?Sized>(facade: &F) -> glium::Program
+    where F: glium::backend::Facade
+{
+    program!(facade,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform vec4 color;
+
+                void main() {
+                    gl_FragColor = color;
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                uniform lowp vec4 color;
+
+                void main() {
+                    gl_FragColor = color;
+                }
+            ",
+        },
+    ).unwrap()
+}
+
+#[test]
+fn submit_sorted() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let mut batch = DrawBatch::new();
+    batch.push(&vb, &ib, &program, glium::uniforms::EmptyUniforms, Default::default());
+    batch.push(&vb, &ib, &program, glium::uniforms::EmptyUniforms, Default::default());
+    assert_eq!(batch.len(), 2);
+
+    batch.submit(&mut texture.as_surface()).unwrap();
+    assert!(batch.is_empty());
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn submit_ordered() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let mut batch = DrawBatch::ordered();
+    assert!(!batch.is_sorting());
+    batch.push(&vb, &ib, &program, glium::uniforms::EmptyUniforms, Default::default());
+    batch.submit(&mut texture.as_surface()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn submit_sorted_keeps_order_of_same_state() {
+    let display = support::build_display();
+
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+    let program = build_color_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // the commands share the same state, so the last one pushed must be drawn last
+    let mut batch = DrawBatch::new();
+    batch.push(&vb, &ib, &program, uniform!{ color: [1.0f32, 0.0, 0.0, 1.0] },
+               Default::default());
+    batch.push(&vb, &ib, &program, uniform!{ color: [0.0f32, 1.0, 0.0, 1.0] },
+               Default::default());
+    batch.submit(&mut texture.as_surface()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn submit_ordered_keeps_push_order() {
+    let display = support::build_display();
+
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+    let first = build_color_program(&display);
+    let second = build_color_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+
+    // whatever the addresses of the programs, the last command pushed must be drawn last
+    for &(a, b) in [(&first, &second), (&second, &first)].iter() {
+        texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+        let mut batch = DrawBatch::ordered();
+        batch.push(&vb, &ib, a, uniform!{ color: [1.0f32, 0.0, 0.0, 1.0] }, Default::default());
+        batch.push(&vb, &ib, b, uniform!{ color: [0.0f32, 1.0, 0.0, 1.0] }, Default::default());
+        batch.push(&vb, &ib, a, uniform!{ color: [0.0f32, 0.0, 1.0, 1.0] }, Default::default());
+        batch.submit(&mut texture.as_surface()).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        for row in data.iter() {
+            for pixel in row.iter() {
+                assert_eq!(pixel, &(0, 0, 255, 255));
+            }
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn submit_sorted_groups_same_state() {
+    let display = support::build_display();
+
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+    let first = build_color_program(&display);
+    let second = build_color_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+
+    // pushing A, B, A: sorting groups the commands as either A, A, B or B, A, A depending on
+    // the order of the programs, so trying both assignments must produce each grouping once
+    let mut last_colors = Vec::new();
+    for &(a, b) in [(&first, &second), (&second, &first)].iter() {
+        for &sorted in [true, false].iter() {
+            texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+            let mut batch = DrawBatch::new();
+            batch.set_sorting(sorted);
+            batch.push(&vb, &ib, a, uniform!{ color: [1.0f32, 0.0, 0.0, 1.0] },
+                       Default::default());
+            batch.push(&vb, &ib, b, uniform!{ color: [0.0f32, 1.0, 0.0, 1.0] },
+                       Default::default());
+            batch.push(&vb, &ib, a, uniform!{ color: [0.0f32, 0.0, 1.0, 1.0] },
+                       Default::default());
+            batch.submit(&mut texture.as_surface()).unwrap();
+
+            let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+            if sorted {
+                last_colors.push(data[0][0]);
+            } else {
+                // without sorting, the A, B, A order is kept and the last A is drawn last
+                assert_eq!(data[0][0], (0, 0, 255, 255));
+            }
+        }
+    }
+
+    // A, A, B draws B last, while B, A, A draws the second A last
+    last_colors.sort();
+    assert_eq!(last_colors, vec![(0, 0, 255, 255), (0, 255, 0, 255)]);
+
+    display.assert_no_error(None);
+}
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate glium;
 
-use glium::Surface;
+use glium::{CapabilitiesSource, Surface};
 use glium::index::PrimitiveType;
 
 mod support;
@@ -81,6 +81,84 @@ fn wrong_depth_range() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn srgb_override() {
+    let display = support::build_display();
+
+    if !(display.get_opengl_version() >= &glium::Version(glium::Api::Gl, 3, 0)) &&
+       !display.get_extensions().gl_arb_framebuffer_srgb &&
+       !display.get_extensions().gl_ext_framebuffer_srgb &&
+       !display.get_extensions().gl_ext_srgb_write_control
+    {
+        return;
+    }
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(0.5, 0.5, 0.5, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(0.5, 0.5, 0.5, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = glium::texture::SrgbTexture2d::empty(&display, 64, 64).unwrap();
+
+    let draw = |srgb| {
+        let params = glium::DrawParameters {
+            srgb,
+            .. Default::default()
+        };
+
+        let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture)
+            .unwrap();
+        framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+        framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        data[0][0].0
+    };
+
+    // without conversion the value is written as is, while mid-grey is roughly 188 in sRGB
+    let linear = draw(Some(false));
+    let converted = draw(Some(true));
+    assert!((126..=129).contains(&linear), "{}", linear);
+    assert!((186..=190).contains(&converted), "{}", converted);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn scissor() {
     let display = support::build_display();
@@ -217,6 +295,74 @@ fn viewport() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn inherit_viewport() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        viewport: Some(glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: 1,
+            height: 1,
+        }),
+        .. Default::default()
+    };
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // the viewport of the previous draw is reused
+    let params = glium::DrawParameters {
+        inherit_viewport: true,
+        .. Default::default()
+    };
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data[1][1], (0, 0, 0, 0));
+    assert_eq!(data[512][512], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn scissor_to_viewport() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        viewport: Some(glium::Rect {
+            left: 2,
+            bottom: 2,
+            width: 2,
+            height: 2,
+        }),
+        scissor_to_viewport: true,
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(data[1][1], (0, 0, 0, 0));
+    assert_eq!(data[2][2], (255, 0, 0, 255));
+    assert_eq!(data[3][3], (255, 0, 0, 255));
+    assert_eq!(data[4][4], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn dont_draw_primitives() {
     let display = support::build_display();
@@ -668,14 +814,18 @@ fn cull_counterclockwise_trianglestrip() {
 
 macro_rules! blending_test {
     ($name:ident, $func:expr, $source:expr, $dest:expr, $result:expr) => (
+        blending_test!($name, $func, $func, $source, $dest, $result);
+    );
+
+    ($name:ident, $color:expr, $alpha:expr, $source:expr, $dest:expr, $result:expr) => (
         #[test]
         fn $name() {
             let display = support::build_display();
 
             let params = glium::DrawParameters {
                 blend: glium::Blend {
-                    color: $func,
-                    alpha: $func,
+                    color: $color,
+                    alpha: $alpha,
                     constant_value: (1.0, 1.0, 1.0, 1.0)
                 },
                 .. Default::default()
@@ -765,12 +915,94 @@ blending_test!(min_blending, glium::BlendingFunction::Min,
 blending_test!(max_blending, glium::BlendingFunction::Max,
                (0.4, 1.0, 1.0, 0.2), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 255));
 
+blending_test!(min_color_additive_alpha, glium::BlendingFunction::Min,
+               glium::BlendingFunction::Addition {
+                   source: glium::LinearBlendingFactor::One,
+                   destination: glium::LinearBlendingFactor::One,
+               },
+               (0.0, 0.2, 0.3, 0.0), (1.0, 0.0, 0.0, 1.0), (0, 0, 0, 255));
+
 blending_test!(one_plus_one, glium::BlendingFunction::Addition {
                    source: glium::LinearBlendingFactor::One,
                    destination: glium::LinearBlendingFactor::One,
                },
                (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 255));
 
+#[test]
+fn blend_presets() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // half-transparent red, premultiplied by its alpha
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(0.5, 0.0, 0.0, 0.5);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(0.5, 0.0, 0.0, 0.5);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    let draw = |blend| {
+        let params = glium::DrawParameters {
+            blend,
+            .. Default::default()
+        };
+
+        texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                  &params).unwrap();
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        data[0][0]
+    };
+
+    // the destination is multiplied by one minus the source alpha
+    let (r, g, b, a) = draw(glium::Blend::premultiplied_alpha());
+    assert!((127..=128).contains(&r), "{}", r);
+    assert_eq!(g, 0);
+    assert!((127..=128).contains(&b), "{}", b);
+    assert_eq!(a, 255);
+
+    // the source is added to the destination
+    let (r, g, b, a) = draw(glium::Blend::additive());
+    assert!((127..=128).contains(&r), "{}", r);
+    assert_eq!((g, b, a), (0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
 
 #[test]
 fn provoking_vertex_last() {
@@ -1408,3 +1640,381 @@ fn primitive_restart_index() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn custom_primitive_restart_index() {
+    let display = support::build_display();
+
+    // same as `primitive_restart_index`, but with a sentinel that isn't the max value
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: (f32, f32, f32),
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: (-0.5, -0.5, 0.0) },
+            Vertex { position: ( 0.5, -0.5, 0.0) },
+            Vertex { position: (-0.5,  0.5, 0.0) },
+            Vertex { position: ( 0.5,  0.5, 0.0) },
+        ]).unwrap()
+    };
+
+    let index_buffer = glium::IndexBuffer::<u16>::new(&display,
+                                                      glium::index::PrimitiveType::LineStrip,
+                                                      &[0, 1, 1000, 2, 3]).unwrap();
+    let small_index_buffer = glium::IndexBuffer::<u8>::new(&display,
+                                                           glium::index::PrimitiveType::LineStrip,
+                                                           &[0, 1, 2, 3]).unwrap();
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 1.0, 1.0, 1.0);
+
+    let options = glium::DrawParameters {
+        custom_primitive_restart_index: Some(1000),
+        .. Default::default()
+    };
+
+    // 1000 doesn't fit in a `u8`
+    match texture.as_surface().draw(&vertex_buffer, &small_index_buffer, &program,
+                                    &glium::uniforms::EmptyUniforms, &options)
+    {
+        Err(glium::DrawError::PrimitiveRestartIndexOutOfRange) => (),
+        e => panic!("{:?}", e),
+    }
+
+    match texture.as_surface().draw(&vertex_buffer, &index_buffer, &program,
+                                    &glium::uniforms::EmptyUniforms, &options)
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::CustomIndexRestartingNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    let mid_x = texture.get_width() as usize / 2;
+    let mid_y = texture.get_height().unwrap() as usize / 2;
+
+    for row in (mid_y - 2)..(mid_y + 2) {
+        for pixel in (mid_x - 2)..(mid_x + 2) {
+            assert_eq!(data[row][pixel], (255, 255, 255, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn back_polygon_mode() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        polygon_mode: glium::PolygonMode::Fill,
+        back_polygon_mode: Some(glium::PolygonMode::Line),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    // falls back to the front mode on contexts that don't support per-face modes
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn wireframe_overlay() {
+    use glium::wireframe::WireframeOverlay;
+
+    let display = support::build_display();
+    if let glium::Version(glium::Api::GlEs, _, _) = *display.get_opengl_version() {
+        return;
+    }
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform vec4 wireframe_color;
+
+                void main() {
+                    vec4 color = vec4(1.0, 0.0, 0.0, 1.0);
+                    gl_FragColor = mix(color, vec4(wireframe_color.rgb, 1.0), wireframe_color.a);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_wireframe_overlay(&vb, &ib, &program,
+                                                &glium::uniforms::EmptyUniforms,
+                                                [0.0, 1.0, 0.0, 1.0], &Default::default())
+                        .unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    // the inside of the triangles is filled
+    assert_eq!(data[data.len() / 4][data[0].len() * 3 / 4], (255, 0, 0, 255));
+    assert_eq!(data[data.len() * 3 / 4][data[0].len() / 4], (255, 0, 0, 255));
+
+    // the diagonal shared by the two triangles is drawn with the line color
+    let row = &data[data.len() / 2];
+    assert!(row.iter().any(|pixel| *pixel == (0, 255, 0, 255)));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn conservative_rasterization() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        conservative_rasterization: true,
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let res = texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                        &params);
+
+    if display.get_capabilities().conservative_rasterization {
+        res.unwrap();
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        assert_eq!(data[0][0], (255, 0, 0, 255));
+    } else {
+        match res {
+            Err(glium::DrawError::ConservativeRasterizationNotSupported) => (),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn default_tessellation_levels() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        default_tessellation_levels: glium::draw_parameters::TessellationLevels {
+            outer: [4.0, 4.0, 4.0, 4.0],
+            inner: [2.0, 2.0],
+        },
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Ok(_) | Err(glium::DrawError::TessellationNotSupported) => (),
+        e => e.unwrap(),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn infinite_projection_reverse_z() {
+    let display = support::build_display();
+
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: [-1.0, 1.0] },
+            Vertex { position: [1.0, 1.0] },
+            Vertex { position: [-1.0, -1.0] },
+            Vertex { position: [1.0, -1.0] },
+        ]).unwrap()
+    };
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 140
+
+            uniform mat4 projection;
+            uniform float distance;
+            in vec2 position;
+
+            void main() {
+                // a quad large enough to cover the whole viewport at this distance
+                gl_Position = projection * vec4(position * distance * 2.0, -distance, 1.0);
+            }
+        ",
+        "
+            #version 140
+
+            uniform vec4 quad_color;
+            out vec4 color;
+
+            void main() {
+                color = quad_color;
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    let depth = match glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                glium::texture::DepthFormat::F32, 1024, 1024)
+    {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                  &texture, &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 0.0);
+
+    let projection = glium::matrix::perspective_infinite_reverse_z(
+                                                    std::f32::consts::FRAC_PI_2, 1.0, 0.1);
+
+    let params = glium::DrawParameters {
+        depth: glium::Depth {
+            test: glium::DepthTest::IfMore,
+            write: true,
+            .. Default::default()
+        },
+        clip_control_depth: glium::draw_parameters::ClipControlDepth::ZeroToOne,
+        .. Default::default()
+    };
+
+    // the closest quad is drawn first and must not be overwritten by the farthest one, even
+    // though they are extremely far away and very close to each other
+    let res = framebuffer.draw(&vertex_buffer,
+                               &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                               &program,
+                               &uniform!{ projection: projection, distance: 1.0e6f32,
+                                          quad_color: [1.0, 0.0, 0.0, 1.0f32] },
+                               &params);
+
+    match res {
+        Ok(_) => (),
+        Err(glium::DrawError::ClipControlNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    framebuffer.draw(&vertex_buffer,
+                     &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                     &program,
+                     &uniform!{ projection: projection, distance: 1.001e6f32,
+                                quad_color: [0.0, 1.0, 0.0, 1.0f32] },
+                     &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    // geometry at an extreme distance is not clipped by the far plane
+    framebuffer.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 0.0);
+    framebuffer.draw(&vertex_buffer,
+                     &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                     &program,
+                     &uniform!{ projection: projection, distance: 1.0e30f32,
+                                quad_color: [0.0, 0.0, 1.0, 1.0f32] },
+                     &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 255, 255));
+    assert_eq!(data[512][512], (0, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
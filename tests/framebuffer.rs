@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate glium;
 
-use glium::Surface;
+use glium::{CapabilitiesSource, Surface};
 
 mod support;
 
@@ -254,6 +254,93 @@ fn multioutput() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn multioutput_blend_per_buffer() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 1.0, 1.0);
+                color2 = vec4(1.0, 0.0, 1.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color1.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color2.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    let blends = [
+        glium::Blend {
+            color: glium::BlendingFunction::Addition {
+                source: glium::LinearBlendingFactor::One,
+                destination: glium::LinearBlendingFactor::One,
+            },
+            alpha: glium::BlendingFunction::Addition {
+                source: glium::LinearBlendingFactor::One,
+                destination: glium::LinearBlendingFactor::One,
+            },
+            .. Default::default()
+        },
+        glium::Blend {
+            color: glium::BlendingFunction::Min,
+            alpha: glium::BlendingFunction::Min,
+            .. Default::default()
+        },
+    ];
+
+    let params = glium::DrawParameters {
+        blend_per_buffer: Some(&blends),
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::IndexedBlendingNotSupported) => return,
+        res => res.unwrap()
+    };
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[64][64], (255, 0, 255, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[64][64], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn array_level() {
     let display = support::build_display();
@@ -304,6 +391,30 @@ fn cubemap_layer() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn depth_cubemap_array_face_attachment() {
+    let display = support::build_display();
+
+    if !glium::texture::is_cubemap_arrays_supported(&display) {
+        return;
+    }
+
+    let texture = match glium::texture::DepthCubemapArray::empty(&display, 64, 3) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+    assert_eq!(texture.array_size(), 3);
+
+    let image = texture.main_level().layer(2).unwrap()
+                       .image(glium::texture::CubeLayer::NegativeZ);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::depth_only(&display, image)
+                                                                               .unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (64, 64));
+    framebuffer.clear_depth(0.5);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 #[should_panic]
 fn multi_color_attachments_maximum() {
@@ -447,3 +558,273 @@ fn empty_framebuffer_simple_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn clear_color_and_depth() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                           glium::texture::DepthFormat::I24,
+                                                           1024, 1024).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                  &texture, &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 1.0, 0.0, 1.0), 1.0);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn ping_pong() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let mut ping_pong = glium::framebuffer::PingPong::new(&display, 64, 64).unwrap();
+    assert_eq!(ping_pong.dimensions(), (64, 64));
+
+    let first = ping_pong.pass(|_, output| {
+        output.clear_color(0.0, 0.0, 1.0, 1.0);
+        output.get_dimensions()
+    }).unwrap();
+    assert_eq!(first, (64, 64));
+
+    // the second pass reads the result of the first one and draws into the other texture
+    ping_pong.pass(|input, output| {
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = input.read();
+        assert_eq!(data[0][0], (0, 0, 255, 255));
+
+        output.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                    &Default::default()).unwrap();
+    }).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = ping_pong.result().read();
+    assert_eq!(data[32][32], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_color_masked() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 1.0, 1.0, 1.0);
+    texture.as_surface().clear_color_masked(0.0, 0.0, 0.0, 0.0, (true, false, true, false))
+                        .unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 255, 0, 255));
+
+    // a regular clear writes all the components again
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn render_buffer_dimensions_too_large() {
+    let display = support::build_display();
+
+    let max = display.get_capabilities().max_renderbuffer_size.unwrap_or(0) as u32;
+
+    match glium::framebuffer::RenderBuffer::new(&display,
+                                                glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                max + 1, 16)
+    {
+        Err(glium::framebuffer::RenderBufferCreationError::DimensionsNotSupported) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn render_buffer_zero_dimensions() {
+    let display = support::build_display();
+
+    let buffer = glium::framebuffer::RenderBuffer::new(&display,
+                                                       glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                       0, 0).unwrap();
+    assert_eq!(buffer.get_dimensions(), (0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_render_buffers_blit() {
+    let display = support::build_display();
+
+    let color = match glium::framebuffer::RenderBuffer::new_multisample(&display,
+                                                glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                64, 64, 4)
+    {
+        Ok(b) => b,
+        Err(glium::framebuffer::RenderBufferCreationError::SamplesNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+    let depth = glium::framebuffer::DepthRenderBuffer::new_multisample(&display,
+                                                glium::texture::DepthFormat::I24, 64, 64, 4)
+                                                .unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                  &color, &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 1.0, 0.0, 1.0), 1.0);
+
+    let texture = support::build_renderable_texture(&display);
+    framebuffer.fill(&texture.as_surface(), glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn coverage_render_buffers_blit() {
+    let display = support::build_display();
+
+    let color = match glium::framebuffer::RenderBuffer::new_multisample_coverage(&display,
+                                                glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                                64, 64, 8, 4)
+    {
+        Ok(b) => b,
+        Err(glium::framebuffer::RenderBufferCreationError::CoverageSamplingNotSupported) => {
+            assert!(!display.has_extension("GL_NV_framebuffer_multisample_coverage"));
+            return;
+        },
+        Err(glium::framebuffer::RenderBufferCreationError::CoverageModeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+    assert_eq!(color.get_samples(), Some(4));
+    assert_eq!(color.get_coverage_samples(), Some(8));
+
+    let depth = glium::framebuffer::DepthRenderBuffer::new_multisample_coverage(&display,
+                                                glium::texture::DepthFormat::I24, 64, 64, 8, 4)
+                                                .unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                  &color, &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 1.0, 0.0, 1.0), 1.0);
+
+    let texture = support::build_renderable_texture(&display);
+    framebuffer.fill(&texture.as_surface(), glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalidate_depth() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                           glium::texture::DepthFormat::I24,
+                                                           1024, 1024).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                  &texture, &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 1.0, 0.0, 1.0), 1.0);
+    framebuffer.invalidate(&[glium::framebuffer::Attachment::Depth]);
+    framebuffer.invalidate_rect(&[glium::framebuffer::Attachment::Depth],
+                                &glium::Rect { left: 0, bottom: 0, width: 2, height: 2 });
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn render_to_texture_level() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                            glium::texture::MipmapsOption::EmptyMipmaps, 64, 64)
+                                            .unwrap();
+
+    {
+        let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_texture_level(&display,
+                                                                            &texture, 2).unwrap();
+        assert_eq!(framebuffer.get_dimensions(), (16, 16));
+        framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+    }
+
+    let level: glium::texture::TextureAnyImage = texture.mipmap(2).unwrap().into();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = level.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 16, height: 16
+    });
+    assert_eq!(data[15][15], (255, 0, 0, 255));
+
+    match glium::framebuffer::SimpleFrameBuffer::with_texture_level(&display, &texture, 7) {
+        Err(glium::framebuffer::ValidationError::MipmapLevelOutOfRange) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_stencil() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty(&display, 16, 16).unwrap();
+    let depth_stencil = glium::framebuffer::DepthStencilRenderBuffer::new(&display,
+                                                glium::texture::DepthStencilFormat::I24I8,
+                                                16, 16).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(&display,
+                                                        &texture, &depth_stencil).unwrap();
+    framebuffer.clear_stencil(0);
+    framebuffer.clear(Some(&glium::Rect { left: 0, bottom: 0, width: 8, height: 4 }), None, false,
+                      None, Some(7));
+
+    let data = match framebuffer.read_stencil(&glium::Rect { left: 0, bottom: 0, width: 16,
+                                                             height: 16 })
+    {
+        Err(glium::ReadError::AttachmentTypeNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data.len(), 16 * 16);
+    assert_eq!(data[0], 7);
+    assert_eq!(data[3 * 16 + 7], 7);
+    assert_eq!(data[3 * 16 + 8], 0);
+    assert_eq!(data[4 * 16], 0);
+
+    let empty = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    match empty.read_stencil(&glium::Rect { left: 0, bottom: 0, width: 1, height: 1 }) {
+        Err(glium::ReadError::NoStencilBuffer) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_invalidate_depth_stencil() {
+    let display = support::build_display();
+
+    let frame = display.draw();
+    frame.invalidate_depth_stencil();
+    frame.finish().unwrap();
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,89 @@
+#![cfg(target_os = "linux")]
+
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::backend::glutin::Headless;
+use glutin::api::egl::device::Device;
+use glutin::api::egl::display::Display;
+use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
+use glutin::context::{ContextApi, ContextAttributesBuilder, PossiblyCurrentContext};
+use glutin::prelude::*;
+
+mod support;
+
+/// Builds a headless context on top of the first EGL device.
+fn build_headless(dimensions: (u32, u32)) -> Headless {
+    let device = Device::query_devices().unwrap().next().expect("no EGL device available");
+    let display = unsafe { Display::with_device(&device, None) }.unwrap();
+
+    let template = ConfigTemplateBuilder::new()
+        .with_alpha_size(8)
+        .with_surface_type(ConfigSurfaceTypes::empty())
+        .build();
+    let config = unsafe { display.find_configs(template) }.unwrap().next().unwrap();
+
+    let context_attributes = ContextAttributesBuilder::new().build(None);
+    let fallback_context_attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::Gles(None))
+        .build(None);
+    let not_current_context = unsafe {
+        display.create_context(&config, &context_attributes).unwrap_or_else(|_| {
+            display.create_context(&config, &fallback_context_attributes).unwrap()
+        })
+    };
+    let context = not_current_context.make_current_surfaceless().unwrap();
+
+    Headless::new(PossiblyCurrentContext::Egl(context), dimensions).unwrap()
+}
+
+#[test]
+fn headless_draw_and_read() {
+    let headless = build_headless((256, 256));
+    assert_eq!(headless.get_framebuffer_dimensions(), (256, 256));
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&headless);
+
+    let mut target = headless.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = headless.read();
+    assert_eq!(data.len(), 256);
+    for row in data.iter() {
+        assert_eq!(row.len(), 256);
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    headless.assert_no_error(None);
+}
+
+#[test]
+fn headless_resize() {
+    let mut headless = build_headless((256, 256));
+    headless.resize((128, 64)).unwrap();
+    assert_eq!(headless.get_framebuffer_dimensions(), (128, 64));
+    assert_eq!(headless.draw().get_dimensions(), (128, 64));
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&headless);
+
+    let mut target = headless.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = headless.read();
+    assert_eq!(data.len(), 64);
+    for row in data.iter() {
+        assert_eq!(row.len(), 128);
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    headless.assert_no_error(None);
+}
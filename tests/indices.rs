@@ -106,6 +106,36 @@ fn triangle_strip() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn indices_from_buffer_slice() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    // a regular buffer that could have been written by a compute shader
+    let buffer = glium::buffer::Buffer::new(&display, &[0u16, 1, 2, 3][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+    let indices = index::IndexBufferSlice::from_buffer_slice(buffer.as_slice(),
+                                                             PrimitiveType::TriangleStrip).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn triangle_fan() {
     let display = support::build_display();
@@ -412,3 +442,168 @@ fn multidraw_elements() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn indexbuffer_from_iter() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::from_iter(&display, PrimitiveType::TrianglesList,
+                                                (0 .. 6u16).map(|i| i * 2)).unwrap();
+    assert_eq!(indices.len(), 6);
+
+    let data = match indices.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, vec![0, 2, 4, 6, 8, 10]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn indexbuffer_from_iter_wrong_length() {
+    // an iterator whose `len()` doesn't match the number of elements it produces
+    struct WrongLength(std::ops::Range<u16>, usize);
+
+    impl Iterator for WrongLength {
+        type Item = u16;
+        fn next(&mut self) -> Option<u16> { self.0.next() }
+        fn size_hint(&self) -> (usize, Option<usize>) { (self.1, Some(self.1)) }
+    }
+
+    impl ExactSizeIterator for WrongLength {}
+
+    let display = support::build_display();
+
+    match glium::IndexBuffer::from_iter(&display, PrimitiveType::TrianglesList,
+                                        WrongLength(0 .. 9, 6))
+    {
+        Err(index::FromIterError::LengthMismatch) => (),
+        e => panic!("{:?}", e)
+    };
+
+    match glium::IndexBuffer::from_iter(&display, PrimitiveType::TrianglesList,
+                                        WrongLength(0 .. 3, 6))
+    {
+        Err(index::FromIterError::LengthMismatch) => (),
+        e => panic!("{:?}", e)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn indexbuffer_write_at() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2, 3, 4, 5]).unwrap();
+    indices.write_at(2, &[7, 8]);
+
+    let data = match indices.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, vec![0, 1, 7, 8, 4, 5]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn indexbuffer_write_at_out_of_range() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2]).unwrap();
+    indices.write_at(2, &[7, 8]);
+}
+
+#[test]
+fn adjacency_wrong_indices_count() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    if !PrimitiveType::TrianglesListAdjacency.is_supported(&display) {
+        return;
+    }
+
+    let (vb, _) = support::build_rectangle_vb_ib(&display);
+
+    // 4 indices don't form a list of triangles with adjacency
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesListAdjacency,
+                                          &[0u16, 1, 2, 3]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &Default::default()) {
+        Err(glium::DrawError::InvalidAdjacencyIndicesCount) => (),
+        a => panic!("{:?}", a)
+    };
+
+    match texture.as_surface().draw(&vb, &index::NoIndices(PrimitiveType::TrianglesListAdjacency),
+                                    &program, &uniform!{}, &Default::default())
+    {
+        Err(glium::DrawError::InvalidAdjacencyIndicesCount) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn adjacency_geometry_shader_input_mismatch() {
+    let display = support::build_display();
+
+    if !glium::program::is_geometry_shader_supported(&display) {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        Some("
+            #version 330
+
+            layout(triangles) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; i++) {
+                    gl_Position = gl_in[i].gl_Position;
+                    EmitVertex();
+                }
+            }
+        ")).unwrap();
+
+    let (vb, _) = support::build_rectangle_vb_ib(&display);
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesListAdjacency,
+                                          &[0u16, 1, 2, 1, 2, 3]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &Default::default()) {
+        Err(glium::DrawError::GeometryShaderInputMismatch) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
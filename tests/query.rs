@@ -65,6 +65,40 @@ fn samples_passed() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn samples_passed_try_get() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::SamplesPassedQuery::new(&display) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    // the query hasn't been used yet
+    assert_eq!(query.try_get(), None);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let params = glium::DrawParameters {
+            samples_passed_query: Some((&query).into()),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    display.finish();
+
+    assert_eq!(query.try_get(), Some(1024 * 1024));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn any_samples_passed() {
     let display = support::build_display();
@@ -159,6 +193,41 @@ fn time_elapsed() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn time_elapsed_try_get() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::TimeElapsedQuery::new(&display) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    // the query hasn't been used yet
+    assert_eq!(query.try_get(), None);
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let params = glium::DrawParameters {
+            time_elapsed_query: Some(&query),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    display.finish();
+
+    let result = query.try_get().unwrap();
+    assert!(result >= 1);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 #[ignore]       // not sure about the interaction between primitives_generated and no geometry shader
 fn primitives_generated() {
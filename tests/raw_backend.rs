@@ -0,0 +1,408 @@
+#[macro_use]
+extern crate glium;
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::rc::Rc;
+
+use glium::backend::Backend;
+use glium::backend::Context;
+use glium::backend::raw::RawBackend;
+use glium::debug::DebugCallbackBehavior;
+use glium::{Api, CapabilitiesSource, Frame, Program, Surface, Version};
+use glium::index::{NoIndices, PrimitiveType};
+use glium::texture::Texture2d;
+use glium::uniforms::SamplerWrapFunction;
+use glium::vertex::EmptyVertexAttributes;
+
+// a fake OpenGL ES 2.0 implementation that only supports the functions called when creating
+// and destroying a context
+
+const VERSION: u32 = 0x1F02;
+const VENDOR: u32 = 0x1F00;
+const RENDERER: u32 = 0x1F01;
+const EXTENSIONS: u32 = 0x1F03;
+const SHADING_LANGUAGE_VERSION: u32 = 0x8B8C;
+
+extern "system" fn get_string(name: u32) -> *const u8 {
+    let value: &'static [u8] = match name {
+        VERSION => b"OpenGL ES 2.0 Mock\0",
+        VENDOR => b"glium\0",
+        RENDERER => b"mock renderer\0",
+        EXTENSIONS => b"\0",
+        SHADING_LANGUAGE_VERSION => b"OpenGL ES GLSL ES 1.00\0",
+        _ => return ptr::null(),
+    };
+
+    value.as_ptr()
+}
+
+const MAX_VIEWPORT_DIMS: u32 = 0x0D3A;
+
+extern "system" fn get_integerv(name: u32, value: *mut i32) {
+    unsafe {
+        *value = 1;
+        if name == MAX_VIEWPORT_DIMS {
+            *value.add(1) = 1;
+        }
+    }
+}
+
+extern "system" fn get_booleanv(_: u32, value: *mut u8) {
+    unsafe { *value = 1; }
+}
+
+extern "system" fn get_float_v(_: u32, value: *mut f32) {
+    unsafe { *value = 1.0; }
+}
+
+extern "system" fn get_error() -> u32 {
+    0
+}
+
+extern "system" fn bind_framebuffer(_: u32, _: u32) {
+}
+
+extern "system" fn no_op() {
+}
+
+thread_local! {
+    static DEPTH_RANGE: Cell<(f32, f32)> = Cell::new((0.0, 1.0));
+}
+
+extern "system" fn depth_range_f(near: f32, far: f32) {
+    DEPTH_RANGE.with(|r| r.set((near, far)));
+}
+
+fn mock_functions() -> HashMap<&'static str, *const c_void> {
+    let mut functions = HashMap::new();
+    functions.insert("glGetString", get_string as *const c_void);
+    functions.insert("glGetIntegerv", get_integerv as *const c_void);
+    functions.insert("glGetBooleanv", get_booleanv as *const c_void);
+    functions.insert("glGetFloatv", get_float_v as *const c_void);
+    functions.insert("glGetError", get_error as *const c_void);
+    functions.insert("glBindFramebuffer", bind_framebuffer as *const c_void);
+    functions.insert("glFinish", no_op as *const c_void);
+    functions.insert("glFlush", no_op as *const c_void);
+    functions.insert("glDepthRangef", depth_range_f as *const c_void);
+    functions
+}
+
+fn build_backend(dimensions: Rc<Cell<(u32, u32)>>, loaded: Rc<Cell<usize>>) -> RawBackend {
+    let functions = mock_functions();
+    let resize_dimensions = dimensions.clone();
+
+    unsafe {
+        RawBackend::new(move |symbol| {
+            loaded.set(loaded.get() + 1);
+            functions.get(symbol).cloned().unwrap_or(ptr::null())
+        }, move || dimensions.get(), move |size| resize_dimensions.set(size), || true)
+    }
+}
+
+#[test]
+fn context_from_mock_loader() {
+    let dimensions = Rc::new(Cell::new((640, 480)));
+    let loaded = Rc::new(Cell::new(0));
+    let backend = build_backend(dimensions.clone(), loaded.clone());
+
+    let context = unsafe {
+        Context::new(backend, true, DebugCallbackBehavior::Ignore)
+    }.unwrap();
+
+    assert!(loaded.get() > 0);
+    assert_eq!(*context.get_opengl_version(), Version(Api::GlEs, 2, 0));
+    assert_eq!(context.get_opengl_renderer_string(), "mock renderer");
+    assert_eq!(context.get_framebuffer_dimensions(), (640, 480));
+
+    dimensions.set((800, 600));
+    assert_eq!(context.get_framebuffer_dimensions(), (800, 600));
+}
+
+#[test]
+fn raw_backend_callbacks() {
+    let dimensions = Rc::new(Cell::new((640, 480)));
+    let loaded = Rc::new(Cell::new(0));
+    let swapped = Rc::new(Cell::new(false));
+    let swapped2 = swapped.clone();
+
+    let backend = build_backend(dimensions.clone(), loaded.clone())
+        .with_swap_buffers(move || { swapped2.set(true); Ok(()) });
+
+    backend.resize((320, 200));
+    assert_eq!(dimensions.get(), (320, 200));
+    assert_eq!(backend.get_framebuffer_dimensions(), (320, 200));
+    assert!(backend.is_current());
+
+    backend.swap_buffers().unwrap();
+    assert!(swapped.get());
+
+    assert!(!unsafe { backend.get_proc_address("glGetError") }.is_null());
+    assert!(unsafe { backend.get_proc_address("glDrawArrays") }.is_null());
+    assert_eq!(loaded.get(), 2);
+}
+
+#[test]
+fn is_current_is_cached_after_assume_current() {
+    let functions = mock_functions();
+    let calls = Rc::new(Cell::new(0));
+    let calls2 = calls.clone();
+
+    let backend = unsafe {
+        RawBackend::new(move |symbol| functions.get(symbol).cloned().unwrap_or(ptr::null()),
+                        || (640, 480), |_| {}, move || { calls2.set(calls2.get() + 1); true })
+    };
+
+    let context = unsafe {
+        Context::new(backend, true, DebugCallbackBehavior::Ignore)
+    }.unwrap();
+
+    // the backend is asked before each operation by default
+    calls.set(0);
+    for _ in 0 .. 10 {
+        context.is_context_lost();
+    }
+    assert_eq!(calls.get(), 10);
+
+    calls.set(0);
+    unsafe { context.assume_current(); }
+    for _ in 0 .. 10 {
+        context.is_context_lost();
+    }
+    assert_eq!(calls.get(), 0);
+
+    // swapping buffers asks the backend again, and so do the next operations
+    context.swap_buffers().unwrap();
+    calls.set(0);
+    context.is_context_lost();
+    context.is_context_lost();
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn lazy_capabilities() {
+    let dimensions = Rc::new(Cell::new((640, 480)));
+    let eager = unsafe {
+        Context::new(build_backend(dimensions.clone(), Rc::new(Cell::new(0))), true,
+                     DebugCallbackBehavior::Ignore)
+    }.unwrap();
+    let lazy = unsafe {
+        Context::new_lazy(build_backend(dimensions, Rc::new(Cell::new(0))), true,
+                          DebugCallbackBehavior::Ignore)
+    }.unwrap();
+
+    assert_eq!(lazy.get_opengl_renderer_string(), eager.get_opengl_renderer_string());
+    assert_eq!(lazy.get_capabilities().max_texture_size,
+               eager.get_capabilities().max_texture_size);
+    assert_eq!(lazy.get_capabilities().depth_bits, eager.get_capabilities().depth_bits);
+}
+
+#[test]
+fn depth_range_on_gles() {
+    let backend = build_backend(Rc::new(Cell::new((640, 480))), Rc::new(Cell::new(0)));
+    let context = unsafe {
+        Context::new(backend, true, DebugCallbackBehavior::Ignore)
+    }.unwrap();
+
+    // the mock doesn't provide `glDepthRange`, so this would crash if it was called
+    context.set_depth_range(0.25, 0.75);
+    assert_eq!(DEPTH_RANGE.with(|r| r.get()), (0.25, 0.75));
+
+    context.set_depth_range(-1.0, 2.0);
+    assert_eq!(DEPTH_RANGE.with(|r| r.get()), (0.0, 1.0));
+}
+
+// a fake OpenGL 2.1 implementation, which doesn't support sampler objects, with the functions
+// required to draw with a texture
+extern "system" fn get_string_gl21(name: u32) -> *const u8 {
+    let value: &'static [u8] = match name {
+        VERSION => b"2.1 Mock\0",
+        EXTENSIONS => b"GL_EXT_framebuffer_object GL_EXT_framebuffer_blit\0",
+        SHADING_LANGUAGE_VERSION => b"1.20\0",
+        _ => return get_string(name),
+    };
+
+    value.as_ptr()
+}
+
+const TEXTURE_WRAP_S: u32 = 0x2802;
+const CLAMP_TO_EDGE: i32 = 0x812F;
+const TEXTURE_COMPARE_MODE: u32 = 0x884C;
+const NONE: i32 = 0;
+
+const COMPILE_STATUS: u32 = 0x8B81;
+const LINK_STATUS: u32 = 0x8B82;
+const ACTIVE_UNIFORMS: u32 = 0x8B86;
+const SAMPLER_2D: u32 = 0x8B5E;
+
+thread_local! {
+    static TEX_PARAMETERS: RefCell<Vec<(u32, i32)>> = RefCell::new(Vec::new());
+}
+
+extern "system" fn tex_parameter_i(_: u32, pname: u32, param: i32) {
+    TEX_PARAMETERS.with(|p| p.borrow_mut().push((pname, param)));
+}
+
+extern "system" fn gen_objects(n: i32, ids: *mut u32) {
+    for i in 0 .. n as usize {
+        unsafe { *ids.add(i) = i as u32 + 1; }
+    }
+}
+
+extern "system" fn delete_objects(_: i32, _: *const u32) {
+}
+
+extern "system" fn tex_image_2d(_: u32, _: i32, _: i32, _: i32, _: i32, _: i32, _: u32, _: u32,
+                                _: *const c_void) {
+}
+
+extern "system" fn create_object(_: u32) -> u32 {
+    1
+}
+
+extern "system" fn create_program() -> u32 {
+    1
+}
+
+extern "system" fn shader_source(_: u32, _: i32, _: *const *const c_char, _: *const i32) {
+}
+
+extern "system" fn get_shader_or_program_iv(_: u32, pname: u32, value: *mut i32) {
+    let result = match pname {
+        COMPILE_STATUS | LINK_STATUS | ACTIVE_UNIFORMS => 1,
+        _ => 0,
+    };
+
+    unsafe { *value = result; }
+}
+
+// the program has a single `sampler2D` uniform named `tex`
+extern "system" fn get_active_uniform(_: u32, _: u32, _: i32, length: *mut i32, size: *mut i32,
+                                      ty: *mut u32, name: *mut c_char) {
+    unsafe {
+        ptr::copy_nonoverlapping(b"tex".as_ptr() as *const c_char, name, 3);
+        *length = 3;
+        *size = 1;
+        *ty = SAMPLER_2D;
+    }
+}
+
+extern "system" fn get_uniform_location(_: u32, _: *const c_char) -> i32 {
+    0
+}
+
+extern "system" fn no_op_u32(_: u32) {
+}
+
+extern "system" fn no_op_u32_u32(_: u32, _: u32) {
+}
+
+extern "system" fn no_op_u32_i32(_: u32, _: i32) {
+}
+
+extern "system" fn no_op_i32_i32(_: i32, _: i32) {
+}
+
+extern "system" fn no_op_i32_4(_: i32, _: i32, _: i32, _: i32) {
+}
+
+extern "system" fn no_op_bool_4(_: u8, _: u8, _: u8, _: u8) {
+}
+
+extern "system" fn no_op_f64_f64(_: f64, _: f64) {
+}
+
+extern "system" fn no_op_u32_i32_i32(_: u32, _: i32, _: i32) {
+}
+
+fn mock_gl21_functions() -> HashMap<&'static str, *const c_void> {
+    let mut functions = mock_functions();
+    functions.insert("glGetString", get_string_gl21 as *const c_void);
+    functions.insert("glBindFramebufferEXT", bind_framebuffer as *const c_void);
+
+    functions.insert("glPixelStorei", no_op_u32_i32 as *const c_void);
+    functions.insert("glGenTextures", gen_objects as *const c_void);
+    functions.insert("glDeleteTextures", delete_objects as *const c_void);
+    functions.insert("glBindTexture", no_op_u32_u32 as *const c_void);
+    functions.insert("glActiveTexture", no_op_u32 as *const c_void);
+    functions.insert("glTexParameteri", tex_parameter_i as *const c_void);
+    functions.insert("glTexImage2D", tex_image_2d as *const c_void);
+
+    functions.insert("glCreateShader", create_object as *const c_void);
+    functions.insert("glShaderSource", shader_source as *const c_void);
+    functions.insert("glCompileShader", no_op_u32 as *const c_void);
+    functions.insert("glGetShaderiv", get_shader_or_program_iv as *const c_void);
+    functions.insert("glDeleteShader", no_op_u32 as *const c_void);
+    functions.insert("glCreateProgram", create_program as *const c_void);
+    functions.insert("glAttachShader", no_op_u32_u32 as *const c_void);
+    functions.insert("glLinkProgram", no_op_u32 as *const c_void);
+    functions.insert("glGetProgramiv", get_shader_or_program_iv as *const c_void);
+    functions.insert("glGetActiveUniform", get_active_uniform as *const c_void);
+    functions.insert("glGetUniformLocation", get_uniform_location as *const c_void);
+    functions.insert("glUseProgram", no_op_u32 as *const c_void);
+    functions.insert("glDeleteProgram", no_op_u32 as *const c_void);
+    functions.insert("glUniform1i", no_op_i32_i32 as *const c_void);
+
+    functions.insert("glEnable", no_op_u32 as *const c_void);
+    functions.insert("glDisable", no_op_u32 as *const c_void);
+    functions.insert("glViewport", no_op_i32_4 as *const c_void);
+    functions.insert("glScissor", no_op_i32_4 as *const c_void);
+    functions.insert("glColorMask", no_op_bool_4 as *const c_void);
+    functions.insert("glPolygonMode", no_op_u32_u32 as *const c_void);
+    functions.insert("glDepthRange", no_op_f64_f64 as *const c_void);
+    functions.insert("glDrawArrays", no_op_u32_i32_i32 as *const c_void);
+    functions
+}
+
+#[test]
+fn texture_parameters_without_sampler_objects() {
+    let functions = mock_gl21_functions();
+    let backend = unsafe {
+        RawBackend::new(move |symbol| functions.get(symbol).cloned().unwrap_or(ptr::null()),
+                        || (1, 1), |_| {}, || true)
+    };
+
+    let context = unsafe {
+        Context::new(backend, true, DebugCallbackBehavior::Ignore)
+    }.unwrap();
+    assert_eq!(*context.get_opengl_version(), Version(Api::Gl, 2, 1));
+
+    let texture = Texture2d::empty(&context, 1, 1).unwrap();
+
+    let program = Program::from_source(&context,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D tex;
+
+            void main() {
+                gl_FragColor = texture2D(tex, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    TEX_PARAMETERS.with(|p| p.borrow_mut().clear());
+
+    let mut frame = Frame::new(context.clone(), (1, 1));
+    frame.draw(EmptyVertexAttributes { len: 1 }, NoIndices(PrimitiveType::Points), &program,
+               &uniform!{
+                   tex: texture.sampled().wrap_function(SamplerWrapFunction::Clamp)
+               },
+               &Default::default()).unwrap();
+    frame.finish().unwrap();
+
+    // the sampling parameters are set on the texture itself, and a comparison that may have been
+    // enabled by a previous draw is disabled
+    let parameters = TEX_PARAMETERS.with(|p| p.borrow().clone());
+    assert!(parameters.contains(&(TEXTURE_WRAP_S, CLAMP_TO_EDGE)));
+    assert!(parameters.contains(&(TEXTURE_COMPARE_MODE, NONE)));
+}
@@ -79,3 +79,112 @@ fn magnify_nearest_filtering() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn same_texture_different_filtering() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D texture;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, vec2(0.51, 0.0));
+                }
+            ",
+        }).unwrap();
+
+    let texture_data = vec![vec![(0u8, 0, 0), (255, 255, 255)]];
+    let texture = glium::texture::Texture2d::new(&display, texture_data).unwrap();
+
+    let nearest = support::build_renderable_texture(&display);
+    let linear = support::build_renderable_texture(&display);
+
+    // the same texture is used by both draws, only the sampling parameters change
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+    };
+    nearest.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let uniforms = uniform! {
+        texture: texture.sampled()
+                        .minify_filter(glium::uniforms::MinifySamplerFilter::Linear)
+                        .magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear)
+    };
+    linear.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = nearest.read();
+    assert_eq!(data[0][0], (255, 255, 255, 255));
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = linear.read();
+    assert!(data[0][0].0 > 64 && data[0][0].0 < 192);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn anisotropic_filtering() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                varying vec2 v_tex_coords;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    v_tex_coords = position * 0.5 + 0.5;
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D texture;
+                varying vec2 v_tex_coords;
+
+                void main() {
+                    gl_FragColor = texture2D(texture, v_tex_coords);
+                }
+            ",
+        }).unwrap();
+
+    if let Some(max) = display.get_max_anisotropy_support() {
+        assert!(max >= 1);
+    }
+
+    let texture = support::build_unicolor_texture2d(&display, 0.0, 1.0, 0.0);
+
+    // values above the hardware maximum are clamped, and ignored if unsupported
+    let uniforms = uniform! {
+        texture: texture.sampled().anisotropy(64)
+    };
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
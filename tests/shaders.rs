@@ -154,6 +154,61 @@ fn get_frag_data_location() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn get_block_members() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+
+            layout(std140) uniform MyBlock {
+                mat4 transform;
+                float weights[4];
+            };
+
+            void main() {
+                gl_Position = transform * vec4(weights[1]);
+            }
+        ",
+        "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert!(program.get_block_members("NotABlock").is_none());
+
+    let mut members = program.get_block_members("MyBlock").unwrap().to_vec();
+    members.sort_by(|a, b| a.offset.cmp(&b.offset));
+    assert_eq!(members.len(), 2);
+
+    assert_eq!(members[0].name, "transform");
+    assert_eq!(members[0].ty, glium::uniforms::UniformType::FloatMat4);
+    assert_eq!(members[0].offset, 0);
+    assert_eq!(members[0].matrix_stride, 16);
+    assert!(!members[0].row_major);
+
+    assert_eq!(members[1].name, "weights[0]");
+    assert_eq!(members[1].ty, glium::uniforms::UniformType::Float);
+    assert_eq!(members[1].offset, 64);
+    assert_eq!(members[1].array_size, 4);
+    assert_eq!(members[1].array_stride, 16);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn get_uniform_blocks() {
     let display = support::build_display();
@@ -858,3 +913,97 @@ fn array_layout_offsets() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn geometry_shader_max_vertices_too_large() {
+    let display = support::build_display();
+
+    if !glium::program::is_geometry_shader_supported(&display) {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        Some("
+            #version 330
+
+            layout(points) in;
+            layout(points, max_vertices = 1000000) out;
+
+            void main() {
+                gl_Position = gl_in[0].gl_Position;
+                EmitVertex();
+            }
+        "));
+
+    match program {
+        Err(glium::program::ProgramCreationError::GeometryMaxVerticesTooLarge { declared, .. }) => {
+            assert_eq!(declared, 1000000);
+        },
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn geometry_shader_max_vertices_in_comments() {
+    let display = support::build_display();
+
+    if !glium::program::is_geometry_shader_supported(&display) {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        Some("
+            #version 330
+
+            // layout(points, max_vertices = 1000000) out;
+            /* layout(points, max_vertices = 1000000) out; */
+
+            layout(points) in;
+            layout(points, max_vertices = 1) out;
+
+            void main() {
+                gl_Position = gl_in[0].gl_Position;
+                EmitVertex();
+            }
+        "));
+
+    if let Err(err) = program {
+        panic!("{:?}", err);
+    }
+
+    display.assert_no_error(None);
+}
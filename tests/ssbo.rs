@@ -134,3 +134,90 @@ fn custom_bind_point() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn compute_shader_doubles_values() {
+    let display = support::build_display();
+
+    let program = glium::program::ComputeShader::from_source(&display, "
+        #version 430
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        layout(std140) buffer MyBlock {
+            vec4 values[16];
+        };
+
+        void main() {
+            values[gl_GlobalInvocationID.x] *= 2.0;
+        }
+    ");
+
+    let program = match program {
+        Ok(p) => p,
+        Err(glium::ProgramCreationError::ComputeShaderNotSupported) => {
+            assert!(!glium::program::ComputeShader::is_supported(&display));
+            return;
+        },
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        values: [[f32; 4]; 16],
+    }
+
+    implement_uniform_block!(Data, values);
+
+    let mut values = [[0.0; 4]; 16];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = [i as f32, 1.0, -2.0, 0.5];
+    }
+
+    let buffer = match glium::uniforms::UniformBuffer::new(&display, Data { values }) {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    program.execute(uniform! { MyBlock: &buffer }, 16, 1, 1);
+
+    let data = buffer.read().unwrap();
+    for (i, value) in data.values.iter().enumerate() {
+        assert_eq!(*value, [i as f32 * 2.0, 2.0, -4.0, 1.0]);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_storage_buffer_unsized() {
+    let display = support::build_display();
+
+    let program = match glium::program::ComputeShader::from_source(&display, "
+        #version 430
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        layout(std430, binding = 3) buffer MyBlock {
+            float values[];
+        };
+
+        void main() {
+            values[gl_GlobalInvocationID.x] = float(gl_GlobalInvocationID.x) * 3.0;
+        }
+    ") {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let buffer: glium::uniforms::ShaderStorageBuffer<[f32]> =
+        match glium::uniforms::ShaderStorageBuffer::empty_unsized(&display, 8 * 4) {
+            Err(_) => return,
+            Ok(b) => b
+        };
+
+    program.execute(uniform! { MyBlock: &buffer }, 8, 1, 1);
+
+    let data = buffer.read().unwrap();
+    assert_eq!(data, vec![0.0, 3.0, 6.0, 9.0, 12.0, 15.0, 18.0, 21.0]);
+
+    display.assert_no_error(None);
+}
@@ -368,3 +368,24 @@ fn mismatched_subroutines() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn subroutines_not_supported() {
+    let display = support::build_display();
+    if is_subroutine_supported(display.get_context()) {
+        return
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    let uniforms = uniform!(
+        Color: ("ColorBlue", ShaderStage::Fragment),
+    );
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(DrawError::SubroutinesNotSupported) => (),
+        _ => panic!("Drawing should have errored")
+    }
+
+    display.assert_no_error(None);
+}
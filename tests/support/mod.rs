@@ -24,6 +24,13 @@ use std::env;
 
 /// Builds a display for tests.
 pub fn build_display() -> Display<WindowSurface> {
+    build_display_with_debug(Default::default())
+}
+
+/// Builds a display for tests with a specific debug callback behavior.
+pub fn build_display_with_debug(debug: glium::debug::DebugCallbackBehavior)
+                                -> Display<WindowSurface>
+{
     let version = parse_version();
     let event_loop = EventLoopBuilder::new().build();
     let window_builder = WindowBuilder::new().with_visible(false);
@@ -59,7 +66,47 @@ pub fn build_display() -> Display<WindowSurface> {
     // Now we can create our surface, use it to make our context current and finally create our display
     let surface = unsafe { gl_config.display().create_window_surface(&gl_config, &attrs).unwrap() };
     let current_context = not_current_gl_context.unwrap().make_current(&surface).unwrap();
-    glium::Display::from_context_surface(current_context, surface).unwrap()
+    glium::Display::with_debug(current_context, surface, debug).unwrap()
+}
+
+/// Builds two displays with their own window that share the same context.
+pub fn build_shared_displays() -> (Display<WindowSurface>, Display<WindowSurface>) {
+    let version = parse_version();
+    let event_loop = EventLoopBuilder::new().build();
+    let window_builder = WindowBuilder::new().with_visible(false);
+    let config_template_builder = ConfigTemplateBuilder::new();
+    let display_builder = DisplayBuilder::new().with_window_builder(Some(window_builder));
+
+    let (window, gl_config) = display_builder
+        .build(&event_loop, config_template_builder, |mut configs| configs.next().unwrap())
+        .unwrap();
+    let window = window.unwrap();
+    let other_window = glutin_winit::finalize_window(&event_loop,
+                                                     WindowBuilder::new().with_visible(false),
+                                                     &gl_config).unwrap();
+
+    let context_attributes = ContextAttributesBuilder::new()
+        .with_context_api(version)
+        .build(Some(window.raw_window_handle()));
+    let not_current_gl_context = unsafe {
+        gl_config.display().create_context(&gl_config, &context_attributes).unwrap()
+    };
+
+    let build_surface = |window: &winit::window::Window| {
+        let attrs = SurfaceAttributesBuilder::<WindowSurface>::new().build(
+            window.raw_window_handle(),
+            NonZeroU32::new(800).unwrap(),
+            NonZeroU32::new(600).unwrap(),
+        );
+        unsafe { gl_config.display().create_window_surface(&gl_config, &attrs).unwrap() }
+    };
+
+    let surface = build_surface(&window);
+    let other_surface = build_surface(&other_window);
+    let current_context = not_current_gl_context.make_current(&surface).unwrap();
+    let display = glium::Display::new(current_context, surface).unwrap();
+    let other_display = glium::Display::new_shared(other_surface, &display);
+    (display, other_display)
 }
 
 
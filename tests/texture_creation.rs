@@ -177,6 +177,154 @@ fn compressed_texture_2d_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn texture_2d_array_from_images() {
+    let display = support::build_display();
+
+    let images = (0 .. 3u8).map(|layer| {
+        glium::texture::RawImage2d::from_raw_rgba(vec![layer * 100, 0, 0, 255].repeat(4), (2, 2))
+    }).collect::<Vec<_>>();
+
+    let texture = glium::texture::Texture2dArray::from_images(&display, &images).unwrap();
+
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+    assert_eq!(texture.get_array_size(), Some(3));
+
+    let layer: glium::texture::TextureAnyImage = texture.main_level().layer(2).unwrap().into();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = layer.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 2, height: 2
+    });
+    assert_eq!(data[1][1], (200, 0, 0, 255));
+
+    let mismatch = vec![
+        glium::texture::RawImage2d::from_raw_rgba(vec![0u8; 16], (2, 2)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![0u8; 32], (4, 2)),
+    ];
+    match glium::texture::Texture2dArray::from_images(&display, &mismatch) {
+        Err(glium::texture::TextureCreationError::InconsistentImages) => (),
+        _ => panic!(),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn cubemap_array_with_faces() {
+    use glium::texture::RawImage2d;
+
+    let display = support::build_display();
+
+    if !glium::texture::is_cubemap_arrays_supported(&display) {
+        return;
+    }
+
+    let layer = |value: u8| {
+        [0, 1, 2, 3, 4, 5].map(|face| {
+            RawImage2d::from_raw_rgba(vec![value + face; 8 * 8 * 4], (8, 8))
+        })
+    };
+
+    let texture = glium::texture::CubemapArray::with_faces(&display, vec![layer(0), layer(10)],
+                                                           glium::texture::MipmapsOption::NoMipmap)
+                                                           .unwrap();
+
+    assert_eq!(texture.dimensions(), 8);
+    assert_eq!(texture.array_size(), 2);
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    let wrong = vec![[0, 1, 2, 3, 4, 5].map(|_| RawImage2d::from_raw_rgba(vec![0u8; 8 * 4 * 4], (8, 4)))];
+    match glium::texture::CubemapArray::with_faces(&display, wrong,
+                                                   glium::texture::MipmapsOption::NoMipmap)
+    {
+        Err(glium::texture::TextureCreationError::InconsistentImages) => (),
+        a => panic!("{:?}", a.err()),
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_immutable_storage() {
+    use glium::{Api, Version};
+
+    let display = support::build_display();
+
+    if !(display.get_opengl_version() >= &Version(Api::Gl, 4, 2)) &&
+       !(display.get_opengl_version() >= &Version(Api::GlEs, 3, 0)) &&
+       !display.has_extension("GL_ARB_texture_storage")
+    {
+        return;
+    }
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::EmptyMipmaps, 64, 32).unwrap();
+    assert!(texture.has_immutable_storage());
+
+    let texture = glium::texture::Texture2dArray::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 16, 16, 4).unwrap();
+    assert!(texture.has_immutable_storage());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_view() {
+    use glium::texture::{TextureViewError, UncompressedUintFormat};
+
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::EmptyMipmaps, 64, 32).unwrap();
+
+    let view = match glium::texture::UnsignedTexture2d::view_of(&texture,
+                                                                UncompressedUintFormat::U8U8U8U8,
+                                                                1, 2, 0, 1)
+    {
+        Ok(v) => v,
+        Err(TextureViewError::NotSupported) | Err(TextureViewError::MutableStorage) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(view.get_width(), 32);
+    assert_eq!(view.get_height(), Some(16));
+    assert_eq!(view.get_mipmap_levels(), 2);
+
+    // the view shares the storage of the texture, which must remain valid after the drop
+    drop(texture);
+    let _ = view.get_internal_format();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 64, 32).unwrap();
+
+    match glium::texture::UnsignedTexture2d::view_of(&texture,
+                                                     UncompressedUintFormat::U16U16U16U16,
+                                                     0, 1, 0, 1)
+    {
+        Err(TextureViewError::IncompatibleFormat) => (),
+        a => panic!("{:?}", a.err()),
+    }
+
+    match texture.create_view(UncompressedUintFormat::U8U8U8U8.to_texture_format(), 1, 1, 0, 1) {
+        Err(TextureViewError::InvalidLevels) => (),
+        a => panic!("{:?}", a.err()),
+    }
+
+    match glium::texture::UnsignedTexture2dArray::view_of(&texture,
+                                                          UncompressedUintFormat::U8U8U8U8,
+                                                          0, 1, 0, 1)
+    {
+        Err(TextureViewError::IncompatibleDimensions) => (),
+        a => panic!("{:?}", a.err()),
+    }
+
+    display.assert_no_error(None);
+}
+
 macro_rules! empty_texture_test {
     ($test_name:ident, $tex_ty:ident, [$($dims:expr),+],
      $w:expr, $h:expr, $d:expr, $s:expr) =>
@@ -407,3 +555,148 @@ fn upload_from_pixel_buffer_inverted() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture2d_multisample_as_render_target() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dMultisample::empty(&display, 64, 64, 4) {
+        Err(_) => return,
+        Ok(tex) => tex
+    };
+
+    assert_eq!(texture.get_samples(), Some(4));
+    assert_eq!(texture.get_mipmap_levels(), 1);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture2d_multisample_too_many_samples() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2dMultisample::empty_with_format(&display,
+                        glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                        glium::texture::MipmapsOption::NoMipmap, 64, 64, 4096);
+
+    assert!(texture.is_err());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture2d_multisample_mipmaps() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2dMultisample::empty_with_mipmaps(&display,
+                        glium::texture::MipmapsOption::AutoGeneratedMipmaps, 64, 64, 4);
+
+    match texture {
+        Err(glium::texture::TextureCreationError::MipmapsNotSupported) => (),
+        _ => panic!()
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_with_mipmap_data() {
+    let display = support::build_display();
+
+    let levels = vec![
+        glium::texture::RawImage2d::from_raw_rgba(vec![255u8; 4 * 4 * 4], (4, 4)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![128u8; 2 * 2 * 4], (2, 2)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![0u8; 4], (1, 1)),
+    ];
+
+    let texture = glium::texture::Texture2d::with_mipmap_data(&display,
+                        glium::texture::UncompressedFloatFormat::U8U8U8U8, levels).unwrap();
+
+    assert_eq!(texture.get_width(), 4);
+    assert_eq!(texture.get_height(), Some(4));
+    assert_eq!(texture.get_mipmap_levels(), 3);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_with_mipmap_data_wrong_dimensions() {
+    let display = support::build_display();
+
+    let levels = vec![
+        glium::texture::RawImage2d::from_raw_rgba(vec![255u8; 4 * 4 * 4], (4, 4)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![128u8; 3 * 3 * 4], (3, 3)),
+    ];
+
+    match glium::texture::Texture2d::with_mipmap_data(&display,
+                        glium::texture::UncompressedFloatFormat::U8U8U8U8, levels)
+    {
+        Err(glium::texture::TextureCreationError::InconsistentImages) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_with_mipmap_data_too_many_levels() {
+    let display = support::build_display();
+
+    let levels = vec![
+        glium::texture::RawImage2d::from_raw_rgba(vec![255u8; 2 * 2 * 4], (2, 2)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![128u8; 4], (1, 1)),
+        glium::texture::RawImage2d::from_raw_rgba(vec![0u8; 4], (1, 1)),
+    ];
+
+    match glium::texture::Texture2d::with_mipmap_data(&display,
+                        glium::texture::UncompressedFloatFormat::U8U8U8U8, levels)
+    {
+        Err(glium::texture::TextureCreationError::InconsistentImages) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_from_image() {
+    let display = support::build_display();
+
+    // red on the top row, blue on the bottom row
+    let image = image::RgbImage::from_fn(2, 2, |_, y| {
+        if y == 0 { image::Rgb([255, 0, 0]) } else { image::Rgb([0, 0, 255]) }
+    });
+
+    let texture = glium::texture::Texture2d::from_image(&display,
+                                                        &image::DynamicImage::ImageRgb8(image))
+                                                        .unwrap();
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+
+    // the first row of the texture data is the bottom of the image
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 0, 255, 255));
+    assert_eq!(read_back[1][1], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_from_luma_image() {
+    let display = support::build_display();
+
+    let image = image::GrayImage::from_pixel(4, 4, image::Luma([128]));
+    let texture = glium::texture::Texture2d::from_image(&display,
+                                                        &image::DynamicImage::ImageLuma8(image))
+                                                        .unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[2][2], (128, 128, 128, 255));
+
+    display.assert_no_error(None);
+}
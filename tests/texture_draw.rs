@@ -108,3 +108,77 @@ texture_draw_test!(texture_2d_draw, Texture2d, [1024, 1024], "vec4",
                    "vec4(1.0, 0.0, 1.0, 0.0)", (255, 0, 255, 0));
 unowned_draw_test!(texture_2d_draw_unowned, Texture2d, UncompressedFloatFormat, F32F32F32F32, [1024, 1024], "vec4",
                    "vec4(1.0, 0.0, 1.0, 0.0)", (255, 0, 255, 0));
+
+#[test]
+fn float_accumulation_across_frames() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform float contribution;
+            out vec4 color;
+
+            void main() {
+                color = vec4(contribution, 1.0, 0.0, 0.5);
+            }
+        ",
+        None);
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = match glium::texture::Texture2d::empty_with_format(&display,
+                                          glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                                          glium::texture::MipmapsOption::NoMipmap, 16, 16)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        blend: glium::Blend::additive(),
+        .. Default::default()
+    };
+
+    // the content of the texture must survive the frames in between
+    for i in 0 .. 8 {
+        let frame = display.draw();
+        frame.finish().unwrap();
+
+        texture.as_surface().draw(&vb, &ib, &program,
+                                  &uniform!{ contribution: 0.125 * (i + 1) as f32 },
+                                  &params).unwrap();
+    }
+
+    let pixel_buffer = unsafe {
+        texture.unchecked_read_to_pixel_buffer::<(f32, f32, f32, f32)>()
+    };
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = match pixel_buffer.read_as_texture_2d() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(*pixel, (4.5, 8.0, 0.0, 4.0));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -40,6 +40,31 @@ fn texture_2d_read_pixelbuffer() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn pixel_unpack_buffer_binding_is_restored() {
+    let display = support::build_display();
+
+    let pixel_buffer = glium::pixel_buffer::PixelBuffer::<(u8, u8, u8, u8)>::new_empty(&display, 16);
+    let slice = pixel_buffer.slice(4 ..).unwrap();
+
+    let offset = display.with_pixel_unpack_buffer(slice.as_slice_any(), |offset| offset);
+    assert_eq!(offset, 4 * 4);
+
+    // GL_PIXEL_UNPACK_BUFFER_BINDING
+    assert_eq!(display.get_integer(0x88EF), 0);
+
+    // uploading from client memory must not be affected by the pixel buffer
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+    ]).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (4, 8, 16, 255));
+
+    display.assert_no_error(None);
+}
+
 macro_rules! read_texture_test {
     ($test_name:ident, $tex_ty:ident, $data_ty:ty, $data:expr) => (
         #[test]
@@ -134,3 +159,206 @@ read_texture_test!(read_unsignedtexture2darray, UnsignedTexture2dArray, (u8, u8,
 read_texture_test!(read_unsignedtexture3d, UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn texture_2d_read_bgra() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 8u8, 16u8, 32u8)],
+        vec![(32u8, 64u8, 128u8, 255u8), (32u8, 16u8, 4u8, 2u8)],
+    ]).unwrap();
+
+    let read_back: Vec<Vec<glium::texture::Bgra>> = match texture.read_bgra() {
+        Ok(r) => r,
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(read_back[0][0], glium::texture::Bgra(2, 1, 0, 3));
+    assert_eq!(read_back[0][1], glium::texture::Bgra(16, 8, 4, 32));
+    assert_eq!(read_back[1][0], glium::texture::Bgra(128, 64, 32, 255));
+    assert_eq!(read_back[1][1], glium::texture::Bgra(4, 16, 32, 2));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_read_single_channel() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 8u8, 16u8, 32u8)],
+        vec![(32u8, 64u8, 128u8, 255u8), (32u8, 16u8, 4u8, 2u8)],
+    ]).unwrap();
+
+    let read_back: Vec<Vec<u8>> = match texture.read_with_format() {
+        Ok(r) => r,
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(read_back, vec![vec![0, 4], vec![32, 32]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_read_r8_rg8_r16() {
+    use glium::texture::{MipmapsOption, UncompressedFloatFormat};
+
+    let display = support::build_display();
+
+    let r8_data = vec![vec![0u8, 64u8], vec![128u8, 255u8]];
+    let rg8_data = vec![vec![(0u8, 1u8), (64u8, 65u8)], vec![(128u8, 129u8), (254u8, 255u8)]];
+    let r16_data = vec![vec![0u16, 1000u16], vec![30000u16, 65535u16]];
+
+    let r8 = glium::texture::Texture2d::with_format(&display, r8_data.clone(),
+                                                    UncompressedFloatFormat::U8,
+                                                    MipmapsOption::NoMipmap);
+    let rg8 = glium::texture::Texture2d::with_format(&display, rg8_data.clone(),
+                                                     UncompressedFloatFormat::U8U8,
+                                                     MipmapsOption::NoMipmap);
+    let r16 = glium::texture::Texture2d::with_format(&display, r16_data.clone(),
+                                                     UncompressedFloatFormat::U16,
+                                                     MipmapsOption::NoMipmap);
+
+    let (r8, rg8, r16) = match (r8, rg8, r16) {
+        (Ok(r8), Ok(rg8), Ok(r16)) => (r8, rg8, r16),
+        _ => return
+    };
+
+    match r8.read_with_format::<Vec<Vec<u8>>, u8>() {
+        Ok(read_back) => assert_eq!(read_back, r8_data),
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        e => { e.unwrap(); }
+    }
+
+    match rg8.read_with_format::<Vec<Vec<(u8, u8)>>, (u8, u8)>() {
+        Ok(read_back) => assert_eq!(read_back, rg8_data),
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        e => { e.unwrap(); }
+    }
+
+    match r16.read_with_format::<Vec<Vec<u16>>, u16>() {
+        Ok(read_back) => assert_eq!(read_back, r16_data),
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        e => { e.unwrap(); }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn unsigned_texture_2d_read_incompatible_format() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::UnsignedTexture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 8u8, 16u8, 32u8)],
+    ]) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.read_with_format::<Vec<Vec<f32>>, f32>() {
+        Err(glium::ReadError::IncompatibleFormat) => (),
+        Err(glium::ReadError::OutputFormatNotSupported) => return,
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_upload_bgra() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2d::new(&display, vec![
+        vec![glium::texture::Bgra(2, 1, 0, 3), glium::texture::Bgra(16, 8, 4, 32)],
+        vec![glium::texture::Bgra(128, 64, 32, 255), glium::texture::Bgra(4, 16, 32, 2)],
+    ]) {
+        Err(glium::texture::TextureCreationError::FormatNotSupported) => return,
+        t => t.unwrap()
+    };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(read_back[0][0], (0, 1, 2, 3));
+    assert_eq!(read_back[0][1], (4, 8, 16, 32));
+    assert_eq!(read_back[1][0], (32, 64, 128, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 2));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pixel_buffer_pool_returns_previous_frame() {
+    use glium::Surface;
+    use glium::texture::pixel_buffer::PixelBufferPool;
+
+    let display = support::build_display();
+    let texture = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+    let mut pool = PixelBufferPool::new(&display, 4);
+
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+    let first: Option<Vec<Vec<(u8, u8, u8, u8)>>> = pool.read(&texture).unwrap();
+    assert!(first.is_none());
+
+    // the first read only starts a copy, reading the buffer back happens from now on
+    texture.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+    let second: Option<Vec<Vec<(u8, u8, u8, u8)>>> = match pool.read(&texture) {
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        r => r.unwrap()
+    };
+    assert_eq!(second.unwrap()[1][1], (255, 0, 0, 255));
+
+    texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    let third: Option<Vec<Vec<(u8, u8, u8, u8)>>> = pool.read(&texture).unwrap();
+    assert_eq!(third.unwrap()[0][0], (0, 255, 0, 255));
+
+    let last: Option<Vec<Vec<(u8, u8, u8, u8)>>> = pool.flush().unwrap();
+    assert_eq!(last.unwrap()[0][1], (0, 0, 255, 255));
+
+    let empty: Option<Vec<Vec<(u8, u8, u8, u8)>>> = pool.flush().unwrap();
+    assert!(empty.is_none());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_1d_write_read() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture1d::new(&display, vec![
+        (0u8, 1u8, 2u8, 4u8), (8u8, 16u8, 32u8, 64u8), (128u8, 64u8, 32u8, 16u8),
+    ]) {
+        Err(_) => return,
+        Ok(t) => t
+    };
+
+    texture.write(1, vec![(1u8, 2u8, 4u8, 8u8), (2u8, 4u8, 8u8, 16u8)]);
+
+    let read_back: Vec<(u8, u8, u8, u8)> = texture.read();
+    assert_eq!(read_back, vec![(0, 1, 2, 4), (1, 2, 4, 8), (2, 4, 8, 16)]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_read_to_image() {
+    let display = support::build_display();
+
+    // the first row of the data is the bottom of the texture
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(255u8, 0u8, 0u8, 255u8), (255u8, 0u8, 0u8, 255u8)],
+        vec![(0u8, 0u8, 255u8, 255u8), (0u8, 0u8, 255u8, 255u8)],
+    ]).unwrap();
+
+    let image = texture.read_to_image();
+    assert_eq!(image.dimensions(), (2, 2));
+    assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255, 255]);
+    assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 255]);
+
+    display.assert_no_error(None);
+}
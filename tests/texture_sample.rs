@@ -130,3 +130,59 @@ fn bindless_texture() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_2d_swizzle_single_channel() {
+    use glium::texture::Swizzle;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = match glium::texture::Texture2d::with_format(&display,
+        vec![vec![128u8, 128u8], vec![128u8, 128u8]],
+        glium::texture::UncompressedFloatFormat::U8, glium::texture::MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.set_swizzle([Swizzle::Red, Swizzle::Red, Swizzle::Red, Swizzle::One]) {
+        Ok(()) => (),
+        Err(glium::texture::SwizzleNotSupportedError) => return,
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(128, 128, 128, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
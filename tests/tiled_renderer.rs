@@ -0,0 +1,164 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::backend::Facade;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::tiled_renderer::TiledRenderer;
+
+mod support;
+
+/// Builds a red square covering the center of the image, with the `tile` matrix applied.
+fn build_square<F: ?Sized>(facade: &F) -> (glium::VertexBuffer<Vertex>, glium::Program)
+    where F: Facade
+{
+    let vb = glium::VertexBuffer::new(facade, &[
+        Vertex { position: [-0.5,  0.5] }, Vertex { position: [0.5,  0.5] },
+        Vertex { position: [-0.5, -0.5] }, Vertex { position: [0.5, -0.5] },
+    ]).unwrap();
+
+    let program = program!(facade,
+        110 => {
+            vertex: "
+                #version 110
+
+                uniform mat4 tile;
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = tile * vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                uniform highp mat4 tile;
+                attribute highp vec2 position;
+
+                void main() {
+                    gl_Position = tile * vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    (vb, program)
+}
+
+#[derive(Copy, Clone)]
+struct Vertex {
+    position: [f32; 2],
+}
+
+implement_vertex!(Vertex, position);
+
+fn expected_pixel(x: usize, y: usize) -> (u8, u8, u8, u8) {
+    if (64 .. 192).contains(&x) && (64 .. 192).contains(&y) {
+        (255, 0, 0, 255)
+    } else {
+        (0, 0, 0, 255)
+    }
+}
+
+#[test]
+fn tiles_cover_image() {
+    let renderer = TiledRenderer::new((250, 130), (100, 100));
+    assert_eq!(renderer.get_tiles_count(), (3, 2));
+
+    let tiles = renderer.tiles().map(|tile| tile.rect).collect::<Vec<_>>();
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(tiles[0], glium::Rect { left: 0, bottom: 0, width: 100, height: 100 });
+    assert_eq!(tiles[2], glium::Rect { left: 200, bottom: 0, width: 50, height: 100 });
+    assert_eq!(tiles[5], glium::Rect { left: 200, bottom: 100, width: 50, height: 30 });
+}
+
+#[test]
+fn render_tiles_and_stitch() {
+    let display = support::build_display();
+    let (vb, program) = build_square(&display);
+
+    let renderer = TiledRenderer::new((256, 256), (100, 100));
+    let tile_texture = glium::Texture2d::empty(&display, 100, 100).unwrap();
+    let mut image = vec![vec![(0u8, 0u8, 0u8, 0u8); 256]; 256];
+
+    renderer.render(|tile| {
+        let params = glium::DrawParameters {
+            viewport: Some(tile.viewport()),
+            .. Default::default()
+        };
+
+        let mut surface = tile_texture.as_surface();
+        surface.clear_color(0.0, 0.0, 0.0, 1.0);
+        surface.draw(&vb, &NoIndices(PrimitiveType::TriangleStrip), &program,
+                     &uniform! { tile: tile.projection() }, &params)?;
+
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = tile_texture.read();
+        let (left, width) = (tile.rect.left as usize, tile.rect.width as usize);
+        for (y, row) in data.iter().take(tile.rect.height as usize).enumerate() {
+            image[tile.rect.bottom as usize + y][left .. left + width]
+                .copy_from_slice(&row[.. width]);
+        }
+
+        Ok::<_, glium::DrawError>(())
+    }).unwrap();
+
+    for (y, row) in image.iter().enumerate() {
+        for (x, pixel) in row.iter().enumerate() {
+            assert_eq!(*pixel, expected_pixel(x, y), "pixel ({}, {})", x, y);
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn render_tiles_with_scissor() {
+    let display = support::build_display();
+    let (vb, program) = build_square(&display);
+
+    let texture = glium::Texture2d::empty(&display, 256, 256).unwrap();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let renderer = TiledRenderer::new((256, 256), (100, 100));
+    let identity = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0f32],
+    ];
+
+    // only the first tile is drawn
+    let tile = renderer.tiles().next().unwrap();
+    texture.as_surface().draw(&vb, &NoIndices(PrimitiveType::TriangleStrip), &program,
+                              &uniform! { tile: identity },
+                              &tile.draw_parameters(&Default::default())).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for (y, row) in data.iter().enumerate() {
+        for (x, pixel) in row.iter().enumerate() {
+            if x < 100 && y < 100 {
+                assert_eq!(*pixel, expected_pixel(x, y), "pixel ({}, {})", x, y);
+            } else {
+                assert_eq!(*pixel, (0, 0, 0, 255), "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    display.assert_no_error(None);
+}
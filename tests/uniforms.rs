@@ -2,6 +2,10 @@
 extern crate glium;
 
 use glium::Surface;
+use glium::debug::DebugCallbackBehavior;
+
+use std::cell::RefCell;
+use std::rc::Rc;
 
 mod support;
 
@@ -178,6 +182,67 @@ fn uniform_wrong_type() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn unset_uniforms_warning() {
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let display = support::build_display_with_debug(DebugCallbackBehavior::Custom {
+        callback: {
+            let messages = messages.clone();
+            Box::new(move |source, _, _, _, _, message: &str| {
+                if let glium::debug::Source::Application = source {
+                    messages.borrow_mut().push(message.to_owned());
+                }
+            })
+        },
+        synchronous: true,
+    });
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            uniform vec2 offset;
+
+            void main() {
+                gl_Position = vec4(position + offset, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = uniform! { color: [1.0, 0.0, 0.0, 0.5f32] };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+    assert!(messages.borrow().is_empty());
+
+    display.set_unset_uniforms_warnings(true);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    {
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("offset"));
+        assert!(!messages[0].contains("color"));
+    }
+
+    let uniforms = uniform! { color: [1.0, 0.0, 0.0, 0.5f32], offset: [0.0, 0.0f32] };
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+    assert_eq!(messages.borrow().len(), 1);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn uniforms_dynamic_single_value() {
     let display = support::build_display();
@@ -302,6 +367,72 @@ fn uniforms_dynamic_ignore_inactive_uniforms() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn uniforms_explicit_location() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 430
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 430
+
+            layout(location = 3) uniform vec4 color1;
+            uniform vec4 color2;
+            out vec4 f_color;
+
+            void main() {
+                f_color = color1 + color2;
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let uniforms = glium::uniforms::UniformsStorage::new("color2", [0.3, 0.0, 0.0, 0.5f32])
+        .add_at_location(3, [0.7, 0.0, 0.0, 0.5f32]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniforms_negative_location() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    // -1 is ignored
+    let uniforms = glium::uniforms::LocatedUniformsStorage::new(-1, 0.5f32);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let uniforms = glium::uniforms::LocatedUniformsStorage::new(-2, 0.5f32);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::InvalidUniformLocation { location: -2 }) => (),
+        e => panic!("{:?}", e),
+    }
+
+    display.assert_no_error(None);
+}
+
 macro_rules! uniform_test(
     ($name:ident, $glsl_ty:expr, $value:expr) => (
         #[test]
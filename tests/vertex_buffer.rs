@@ -90,3 +90,315 @@ fn transform_feedback() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn transform_feedback_separate() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Position {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Position, output_val);
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Doubled {
+        output_doubled: (f32, f32),
+    }
+
+    implement_vertex!(Doubled, output_doubled);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+            varying vec2 output_doubled;
+
+            void main() {
+                output_val = position;
+                output_doubled = position * 2.0;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string(), "output_doubled".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let mut out_positions: glium::VertexBuffer<Position> =
+        glium::VertexBuffer::empty(&display, 6).unwrap();
+    let mut out_doubled: glium::VertexBuffer<Doubled> =
+        glium::VertexBuffer::empty(&display, 6).unwrap();
+
+    // drawing without a buffer for the second varying is an error
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_positions).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        let mut frame = display.draw();
+        match frame.draw(&vb, &ib, &program, &uniform!{}, &params) {
+            Err(glium::DrawError::TransformFeedbackBuffersMismatch) => (),
+            e => panic!("{:?}", e)
+        }
+        frame.finish().unwrap();
+    }
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_positions).unwrap()
+                                                              .add_buffer(&mut out_doubled).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        let mut frame = display.draw();
+        frame.draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+        frame.finish().unwrap();
+    }
+
+    let positions = match out_positions.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+    let doubled = out_doubled.read().unwrap();
+
+    assert_eq!(positions[0].output_val, (-1.0, 1.0));
+    assert_eq!(positions[5].output_val, (1.0, -1.0));
+    for (p, d) in positions.iter().zip(doubled.iter()) {
+        assert_eq!(d.output_doubled, (p.output_val.0 * 2.0, p.output_val.1 * 2.0));
+    }
+
+    display.assert_no_error(None);
+}
+
+fn build_transform_feedback_program<F: ?Sized>(display: &F) -> Option<glium::Program>
+    where F: glium::backend::Facade
+{
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    match glium::Program::new(display, source) {
+        Ok(p) => Some(p),
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => None,
+        Err(e) => panic!("{:?}", e)
+    }
+}
+
+#[test]
+fn transform_feedback_primitives_written() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = match build_transform_feedback_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12).unwrap();
+
+    let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                               &mut out_buffer).unwrap()
+                                                          .count_primitives_written();
+
+    let params = glium::DrawParameters {
+        transform_feedback: Some(&session),
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+
+    if let Some(written) = session.primitives_written() {
+        assert_eq!(written, 4);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_transform_feedback() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let program = match build_transform_feedback_program(&display) {
+        Some(p) => p,
+        None => return
+    };
+
+    // the buffer is larger than what is written, only the captured vertices must be drawn
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_buffer).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            draw_primitives: false,
+            .. Default::default()
+        };
+
+        match texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params) {
+            Ok(_) => (),
+            Err(glium::DrawError::TransformFeedbackNotSupported) => return,
+            e => e.unwrap()
+        };
+    }
+
+    let draw_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 output_val;
+
+                void main() {
+                    gl_Position = vec4(output_val, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw_transform_feedback(&out_buffer,
+                                                       glium::index::PrimitiveType::TrianglesList,
+                                                       &draw_program, &uniform!{},
+                                                       &Default::default())
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::DrawTransformFeedbackNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn streaming_vertex_buffer_regions() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let mut buffer = glium::vertex::StreamingVertexBuffer::new(&display, 2).unwrap();
+    assert_eq!(buffer.region_len(), 2);
+    assert_eq!(buffer.regions(), 3);
+    assert_eq!(buffer.get_buffer().len(), 6);
+
+    for i in 0 .. 3 {
+        let value = i as f32;
+        buffer.next_region().write(&[Vertex { position: [value, value] }; 2]);
+    }
+
+    let data = buffer.get_buffer().read().unwrap();
+    for (i, vertex) in data.iter().enumerate() {
+        let value = (i / 2) as f32;
+        assert_eq!(vertex, &Vertex { position: [value, value] });
+    }
+
+    display.assert_no_error(None);
+}
@@ -447,6 +447,67 @@ fn attributes_marker_indices() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn draw_instanced_empty() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 140
+
+            void main() {
+                // each instance covers one half of the screen
+                float left = gl_InstanceID == 0 ? -1.0 : 0.0;
+                float x = (gl_VertexID == 0 || gl_VertexID == 2) ? left : left + 1.0;
+                float y = gl_VertexID < 2 ? 1.0 : -1.0;
+                gl_Position = vec4(x, y, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 140
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_instanced_empty(&program, 4, 2, PrimitiveType::TriangleStrip,
+                                              &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_instanced_empty_with_attributes() {
+    let display = support::build_display();
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw_instanced_empty(&program, 4, 2, PrimitiveType::TriangleStrip,
+                                                    &uniform!{}, &Default::default())
+    {
+        Err(glium::DrawError::UnexpectedAttribute { ref name }) if name == "position" => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn instancing() {
     let display = support::build_display();
@@ -660,6 +721,85 @@ fn per_instance_length_mismatch() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn per_instance_attribute_collision() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let buffer1 = glium::VertexBuffer::new(&display,
+        &[
+            Vertex { position: [-1.0,  1.0] },
+            Vertex { position: [ 1.0,  1.0] },
+            Vertex { position: [-1.0, -1.0] },
+            Vertex { position: [ 1.0, -1.0] },
+        ]
+    ).unwrap();
+
+    let buffer2 = glium::VertexBuffer::new(&display, &[Vertex { position: [0.0, 0.0] }]).unwrap();
+    let buffer2 = match buffer2.per_instance() {
+        Ok(b) => b,
+        Err(_) => return
+    };
+
+    let index_buffer = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                               &[0u16, 1, 2, 3]).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        }).unwrap();
+
+    let mut frame = display.draw();
+    match frame.draw((&buffer1, buffer2), &index_buffer, &program, &uniform!{},
+                     &Default::default())
+    {
+        Err(glium::DrawError::VertexAttributeCollision { ref name }) if name == "position" => (),
+        a => panic!("{:?}", a)
+    }
+
+    frame.finish().unwrap();
+    display.assert_no_error(None);
+}
+
 #[test]
 fn normalized_vertex() {
         let display = support::build_display();